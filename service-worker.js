@@ -1,9 +1,11 @@
-const cacheName = 'recoder-v1';
+const cacheName = 'recoder-v2';
 const filesToCache = [
   '/',
   '/index.html',
   '/recoder.js',
   '/recoder_bg.wasm',
+  '/worker.js',
+  '/worker_bg.wasm',
   '/pkg/recoder.js',
   '/pkg/recoder_bg.wasm',
 ];
@@ -1,23 +1,24 @@
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
-use crate::components::{Hashing, Header, TextEncoding, Textarea};
+use crate::components::{
+    CryptoTools, Encryption, Hashing, Header, Passwords, Signatures, TextEncoding, Textarea,
+};
 
 pub enum Msg {
     SetText(String),
     SetCategory(Category),
 }
 
-#[derive(Debug, Eq, PartialEq, Clone)]
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
 pub enum Category {
+    #[default]
     Encoding,
     Hashing,
-}
-
-impl Default for Category {
-    fn default() -> Self {
-        Category::Encoding
-    }
+    CryptoTools,
+    Passwords,
+    Encryption,
+    Signatures,
 }
 
 #[derive(Default, Debug)]
@@ -57,6 +58,10 @@ impl Component for App {
             category_el.map(|btn| match btn.value().as_str() {
                 "encoding" => Msg::SetCategory(Category::Encoding),
                 "hashing" => Msg::SetCategory(Category::Hashing),
+                "crypto-tools" => Msg::SetCategory(Category::CryptoTools),
+                "passwords" => Msg::SetCategory(Category::Passwords),
+                "encryption" => Msg::SetCategory(Category::Encryption),
+                "signatures" => Msg::SetCategory(Category::Signatures),
                 _ => Msg::SetCategory(Category::Encoding),
             })
         });
@@ -90,6 +95,46 @@ impl Component for App {
                                 />
                                 <span>{ "Hashing/Crypto" }</span>
                             </label>
+                            <label class="form-radio">
+                                <input
+                                    type="radio"
+                                    name="category"
+                                    value="crypto-tools"
+                                    checked={self.category == Category::CryptoTools}
+                                    onchange={&on_category_click}
+                                />
+                                <span>{ "Crypto Tools" }</span>
+                            </label>
+                            <label class="form-radio">
+                                <input
+                                    type="radio"
+                                    name="category"
+                                    value="passwords"
+                                    checked={self.category == Category::Passwords}
+                                    onchange={&on_category_click}
+                                />
+                                <span>{ "Passwords" }</span>
+                            </label>
+                            <label class="form-radio">
+                                <input
+                                    type="radio"
+                                    name="category"
+                                    value="encryption"
+                                    checked={self.category == Category::Encryption}
+                                    onchange={&on_category_click}
+                                />
+                                <span>{ "Encryption" }</span>
+                            </label>
+                            <label class="form-radio">
+                                <input
+                                    type="radio"
+                                    name="category"
+                                    value="signatures"
+                                    checked={self.category == Category::Signatures}
+                                    onchange={&on_category_click}
+                                />
+                                <span>{ "Signatures" }</span>
+                            </label>
                         </div>
                     </div>
                     <div class="row">
@@ -99,6 +144,18 @@ impl Component for App {
                         else if self.category == Category::Hashing {
                             <Hashing input={self.input.clone()}/>
                         }
+                        else if self.category == Category::CryptoTools {
+                            <CryptoTools input={self.input.clone()}/>
+                        }
+                        else if self.category == Category::Passwords {
+                            <Passwords input={self.input.clone()}/>
+                        }
+                        else if self.category == Category::Encryption {
+                            <Encryption input={self.input.clone()}/>
+                        }
+                        else if self.category == Category::Signatures {
+                            <Signatures input={self.input.clone()}/>
+                        }
                     </div>
                 </main>
                 <footer class="footer">
@@ -0,0 +1,6 @@
+use gloo_worker::Registrable;
+use recoder::worker::BackgroundWorker;
+
+fn main() {
+    BackgroundWorker::registrar().register();
+}
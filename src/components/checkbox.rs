@@ -0,0 +1,42 @@
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::{Event, HtmlInputElement};
+use yew::prelude::*;
+
+fn create_default_on_change_callback() -> Callback<bool> {
+    Callback::from(|_| ())
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub checked: bool,
+    pub label: String,
+    #[prop_or_else(create_default_on_change_callback)]
+    pub on_change: Callback<bool>,
+}
+
+fn get_checked_from_event(e: Event) -> bool {
+    let event_target = e.target().unwrap_throw();
+    let target: HtmlInputElement = event_target.dyn_into().unwrap_throw();
+    target.checked()
+}
+
+/// Controlled Checkbox Component
+#[function_component(Checkbox)]
+pub fn checkbox(props: &Props) -> Html {
+    let Props {
+        checked,
+        label,
+        on_change,
+    } = props.clone();
+
+    let onchange = Callback::from(move |event: Event| {
+        on_change.emit(get_checked_from_event(event));
+    });
+
+    html! {
+        <label class="checkbox">
+            <input type="checkbox" {checked} {onchange} />
+            { label }
+        </label>
+    }
+}
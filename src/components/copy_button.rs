@@ -0,0 +1,46 @@
+use wasm_bindgen::{prelude::wasm_bindgen, JsValue};
+use web_sys::MouseEvent;
+use yew::prelude::*;
+use yew_icons::{Icon, IconId};
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub value: String,
+    #[prop_or("Copy output to clipboard".to_string())]
+    pub label: String,
+}
+
+/// Button that copies `value` to the clipboard
+#[function_component(CopyButton)]
+pub fn copy_button(props: &Props) -> Html {
+    let Props { value, label } = props.clone();
+
+    let onclick = move |_: MouseEvent| {
+        let v = value.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            copy_to_clipboard(v).await.unwrap();
+        });
+    };
+
+    html! {
+        <button type="button" class="button-icon" aria-label={label} {onclick}>
+            <Icon icon_id={IconId::FeatherCopy} />
+        </button>
+    }
+}
+
+#[wasm_bindgen(inline_js=r#"
+export function copy_to_clipboard(value) {
+    try {
+        return window.navigator.clipboard.writeText(value);
+    } catch(e) {
+        console.log(e);
+        return Promise.reject(e)
+    }
+}
+"#)]
+#[rustfmt::skip]
+extern "C" { 
+    #[wasm_bindgen(catch)]
+    async fn copy_to_clipboard(value: String) -> Result<(), JsValue>;
+}
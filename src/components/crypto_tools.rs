@@ -0,0 +1,1177 @@
+use crate::components::{CopyButton, InputEncodingRadios, Select, TextInput, Textarea};
+use crate::engine::{
+    decode_der_input, decode_input, decode_pgp, format_asn1, format_digest, format_pgp_packets,
+    hkdf_expand, hkdf_extract, parse_asn1, parse_pgp_packets, parse_ssh_public_keys, parse_x509,
+    Argon2Variant, DigestFormat, EcdhCurve, HashAlgorithm, InputEncoding, KdfAlgorithm, KeyFormat,
+    KeyPair, KeyType, PgpArmor, Validity, X509Kind,
+};
+use crate::worker::{BackgroundWorker, WorkerRequest, WorkerResponse, WORKER_PATH};
+use gloo_worker::{Spawnable, WorkerBridge};
+use std::collections::VecDeque;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[allow(clippy::enum_variant_names)]
+pub enum Msg {
+    SetTool(Tool),
+    SetKeyType(KeyType),
+    SetKeyFormat(KeyFormat),
+    GenerateKey,
+    WorkerResponse(WorkerResponse),
+    SetEcdhCurve(EcdhCurve),
+    SetEcdhParam(EcdhParam, String),
+    SetEcdhEncoding(EcdhParam, InputEncoding),
+    SetEcdhHash(HashAlgorithm),
+    SetFormat(DigestFormat),
+    SetKdf(Kdf),
+    SetKdfParam(KdfParam, String),
+    SetKdfEncoding(KdfParam, InputEncoding),
+    SetKdfHash(HashAlgorithm),
+    SetArgon2Variant(Argon2Variant),
+    DeriveKey,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum Tool {
+    #[default]
+    KeyGeneration,
+    Ecdh,
+    Kdf,
+    Asn1,
+    X509,
+    Ssh,
+    Pgp,
+}
+
+impl Tool {
+    pub const ALL: [Tool; 7] = [
+        Tool::KeyGeneration,
+        Tool::Ecdh,
+        Tool::Kdf,
+        Tool::Asn1,
+        Tool::X509,
+        Tool::Ssh,
+        Tool::Pgp,
+    ];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            Tool::KeyGeneration => "key-generation",
+            Tool::Ecdh => "ecdh",
+            Tool::Kdf => "kdf",
+            Tool::Asn1 => "asn1",
+            Tool::X509 => "x509",
+            Tool::Ssh => "ssh",
+            Tool::Pgp => "pgp",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|tool| tool.id() == id)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Tool::KeyGeneration => "Key generation",
+            Tool::Ecdh => "ECDH / HKDF",
+            Tool::Kdf => "Key derivation",
+            Tool::Asn1 => "ASN.1",
+            Tool::X509 => "X.509",
+            Tool::Ssh => "SSH keys",
+            Tool::Pgp => "OpenPGP",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum EcdhParam {
+    PrivateKey,
+    PublicKey,
+    Salt,
+    Info,
+    Length,
+}
+
+/// Key agreement inputs and the HKDF applied to its shared secret
+#[derive(Debug, PartialEq)]
+struct EcdhConfig {
+    curve: EcdhCurve,
+    private_key: String,
+    public_key: String,
+    hash: HashAlgorithm,
+    salt: String,
+    salt_encoding: InputEncoding,
+    info: String,
+    info_encoding: InputEncoding,
+    length: String,
+}
+
+impl Default for EcdhConfig {
+    fn default() -> Self {
+        Self {
+            curve: EcdhCurve::default(),
+            private_key: String::default(),
+            public_key: String::default(),
+            hash: HashAlgorithm::Sha256,
+            salt: String::default(),
+            salt_encoding: InputEncoding::default(),
+            info: String::default(),
+            info_encoding: InputEncoding::default(),
+            length: String::default(),
+        }
+    }
+}
+
+impl EcdhConfig {
+    /// Shared secret, HKDF pseudorandom key and HKDF output. `None` until
+    /// both keys are entered.
+    fn derive(&self) -> Option<Result<[Vec<u8>; 3], String>> {
+        if self.private_key.trim().is_empty() || self.public_key.trim().is_empty() {
+            return None;
+        }
+        Some(self.try_derive())
+    }
+
+    fn try_derive(&self) -> Result<[Vec<u8>; 3], String> {
+        let length = match self.length.trim() {
+            "" => self.hash.output_size(),
+            length => length
+                .parse::<usize>()
+                .ok()
+                .filter(|length| *length > 0)
+                .ok_or_else(|| "Output length must be a positive number".to_string())?,
+        };
+        let salt = decode_input(&self.salt, &self.salt_encoding)?;
+        let info = decode_input(&self.info, &self.info_encoding)?;
+        let secret = self
+            .curve
+            .shared_secret(&self.private_key, &self.public_key)?;
+        let prk = hkdf_extract(self.hash, &salt, &secret)?;
+        let okm = hkdf_expand(self.hash, &prk, &info, length)?;
+        Ok([secret, prk, okm])
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum Kdf {
+    #[default]
+    Hkdf,
+    Pbkdf2,
+    Scrypt,
+    Argon2,
+}
+
+impl Kdf {
+    pub const ALL: [Kdf; 4] = [Kdf::Hkdf, Kdf::Pbkdf2, Kdf::Scrypt, Kdf::Argon2];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            Kdf::Hkdf => "hkdf",
+            Kdf::Pbkdf2 => "pbkdf2",
+            Kdf::Scrypt => "scrypt",
+            Kdf::Argon2 => "argon2",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kdf| kdf.id() == id)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Kdf::Hkdf => "HKDF",
+            Kdf::Pbkdf2 => "PBKDF2",
+            Kdf::Scrypt => "scrypt",
+            Kdf::Argon2 => "Argon2",
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum KdfParam {
+    /// The input, which has an encoding but no text field here
+    Secret,
+    Salt,
+    Info,
+    Pbkdf2Iterations,
+    ScryptLogN,
+    ScryptR,
+    ScryptP,
+    Argon2Memory,
+    Argon2Iterations,
+    Argon2Parallelism,
+    Length,
+}
+
+/// Parameters as entered, with costs defaulting to the OWASP recommendations
+/// as on the password hashing page
+#[derive(Debug, PartialEq)]
+struct KdfConfig {
+    kdf: Kdf,
+    secret_encoding: InputEncoding,
+    salt: String,
+    salt_encoding: InputEncoding,
+    info: String,
+    info_encoding: InputEncoding,
+    hash: HashAlgorithm,
+    pbkdf2_iterations: String,
+    scrypt_log_n: String,
+    scrypt_r: String,
+    scrypt_p: String,
+    argon2_variant: Argon2Variant,
+    argon2_memory: String,
+    argon2_iterations: String,
+    argon2_parallelism: String,
+    length: String,
+}
+
+impl Default for KdfConfig {
+    fn default() -> Self {
+        Self {
+            kdf: Kdf::default(),
+            secret_encoding: InputEncoding::default(),
+            salt: String::default(),
+            salt_encoding: InputEncoding::default(),
+            info: String::default(),
+            info_encoding: InputEncoding::default(),
+            hash: HashAlgorithm::Sha256,
+            pbkdf2_iterations: "600000".to_string(),
+            scrypt_log_n: "17".to_string(),
+            scrypt_r: "8".to_string(),
+            scrypt_p: "1".to_string(),
+            argon2_variant: Argon2Variant::default(),
+            argon2_memory: "19456".to_string(),
+            argon2_iterations: "2".to_string(),
+            argon2_parallelism: "1".to_string(),
+            length: "32".to_string(),
+        }
+    }
+}
+
+impl KdfConfig {
+    fn algorithm(&self) -> Result<KdfAlgorithm, String> {
+        let algorithm = match self.kdf {
+            Kdf::Hkdf => KdfAlgorithm::Hkdf {
+                hash: self.hash,
+                info: decode_input(&self.info, &self.info_encoding)?,
+            },
+            Kdf::Pbkdf2 => KdfAlgorithm::Pbkdf2 {
+                hash: self.hash,
+                iterations: parse_param("Iterations", &self.pbkdf2_iterations)?,
+            },
+            Kdf::Scrypt => KdfAlgorithm::Scrypt {
+                log_n: parse_param("log\u{2082}(N)", &self.scrypt_log_n)?,
+                r: parse_param("r", &self.scrypt_r)?,
+                p: parse_param("p", &self.scrypt_p)?,
+            },
+            Kdf::Argon2 => KdfAlgorithm::Argon2 {
+                variant: self.argon2_variant,
+                memory: parse_param("Memory", &self.argon2_memory)?,
+                iterations: parse_param("Iterations", &self.argon2_iterations)?,
+                parallelism: parse_param("Parallelism", &self.argon2_parallelism)?,
+            },
+        };
+        Ok(algorithm)
+    }
+
+    fn request(&self, input: &str) -> Result<WorkerRequest, String> {
+        Ok(WorkerRequest::DeriveKey {
+            algorithm: self.algorithm()?,
+            secret: decode_input(input, &self.secret_encoding)?,
+            salt: decode_input(&self.salt, &self.salt_encoding)?,
+            length: parse_param("Output length", &self.length)?,
+        })
+    }
+}
+
+fn parse_param<T: std::str::FromStr>(label: &str, value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("{} must be a positive number", label))
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    /// Secret input for key derivation
+    pub input: String,
+}
+
+pub struct CryptoTools {
+    tool: Tool,
+    key_type: KeyType,
+    key_format: KeyFormat,
+    key_pair: Option<Result<KeyPair, String>>,
+    /// A key pair is being generated in the worker
+    generating: bool,
+    worker: WorkerBridge<BackgroundWorker>,
+    ecdh_config: EcdhConfig,
+    format: DigestFormat,
+    kdf_config: KdfConfig,
+    /// A key derived in the worker
+    kdf_result: Option<Result<Vec<u8>, String>>,
+    /// Bumped whenever the input or KDF parameters change
+    kdf_generation: u32,
+    /// Generations of key derivations still running in the worker, oldest first
+    pending: VecDeque<u32>,
+}
+
+impl Component for CryptoTools {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let on_response = ctx.link().callback(Msg::WorkerResponse);
+        let worker = BackgroundWorker::spawner()
+            .callback(move |response| on_response.emit(response))
+            .spawn(WORKER_PATH);
+
+        Self {
+            tool: Tool::default(),
+            key_type: KeyType::default(),
+            key_format: KeyFormat::default(),
+            key_pair: None,
+            generating: false,
+            worker,
+            ecdh_config: EcdhConfig::default(),
+            format: DigestFormat::default(),
+            kdf_config: KdfConfig::default(),
+            kdf_result: None,
+            kdf_generation: 0,
+            pending: VecDeque::new(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SetTool(tool) => self.tool = tool,
+            Msg::SetKeyType(key_type) => self.key_type = key_type,
+            Msg::SetKeyFormat(format) => self.key_format = format,
+            Msg::GenerateKey => {
+                self.generating = true;
+                self.worker.send(WorkerRequest::GenerateKey(self.key_type));
+            }
+            Msg::WorkerResponse(response) => match response {
+                WorkerResponse::KeyPair(result) => {
+                    self.generating = false;
+                    self.key_pair = Some(*result);
+                }
+                // Drop keys for an input or parameters that have since changed
+                WorkerResponse::DerivedKey(result)
+                    if self.pending.pop_front() == Some(self.kdf_generation) =>
+                {
+                    self.kdf_result = Some(result);
+                }
+                _ => {}
+            },
+            Msg::SetEcdhCurve(curve) => self.ecdh_config.curve = curve,
+            Msg::SetEcdhParam(param, value) => {
+                let config = &mut self.ecdh_config;
+                match param {
+                    EcdhParam::PrivateKey => config.private_key = value,
+                    EcdhParam::PublicKey => config.public_key = value,
+                    EcdhParam::Salt => config.salt = value,
+                    EcdhParam::Info => config.info = value,
+                    EcdhParam::Length => config.length = value,
+                }
+            }
+            Msg::SetEcdhEncoding(param, encoding) => match param {
+                EcdhParam::Salt => self.ecdh_config.salt_encoding = encoding,
+                EcdhParam::Info => self.ecdh_config.info_encoding = encoding,
+                _ => {}
+            },
+            Msg::SetEcdhHash(hash) => self.ecdh_config.hash = hash,
+            Msg::SetFormat(format) => self.format = format,
+            Msg::SetKdf(kdf) => {
+                self.kdf_config.kdf = kdf;
+                self.clear_kdf_result();
+            }
+            Msg::SetKdfParam(param, value) => {
+                let config = &mut self.kdf_config;
+                match param {
+                    KdfParam::Salt => config.salt = value,
+                    KdfParam::Info => config.info = value,
+                    KdfParam::Pbkdf2Iterations => config.pbkdf2_iterations = value,
+                    KdfParam::ScryptLogN => config.scrypt_log_n = value,
+                    KdfParam::ScryptR => config.scrypt_r = value,
+                    KdfParam::ScryptP => config.scrypt_p = value,
+                    KdfParam::Argon2Memory => config.argon2_memory = value,
+                    KdfParam::Argon2Iterations => config.argon2_iterations = value,
+                    KdfParam::Argon2Parallelism => config.argon2_parallelism = value,
+                    KdfParam::Length => config.length = value,
+                    KdfParam::Secret => {}
+                }
+                self.clear_kdf_result();
+            }
+            Msg::SetKdfEncoding(param, encoding) => {
+                let config = &mut self.kdf_config;
+                match param {
+                    KdfParam::Secret => config.secret_encoding = encoding,
+                    KdfParam::Salt => config.salt_encoding = encoding,
+                    KdfParam::Info => config.info_encoding = encoding,
+                    _ => {}
+                }
+                self.clear_kdf_result();
+            }
+            Msg::SetKdfHash(hash) => {
+                self.kdf_config.hash = hash;
+                self.clear_kdf_result();
+            }
+            Msg::SetArgon2Variant(variant) => {
+                self.kdf_config.argon2_variant = variant;
+                self.clear_kdf_result();
+            }
+            Msg::DeriveKey => match self.kdf_config.request(&ctx.props().input) {
+                Ok(request) => {
+                    self.clear_kdf_result();
+                    self.pending.push_back(self.kdf_generation);
+                    self.worker.send(request);
+                }
+                Err(e) => self.kdf_result = Some(Err(e)),
+            },
+        }
+        true
+    }
+
+    fn changed(&mut self, _ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        // The derived key was for the previous input
+        self.clear_kdf_result();
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+
+        let on_tool_click = link.batch_callback(|e: Event| {
+            let tool_el = e.target_dyn_into::<HtmlInputElement>();
+            tool_el.map(|btn| Msg::SetTool(Tool::from_id(&btn.value()).unwrap_or_default()))
+        });
+
+        html! {
+            <div class="action-component">
+                <div class="controls">
+                    <div class="form-radio-group">
+                        { for Tool::ALL.iter().map(|tool| html! {
+                            <label class="form-radio">
+                                <input
+                                    type="radio"
+                                    name="crypto-tool"
+                                    value={tool.id()}
+                                    checked={self.tool == *tool}
+                                    onchange={&on_tool_click}
+                                />
+                                <span>{ tool.name() }</span>
+                            </label>
+                        }) }
+                    </div>
+                </div>
+                {
+                    match self.tool {
+                        Tool::KeyGeneration => self.view_key_generation(ctx),
+                        Tool::Ecdh => self.view_ecdh(ctx),
+                        Tool::Kdf => self.view_kdf(ctx),
+                        Tool::Asn1 => view_asn1(&ctx.props().input),
+                        Tool::X509 => view_x509(&ctx.props().input),
+                        Tool::Ssh => view_ssh_keys(&ctx.props().input),
+                        Tool::Pgp => view_pgp(&ctx.props().input),
+                    }
+                }
+            </div>
+        }
+    }
+}
+
+impl CryptoTools {
+    fn clear_kdf_result(&mut self) {
+        self.kdf_result = None;
+        self.kdf_generation = self.kdf_generation.wrapping_add(1);
+    }
+
+    fn view_key_generation(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+
+        let on_key_type_click = link.batch_callback(|e: Event| {
+            let key_type_el = e.target_dyn_into::<HtmlInputElement>();
+            key_type_el
+                .map(|btn| Msg::SetKeyType(KeyType::from_id(&btn.value()).unwrap_or_default()))
+        });
+        let on_format_change = link.callback(|value: String| {
+            Msg::SetKeyFormat(KeyFormat::from_id(&value).unwrap_or_default())
+        });
+        let on_generate_click = link.callback(|_| Msg::GenerateKey);
+
+        let format_options = KeyFormat::ALL
+            .iter()
+            .map(|format| (format.id().to_string(), format.name().to_string()))
+            .collect::<Vec<_>>();
+
+        let (private_key, public_key, error) = match &self.key_pair {
+            Some(Ok(key_pair)) => match key_pair.export(self.key_format) {
+                Ok((private_key, public_key)) => (private_key, public_key, None),
+                Err(e) => (
+                    String::default(),
+                    String::default(),
+                    Some(("Export Failed", e)),
+                ),
+            },
+            Some(Err(e)) => (
+                String::default(),
+                String::default(),
+                Some(("Key Generation Failed", e.clone())),
+            ),
+            None => (String::default(), String::default(), None),
+        };
+
+        html! {
+            <>
+                <div class="controls">
+                    <div class="custom-radio-group">
+                        { for KeyType::ALL.iter().map(|key_type| html! {
+                            <label class="custom-radio">
+                                <input
+                                    type="radio"
+                                    name="key-type"
+                                    value={key_type.id()}
+                                    checked={self.key_type == *key_type}
+                                    onchange={&on_key_type_click}
+                                />
+                                <span>{ key_type.name() }</span>
+                            </label>
+                        }) }
+                    </div>
+                </div>
+                <div class="controls">
+                    <button class="button" onclick={on_generate_click} disabled={self.generating}>
+                        { "Generate key pair" }
+                    </button>
+                    if self.generating {
+                        <span role="status">{ "Working\u{2026}" }</span>
+                    }
+                    {"Export as:"}
+                    <Select label={"Key format".to_string()} options={format_options} value={self.key_format.id().to_string()} on_change={on_format_change} />
+                </div>
+                <div class="overlay-container">
+                    <Textarea placeholder={"Private key".to_string()} value={private_key} read_only={true} is_output={true} />
+                    if let Some((title, error)) = error {
+                        <div class="overlay">
+                            <div class="content">
+                                <div><strong>{ title }</strong></div>
+                                <div><em>{ error }</em></div>
+                            </div>
+                        </div>
+                    }
+                </div>
+                <Textarea placeholder={"Public key".to_string()} value={public_key} read_only={true} is_output={true} />
+                if let Some(Ok(key_pair)) = &self.key_pair {
+                    { view_fingerprints(key_pair) }
+                }
+            </>
+        }
+    }
+
+    fn view_ecdh(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let config = &self.ecdh_config;
+
+        let on_curve_click = link.batch_callback(|e: Event| {
+            let curve_el = e.target_dyn_into::<HtmlInputElement>();
+            curve_el
+                .map(|btn| Msg::SetEcdhCurve(EcdhCurve::from_id(&btn.value()).unwrap_or_default()))
+        });
+        let on_private_key_input =
+            link.callback(|value| Msg::SetEcdhParam(EcdhParam::PrivateKey, value));
+        let on_public_key_input =
+            link.callback(|value| Msg::SetEcdhParam(EcdhParam::PublicKey, value));
+        let on_salt_input = link.callback(|value| Msg::SetEcdhParam(EcdhParam::Salt, value));
+        let on_info_input = link.callback(|value| Msg::SetEcdhParam(EcdhParam::Info, value));
+        let on_length_input = link.callback(|value| Msg::SetEcdhParam(EcdhParam::Length, value));
+        let on_salt_encoding_change =
+            link.callback(|encoding| Msg::SetEcdhEncoding(EcdhParam::Salt, encoding));
+        let on_info_encoding_change =
+            link.callback(|encoding| Msg::SetEcdhEncoding(EcdhParam::Info, encoding));
+        let on_hash_change = link.callback(|value: String| {
+            Msg::SetEcdhHash(HashAlgorithm::from_id(&value).unwrap_or(HashAlgorithm::Sha256))
+        });
+        let on_format_change = link.callback(|value: String| {
+            Msg::SetFormat(DigestFormat::from_id(&value).unwrap_or_default())
+        });
+
+        let hash_options = HashAlgorithm::ALL
+            .iter()
+            .filter(|hash| hash.uses_hmac())
+            .map(|hash| (hash.id().to_string(), hash.name().to_string()))
+            .collect::<Vec<_>>();
+        let format_options = DigestFormat::ALL
+            .iter()
+            .map(|format| (format.id().to_string(), format.name().to_string()))
+            .collect::<Vec<_>>();
+
+        let (outputs, error) = match config.derive() {
+            Some(Ok(outputs)) => (Some(outputs), None),
+            Some(Err(e)) => (None, Some(e)),
+            None => (None, None),
+        };
+        let names = ["Shared secret", "HKDF PRK", "HKDF output"];
+        let rows = names.into_iter().enumerate().map(|(i, name)| {
+            let value = outputs
+                .as_ref()
+                .map(|outputs| format_digest(&outputs[i], &self.format))
+                .unwrap_or_default();
+            (name, value)
+        });
+
+        html! {
+            <>
+                <div class="controls">
+                    <div class="custom-radio-group">
+                        { for EcdhCurve::ALL.iter().map(|curve| html! {
+                            <label class="custom-radio">
+                                <input
+                                    type="radio"
+                                    name="ecdh-curve"
+                                    value={curve.id()}
+                                    checked={config.curve == *curve}
+                                    onchange={&on_curve_click}
+                                />
+                                <span>{ curve.name() }</span>
+                            </label>
+                        }) }
+                    </div>
+                </div>
+                <div class="controls">
+                    <TextInput placeholder={"Private key (PEM, DER, JWK or raw)".to_string()} value={config.private_key.clone()} on_change={on_private_key_input} />
+                    <TextInput placeholder={"Peer public key (PEM, DER, JWK or raw)".to_string()} value={config.public_key.clone()} on_change={on_public_key_input} />
+                </div>
+                <div class="controls">
+                    {"HKDF with:"}
+                    <Select label={"HKDF hash".to_string()} options={hash_options} value={config.hash.id().to_string()} on_change={on_hash_change} />
+                    {"Output bytes:"}
+                    <TextInput placeholder={format!("Output length (default {})", config.hash.output_size())} value={config.length.clone()} on_change={on_length_input} />
+                </div>
+                <div class="controls">
+                    <TextInput placeholder={"Salt (optional)".to_string()} value={config.salt.clone()} on_change={on_salt_input} />
+                    <InputEncodingRadios name={"hkdf-salt-encoding".to_string()} label={"Salt as:".to_string()} value={config.salt_encoding} on_change={on_salt_encoding_change} />
+                    <TextInput placeholder={"Info (optional)".to_string()} value={config.info.clone()} on_change={on_info_input} />
+                    <InputEncodingRadios name={"hkdf-info-encoding".to_string()} label={"Info as:".to_string()} value={config.info_encoding} on_change={on_info_encoding_change} />
+                </div>
+                <div class="controls">
+                    {"Output as:"}
+                    <Select label={"Output format".to_string()} options={format_options} value={self.format.id().to_string()} on_change={on_format_change} />
+                </div>
+                <div class="overlay-container">
+                    <table class="digest-table">
+                        <caption>{ format!("{} key agreement", config.curve.name()) }</caption>
+                        <tbody>
+                            { for rows.map(|(name, value)| html! {
+                                <tr>
+                                    <th scope="row">{ name }</th>
+                                    <td class="digest">{ value.clone() }</td>
+                                    <td>
+                                        <CopyButton value={value} label={format!("Copy {} to clipboard", name)} />
+                                    </td>
+                                </tr>
+                            }) }
+                        </tbody>
+                    </table>
+                    if let Some(error) = error {
+                        <div class="overlay">
+                            <div class="content">
+                                <div><strong>{ "Key Agreement Failed" }</strong></div>
+                                <div><em>{ error }</em></div>
+                            </div>
+                        </div>
+                    }
+                </div>
+            </>
+        }
+    }
+}
+
+impl CryptoTools {
+    fn view_kdf(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let config = &self.kdf_config;
+        let param_callback =
+            |param: KdfParam| link.callback(move |value| Msg::SetKdfParam(param, value));
+        let encoding_callback =
+            |param: KdfParam| link.callback(move |encoding| Msg::SetKdfEncoding(param, encoding));
+
+        let on_kdf_click = link.batch_callback(|e: Event| {
+            let kdf_el = e.target_dyn_into::<HtmlInputElement>();
+            kdf_el.map(|btn| Msg::SetKdf(Kdf::from_id(&btn.value()).unwrap_or_default()))
+        });
+        let on_format_change = link.callback(|value: String| {
+            Msg::SetFormat(DigestFormat::from_id(&value).unwrap_or_default())
+        });
+        let on_derive_click = link.callback(|_| Msg::DeriveKey);
+
+        let format_options = DigestFormat::ALL
+            .iter()
+            .map(|format| (format.id().to_string(), format.name().to_string()))
+            .collect::<Vec<_>>();
+        let busy = !self.pending.is_empty();
+
+        let (output, error) = match &self.kdf_result {
+            Some(Ok(key)) => (format_digest(key, &self.format), None),
+            Some(Err(e)) => (String::default(), Some(e.clone())),
+            None => (String::default(), None),
+        };
+
+        html! {
+            <>
+                <div class="controls">
+                    <div class="custom-radio-group">
+                        { for Kdf::ALL.iter().map(|kdf| html! {
+                            <label class="custom-radio">
+                                <input
+                                    type="radio"
+                                    name="kdf"
+                                    value={kdf.id()}
+                                    checked={config.kdf == *kdf}
+                                    onchange={&on_kdf_click}
+                                />
+                                <span>{ kdf.name() }</span>
+                            </label>
+                        }) }
+                    </div>
+                </div>
+                <div class="controls">
+                    <InputEncodingRadios name={"kdf-secret-encoding".to_string()} label={"Input as:".to_string()} value={config.secret_encoding} on_change={encoding_callback(KdfParam::Secret)} />
+                    <TextInput placeholder={"Salt".to_string()} value={config.salt.clone()} on_change={param_callback(KdfParam::Salt)} />
+                    <InputEncodingRadios name={"kdf-salt-encoding".to_string()} label={"Salt as:".to_string()} value={config.salt_encoding} on_change={encoding_callback(KdfParam::Salt)} />
+                </div>
+                <div class="controls">
+                    { self.view_kdf_params(ctx) }
+                    {"Output bytes:"}
+                    <TextInput placeholder={"Output length".to_string()} value={config.length.clone()} on_change={param_callback(KdfParam::Length)} />
+                    <button class="button" onclick={on_derive_click} disabled={busy}>
+                        { "Derive key" }
+                    </button>
+                    if busy {
+                        <span role="status">{ "Working\u{2026}" }</span>
+                    }
+                </div>
+                <div class="controls">
+                    {"Output as:"}
+                    <Select label={"Output format".to_string()} options={format_options} value={self.format.id().to_string()} on_change={on_format_change} />
+                </div>
+                <div class="overlay-container">
+                    <Textarea placeholder={"Derived key".to_string()} value={output} read_only={true} is_output={true} />
+                    if let Some(error) = error {
+                        <div class="overlay">
+                            <div class="content">
+                                <div><strong>{ "Key Derivation Failed" }</strong></div>
+                                <div><em>{ error }</em></div>
+                            </div>
+                        </div>
+                    }
+                </div>
+            </>
+        }
+    }
+
+    /// Hash, cost and info parameters of the selected KDF
+    fn view_kdf_params(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let config = &self.kdf_config;
+        let param_callback =
+            |param: KdfParam| link.callback(move |value| Msg::SetKdfParam(param, value));
+
+        let on_hash_change = link.callback(|value: String| {
+            Msg::SetKdfHash(HashAlgorithm::from_id(&value).unwrap_or(HashAlgorithm::Sha256))
+        });
+        let hash_options = HashAlgorithm::ALL
+            .iter()
+            .filter(|hash| hash.uses_hmac())
+            .map(|hash| (hash.id().to_string(), hash.name().to_string()))
+            .collect::<Vec<_>>();
+
+        match config.kdf {
+            Kdf::Hkdf => {
+                let on_info_encoding_change =
+                    link.callback(|encoding| Msg::SetKdfEncoding(KdfParam::Info, encoding));
+
+                html! {
+                    <>
+                        <Select label={"HKDF hash".to_string()} options={hash_options} value={config.hash.id().to_string()} on_change={on_hash_change} />
+                        <TextInput placeholder={"Info (optional)".to_string()} value={config.info.clone()} on_change={param_callback(KdfParam::Info)} />
+                        <InputEncodingRadios name={"kdf-info-encoding".to_string()} label={"Info as:".to_string()} value={config.info_encoding} on_change={on_info_encoding_change} />
+                    </>
+                }
+            }
+            Kdf::Pbkdf2 => html! {
+                <>
+                    <Select label={"PBKDF2 hash".to_string()} options={hash_options} value={config.hash.id().to_string()} on_change={on_hash_change} />
+                    {"Iterations:"}
+                    <TextInput placeholder={"Iterations".to_string()} value={config.pbkdf2_iterations.clone()} on_change={param_callback(KdfParam::Pbkdf2Iterations)} />
+                </>
+            },
+            Kdf::Scrypt => html! {
+                <>
+                    {"log\u{2082}(N):"}
+                    <TextInput placeholder={"log\u{2082}(N)".to_string()} value={config.scrypt_log_n.clone()} on_change={param_callback(KdfParam::ScryptLogN)} />
+                    {"r:"}
+                    <TextInput placeholder={"Block size".to_string()} value={config.scrypt_r.clone()} on_change={param_callback(KdfParam::ScryptR)} />
+                    {"p:"}
+                    <TextInput placeholder={"Parallelism".to_string()} value={config.scrypt_p.clone()} on_change={param_callback(KdfParam::ScryptP)} />
+                </>
+            },
+            Kdf::Argon2 => {
+                let on_variant_change = link.callback(|value: String| {
+                    Msg::SetArgon2Variant(Argon2Variant::from_id(&value).unwrap_or_default())
+                });
+                let variant_options = Argon2Variant::ALL
+                    .iter()
+                    .map(|variant| (variant.id().to_string(), variant.name().to_string()))
+                    .collect::<Vec<_>>();
+
+                html! {
+                    <>
+                        <Select label={"Argon2 variant".to_string()} options={variant_options} value={config.argon2_variant.id().to_string()} on_change={on_variant_change} />
+                        {"Memory (KiB):"}
+                        <TextInput placeholder={"Memory (KiB)".to_string()} value={config.argon2_memory.clone()} on_change={param_callback(KdfParam::Argon2Memory)} />
+                        {"Iterations:"}
+                        <TextInput placeholder={"Iterations".to_string()} value={config.argon2_iterations.clone()} on_change={param_callback(KdfParam::Argon2Iterations)} />
+                        {"Parallelism:"}
+                        <TextInput placeholder={"Parallelism".to_string()} value={config.argon2_parallelism.clone()} on_change={param_callback(KdfParam::Argon2Parallelism)} />
+                    </>
+                }
+            }
+        }
+    }
+}
+
+/// Structure of the DER, or PEM wrapped DER, in `input`
+fn view_asn1(input: &str) -> Html {
+    if input.trim().is_empty() {
+        return html! {
+            <p>{ "Paste a PEM block, or DER as hex or Base64, into the input to view its structure." }</p>
+        };
+    }
+
+    let result = decode_der_input(input).and_then(|(label, der)| {
+        let tree = format_asn1(&parse_asn1(&der)?);
+        let summary = match label {
+            Some(label) => format!("{} bytes from a {} PEM block", der.len(), label),
+            None => format!("{} bytes", der.len()),
+        };
+        Ok((summary, tree))
+    });
+    let (summary, tree, error) = match result {
+        Ok((summary, tree)) => (Some(summary), tree, None),
+        Err(e) => (None, String::default(), Some(e)),
+    };
+
+    html! {
+        <>
+            if let Some(summary) = summary {
+                <p>{ format!("{}, shown as offset, length and element:", summary) }</p>
+            }
+            <div class="overlay-container">
+                <Textarea placeholder={"ASN.1 structure".to_string()} value={tree} read_only={true} is_output={true} />
+                if let Some(error) = error {
+                    <div class="overlay">
+                        <div class="content">
+                            <div><strong>{ "Parse Failed" }</strong></div>
+                            <div><em>{ error }</em></div>
+                        </div>
+                    </div>
+                }
+            </div>
+        </>
+    }
+}
+
+/// Certificates, CSRs and CRLs in the input, certificates in chain order
+fn view_x509(input: &str) -> Html {
+    if input.trim().is_empty() {
+        return html! {
+            <p>{ "Paste PEM certificates, CSRs or CRLs, or DER as hex or Base64, into the input to decode them." }</p>
+        };
+    }
+    let objects = match parse_x509(input) {
+        Ok(objects) => objects,
+        Err(e) => return html! { <p>{ format!("Unrecognized certificate: {}", e) }</p> },
+    };
+    let now = unix_now();
+
+    html! {
+        { for objects.iter().enumerate().map(|(index, object)| {
+            let caption = if objects.len() > 1 {
+                format!("{} {} of {}", object.kind.name(), index + 1, objects.len())
+            } else {
+                object.kind.name().to_string()
+            };
+            let (start, end) = match object.kind {
+                X509Kind::Crl => ("This update", "Next update"),
+                _ => ("Not before", "Not after"),
+            };
+
+            let mut rows: Vec<(String, Html)> = Vec::new();
+            let mut text_row = |name: &str, value: String| rows.push((name.to_string(), html! { value }));
+            if let Some(subject) = &object.subject {
+                text_row("Subject", subject.clone());
+            }
+            if let Some(issuer) = &object.issuer {
+                text_row("Issuer", issuer.clone());
+                let issued_by = if object.is_self_issued() {
+                    "Self-signed".to_string()
+                } else {
+                    match object.issuer_index {
+                        Some(issuer_index) => format!("Certificate {} of this bundle", issuer_index + 1),
+                        None => "Not in this bundle".to_string(),
+                    }
+                };
+                text_row("Issued by", issued_by);
+            }
+            text_row("Version", object.version.to_string());
+            if let Some(serial) = &object.serial {
+                text_row("Serial number", serial.clone());
+            }
+            if let Some(time) = &object.not_before {
+                text_row(start, time.text.clone());
+            }
+            if let Some(time) = &object.not_after {
+                text_row(end, time.text.clone());
+            }
+            if let Some(public_key) = &object.public_key {
+                text_row("Public key", public_key.clone());
+            }
+            text_row("Signature algorithm", object.signature_algorithm.clone());
+            for extension in &object.extensions {
+                let name = if extension.critical {
+                    format!("{} (critical)", extension.name)
+                } else {
+                    extension.name.clone()
+                };
+                text_row(&name, extension.value.clone());
+            }
+            for revoked in &object.revoked {
+                let value = match &revoked.reason {
+                    Some(reason) => format!("{} on {} ({})", revoked.serial, revoked.date.text, reason),
+                    None => format!("{} on {}", revoked.serial, revoked.date.text),
+                };
+                text_row("Revoked", value);
+            }
+            if let Some(validity) = object.validity(now) {
+                rows.push(("Validity".to_string(), view_validity(validity)));
+            }
+            let fingerprints = [
+                ("SHA-1 fingerprint", format_digest(&object.sha1_fingerprint, &DigestFormat::HexColon)),
+                ("SHA-256 fingerprint", format_digest(&object.sha256_fingerprint, &DigestFormat::HexColon)),
+            ];
+
+            html! {
+                <table class="digest-table">
+                    <caption>{ caption }</caption>
+                    <tbody>
+                        { for rows.into_iter().map(|(name, value)| html! {
+                            <tr>
+                                <th scope="row">{ name }</th>
+                                <td class="digest">{ value }</td>
+                                <td></td>
+                            </tr>
+                        }) }
+                        { for fingerprints.into_iter().map(|(name, value)| html! {
+                            <tr>
+                                <th scope="row">{ name }</th>
+                                <td class="digest">{ value.clone() }</td>
+                                <td>
+                                    <CopyButton value={value} label={format!("Copy {} to clipboard", name)} />
+                                </td>
+                            </tr>
+                        }) }
+                    </tbody>
+                </table>
+            }
+        }) }
+    }
+}
+
+/// Type, comment and fingerprints of each SSH public key in the input
+fn view_ssh_keys(input: &str) -> Html {
+    if input.trim().is_empty() {
+        return html! {
+            <p>{ "Paste SSH public keys, or authorized_keys or known_hosts lines, into the input to fingerprint them." }</p>
+        };
+    }
+    let keys = match parse_ssh_public_keys(input) {
+        Ok(keys) => keys,
+        Err(e) => return html! { <p>{ format!("Unrecognized key: {}", e) }</p> },
+    };
+
+    html! {
+        { for keys.iter().map(|key| {
+            let caption = match key.bits {
+                Some(bits) => format!("Line {}: {} {} bits", key.line, key.key_type, bits),
+                None => format!("Line {}: {}", key.line, key.key_type),
+            };
+            let mut rows = vec![("Algorithm", key.algorithm.clone())];
+            if !key.comment.is_empty() {
+                rows.push(("Comment", key.comment.clone()));
+            }
+            if !key.options.is_empty() {
+                rows.push(("Options or hosts", key.options.clone()));
+            }
+            let fingerprints = [
+                ("SHA-256 fingerprint", key.sha256_fingerprint.clone()),
+                ("MD5 fingerprint", key.md5_fingerprint.clone()),
+            ];
+
+            html! {
+                <table class="digest-table">
+                    <caption>{ caption }</caption>
+                    <tbody>
+                        { for rows.into_iter().map(|(name, value)| html! {
+                            <tr>
+                                <th scope="row">{ name }</th>
+                                <td class="digest">{ value }</td>
+                                <td></td>
+                            </tr>
+                        }) }
+                        { for fingerprints.into_iter().map(|(name, value)| html! {
+                            <tr>
+                                <th scope="row">{ name }</th>
+                                <td class="digest">{ value.clone() }</td>
+                                <td>
+                                    <CopyButton value={value} label={format!("Copy {} to clipboard", name)} />
+                                </td>
+                            </tr>
+                        }) }
+                        <tr>
+                            <th scope="row">{ "Randomart" }</th>
+                            <td class="digest randomart">{ key.randomart.clone() }</td>
+                            <td></td>
+                        </tr>
+                    </tbody>
+                </table>
+            }
+        }) }
+    }
+}
+
+/// ASCII armor details and a dump of the OpenPGP packets inside it
+fn view_pgp(input: &str) -> Html {
+    if input.trim().is_empty() {
+        return html! {
+            <p>{ "Paste an armored PGP key, signature or message, or binary OpenPGP data as hex or Base64, into the input to list its packets." }</p>
+        };
+    }
+    let (armor, data) = match decode_pgp(input) {
+        Ok(decoded) => decoded,
+        Err(e) => return html! { <p>{ format!("Unrecognized OpenPGP data: {}", e) }</p> },
+    };
+    let (packets, error) = match parse_pgp_packets(&data) {
+        Ok(packets) => (format_pgp_packets(&packets), None),
+        Err(e) => (String::default(), Some(e)),
+    };
+
+    html! {
+        <>
+            if let Some(armor) = armor {
+                <table class="digest-table">
+                    <caption>{ format!("PGP {}", armor.label) }</caption>
+                    <tbody>
+                        { for armor.headers.iter().map(|(name, value)| html! {
+                            <tr>
+                                <th scope="row">{ name }</th>
+                                <td class="digest">{ value }</td>
+                            </tr>
+                        }) }
+                        <tr>
+                            <th scope="row">{ "CRC-24" }</th>
+                            <td class="digest">
+                                { format!("{:06X} ", armor.computed_checksum) }
+                                { view_armor_checksum(&armor) }
+                            </td>
+                        </tr>
+                    </tbody>
+                </table>
+            }
+            <p>{ format!("{} bytes of OpenPGP data, shown as packets and their fields:", data.len()) }</p>
+            <div class="overlay-container">
+                <Textarea placeholder={"OpenPGP packets".to_string()} value={packets} read_only={true} is_output={true} />
+                if let Some(error) = error {
+                    <div class="overlay">
+                        <div class="content">
+                            <div><strong>{ "Parse Failed" }</strong></div>
+                            <div><em>{ error }</em></div>
+                        </div>
+                    </div>
+                }
+            </div>
+        </>
+    }
+}
+
+fn view_armor_checksum(armor: &PgpArmor) -> Html {
+    let (class, text) = match (armor.checksum, armor.checksum_matches()) {
+        (Some(_), Some(true)) => ("verification match", "\u{2714} Matches".to_string()),
+        (Some(checksum), _) => (
+            "verification mismatch",
+            format!("\u{2718} Armor says {:06X}", checksum),
+        ),
+        (None, _) => ("verification", "No checksum in armor".to_string()),
+    };
+    html! {
+        <span class={class} role="status">{ text }</span>
+    }
+}
+
+/// Badge for where the current time falls in a certificate's validity period
+fn view_validity(validity: Validity) -> Html {
+    let (class, text) = match validity {
+        Validity::NotYetValid { days } => (
+            "verification mismatch",
+            format!("\u{26a0} Not yet valid, starts in {} days", days),
+        ),
+        Validity::Valid { days_left } => (
+            "verification match",
+            format!("\u{2714} Valid, expires in {} days", days_left),
+        ),
+        Validity::ExpiresSoon { days_left } => (
+            "verification mismatch",
+            format!("\u{26a0} Expires in {} days", days_left),
+        ),
+        Validity::Expired { days_ago } => (
+            "verification mismatch",
+            format!("\u{2718} Expired {} days ago", days_ago),
+        ),
+    };
+    html! {
+        <span class={class} role="status">{ text }</span>
+    }
+}
+
+fn unix_now() -> i64 {
+    (js_sys::Date::now() / 1000.0) as i64
+}
+
+/// Fingerprints of the public key, with the SSH ones only for key types
+/// OpenSSH supports
+fn view_fingerprints(key_pair: &KeyPair) -> Html {
+    let fingerprints = &key_pair.fingerprints;
+    let rows = [
+        ("OpenSSH SHA-256", fingerprints.ssh_sha256.clone()),
+        ("OpenSSH MD5", fingerprints.ssh_md5.clone()),
+        ("SPKI SHA-256 pin", Some(fingerprints.spki_sha256.clone())),
+        ("JWK thumbprint", Some(fingerprints.jwk_thumbprint.clone())),
+    ];
+
+    html! {
+        <table class="digest-table">
+            <caption>{ format!("{} public key fingerprints", key_pair.key_type.name()) }</caption>
+            <tbody>
+                { for rows.into_iter().filter_map(|(name, value)| value.map(|value| html! {
+                    <tr>
+                        <th scope="row">{ name }</th>
+                        <td class="digest">{ value.clone() }</td>
+                        <td>
+                            <CopyButton value={value} label={format!("Copy {} fingerprint to clipboard", name)} />
+                        </td>
+                    </tr>
+                })) }
+            </tbody>
+        </table>
+    }
+}
@@ -0,0 +1,806 @@
+use crate::components::{InputEncodingRadios, Select, TextInput, Textarea};
+use crate::engine::{
+    decode_input, format_digest, format_unix_time, openssl_base64, parse_digest, random_fernet_iv,
+    random_openssl_salt, CipherAlgorithm, CipherDirection, DigestFormat, FernetKey, FernetToken,
+    HashAlgorithm, InputEncoding, KeyDerivation, OpensslParams, OPENSSL_DIGESTS,
+};
+use crate::worker::{BackgroundWorker, WorkerRequest, WorkerResponse, WORKER_PATH};
+use gloo_worker::{Spawnable, WorkerBridge};
+use std::collections::VecDeque;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[allow(clippy::enum_variant_names)]
+pub enum Msg {
+    SetDirection(CipherDirection),
+    SetAction(Action),
+    SetParam(CipherParam, String),
+    SetEncoding(CipherParam, InputEncoding),
+    SetFormat(DigestFormat),
+    SetKeySource(KeySource),
+    SetPasswordParam(PasswordParam, String),
+    SetKdf(KeyDerivation),
+    SetDigest(HashAlgorithm),
+    SetKeySize(usize),
+    NewSalt,
+    RunOpenssl,
+    WorkerResponse(WorkerResponse),
+    SetFernetParam(FernetParam, String),
+    GenerateFernetKey,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Action {
+    Cipher(CipherAlgorithm),
+    Fernet,
+}
+
+impl Default for Action {
+    fn default() -> Self {
+        Action::Cipher(CipherAlgorithm::default())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CipherParam {
+    Key,
+    Nonce,
+    Aad,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum KeySource {
+    #[default]
+    Raw,
+    /// Derived from a password, as `openssl enc` does
+    Password,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum PasswordParam {
+    Password,
+    Iterations,
+    Salt,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum FernetParam {
+    Key,
+    /// Maximum token age in seconds when decrypting
+    Ttl,
+}
+
+/// `openssl enc` options for password-based encryption
+#[derive(Debug, PartialEq)]
+struct PasswordConfig {
+    password: String,
+    kdf: KeyDerivation,
+    digest: HashAlgorithm,
+    iterations: String,
+    key_size: usize,
+    /// Hex, empty for `-nosalt`
+    salt: String,
+}
+
+impl Default for PasswordConfig {
+    fn default() -> Self {
+        PasswordConfig {
+            password: String::default(),
+            kdf: KeyDerivation::default(),
+            digest: HashAlgorithm::Sha256,
+            iterations: "10000".to_string(),
+            key_size: 32,
+            salt: hex::encode(random_openssl_salt()),
+        }
+    }
+}
+
+impl PasswordConfig {
+    /// The `openssl enc` request for `data`, which is plaintext or ciphertext
+    fn request(
+        &self,
+        algorithm: CipherAlgorithm,
+        direction: CipherDirection,
+        data: Vec<u8>,
+    ) -> Result<WorkerRequest, String> {
+        if self.password.is_empty() {
+            return Err("Password is required".to_string());
+        }
+        let params = self.params(algorithm)?;
+        let password = self.password.clone();
+        match direction {
+            CipherDirection::Encrypt => Ok(WorkerRequest::OpensslEncrypt {
+                params,
+                password,
+                salt: decode_input(&self.salt, &InputEncoding::Hex)?,
+                plaintext: data,
+            }),
+            CipherDirection::Decrypt => Ok(WorkerRequest::OpensslDecrypt {
+                params,
+                password,
+                ciphertext: data,
+            }),
+        }
+    }
+
+    fn params(&self, cipher: CipherAlgorithm) -> Result<OpensslParams, String> {
+        Ok(OpensslParams {
+            cipher,
+            key_size: self.key_size,
+            kdf: self.kdf,
+            digest: self.digest,
+            iterations: self
+                .iterations
+                .trim()
+                .parse()
+                .map_err(|_| "Iterations must be a positive number".to_string())?,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    /// Plaintext when encrypting, ciphertext in any digest format when decrypting
+    pub input: String,
+}
+
+pub struct Encryption {
+    direction: CipherDirection,
+    action: Action,
+    key: String,
+    key_encoding: InputEncoding,
+    nonce: String,
+    nonce_encoding: InputEncoding,
+    aad: String,
+    aad_encoding: InputEncoding,
+    format: DigestFormat,
+    key_source: KeySource,
+    password_config: PasswordConfig,
+    password_result: Option<Result<Vec<u8>, String>>,
+    /// Bumped whenever the password result goes stale
+    password_generation: u32,
+    /// Generations of `openssl enc` requests still running in the worker,
+    /// oldest first
+    pending: VecDeque<u32>,
+    worker: WorkerBridge<BackgroundWorker>,
+    fernet_key: String,
+    fernet_ttl: String,
+    /// IV and creation time of the next token, renewed with the input
+    fernet_iv: [u8; 16],
+    fernet_timestamp: u64,
+}
+
+impl Component for Encryption {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let on_response = ctx.link().callback(Msg::WorkerResponse);
+        let worker = BackgroundWorker::spawner()
+            .callback(move |response| on_response.emit(response))
+            .spawn(WORKER_PATH);
+
+        Self {
+            direction: CipherDirection::default(),
+            action: Action::default(),
+            key: String::default(),
+            key_encoding: InputEncoding::Hex,
+            nonce: String::default(),
+            nonce_encoding: InputEncoding::Hex,
+            aad: String::default(),
+            aad_encoding: InputEncoding::Utf8,
+            format: DigestFormat::Base64,
+            key_source: KeySource::default(),
+            password_config: PasswordConfig::default(),
+            password_result: None,
+            password_generation: 0,
+            pending: VecDeque::new(),
+            worker,
+            fernet_key: String::default(),
+            fernet_ttl: String::default(),
+            fernet_iv: random_fernet_iv(),
+            fernet_timestamp: unix_now(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SetDirection(direction) => {
+                self.direction = direction;
+                self.clear_password_result();
+                true
+            }
+            Msg::SetAction(action) => {
+                self.action = action;
+                self.clear_password_result();
+                true
+            }
+            Msg::SetParam(param, value) => {
+                match param {
+                    CipherParam::Key => self.key = value,
+                    CipherParam::Nonce => self.nonce = value,
+                    CipherParam::Aad => self.aad = value,
+                }
+                true
+            }
+            Msg::SetEncoding(param, encoding) => {
+                match param {
+                    CipherParam::Key => self.key_encoding = encoding,
+                    CipherParam::Nonce => self.nonce_encoding = encoding,
+                    CipherParam::Aad => self.aad_encoding = encoding,
+                }
+                true
+            }
+            Msg::SetFormat(format) => {
+                self.format = format;
+                true
+            }
+            Msg::SetKeySource(key_source) => {
+                self.key_source = key_source;
+                self.clear_password_result();
+                true
+            }
+            Msg::SetPasswordParam(param, value) => {
+                let config = &mut self.password_config;
+                match param {
+                    PasswordParam::Password => config.password = value,
+                    PasswordParam::Iterations => config.iterations = value,
+                    PasswordParam::Salt => config.salt = value,
+                }
+                self.clear_password_result();
+                true
+            }
+            Msg::SetKdf(kdf) => {
+                self.password_config.kdf = kdf;
+                self.clear_password_result();
+                true
+            }
+            Msg::SetDigest(digest) => {
+                self.password_config.digest = digest;
+                self.clear_password_result();
+                true
+            }
+            Msg::SetKeySize(key_size) => {
+                self.password_config.key_size = key_size;
+                self.clear_password_result();
+                true
+            }
+            Msg::NewSalt => {
+                self.password_config.salt = hex::encode(random_openssl_salt());
+                self.clear_password_result();
+                true
+            }
+            Msg::RunOpenssl => {
+                let request = match self.action {
+                    Action::Cipher(algorithm) => {
+                        self.input_data(&ctx.props().input).and_then(|data| {
+                            self.password_config
+                                .request(algorithm, self.direction, data)
+                        })
+                    }
+                    _ => return false,
+                };
+                match request {
+                    Ok(request) => {
+                        self.clear_password_result();
+                        self.pending.push_back(self.password_generation);
+                        self.worker.send(request);
+                    }
+                    Err(e) => self.password_result = Some(Err(e)),
+                }
+                true
+            }
+            Msg::WorkerResponse(response) => {
+                // Drop results for an input or options that have since changed
+                if self.pending.pop_front() != Some(self.password_generation) {
+                    return true;
+                }
+                if let WorkerResponse::Openssl(result) = response {
+                    self.password_result = Some(result);
+                }
+                true
+            }
+            Msg::SetFernetParam(param, value) => {
+                match param {
+                    FernetParam::Key => {
+                        self.fernet_key = value;
+                        self.renew_fernet_iv();
+                    }
+                    FernetParam::Ttl => self.fernet_ttl = value,
+                }
+                true
+            }
+            Msg::GenerateFernetKey => {
+                self.fernet_key = FernetKey::generate();
+                self.renew_fernet_iv();
+                true
+            }
+        }
+    }
+
+    fn changed(&mut self, _ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        // The password result was for the previous input
+        self.clear_password_result();
+        self.renew_fernet_iv();
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+
+        let on_direction_click = link.batch_callback(|e: Event| {
+            let direction_el = e.target_dyn_into::<HtmlInputElement>();
+            direction_el.map(|btn| match btn.value().as_str() {
+                "decrypt" => Msg::SetDirection(CipherDirection::Decrypt),
+                _ => Msg::SetDirection(CipherDirection::Encrypt),
+            })
+        });
+
+        let on_action_click = link.batch_callback(|e: Event| {
+            let action_el = e.target_dyn_into::<HtmlInputElement>();
+            action_el.map(|btn| match btn.value().as_str() {
+                "fernet" => Msg::SetAction(Action::Fernet),
+                id => Msg::SetAction(Action::Cipher(
+                    CipherAlgorithm::from_id(id).unwrap_or_default(),
+                )),
+            })
+        });
+
+        let on_format_change = link.callback(|value: String| {
+            Msg::SetFormat(DigestFormat::from_id(&value).unwrap_or_default())
+        });
+
+        let format_options = DigestFormat::ALL
+            .iter()
+            .map(|format| (format.id().to_string(), format.name().to_string()))
+            .collect::<Vec<_>>();
+
+        let output = match self.action {
+            Action::Cipher(algorithm) => self.output(algorithm, &ctx.props().input),
+            Action::Fernet => self.fernet_output(&ctx.props().input),
+        };
+        let (output, error) = match output {
+            Ok(output) => (output, None),
+            Err((title, message)) => (String::default(), Some((title, message))),
+        };
+
+        html! {
+            <div class="action-component">
+                <div class="controls">
+                    <div class="form-radio-group">
+                        <label class="form-radio">
+                            <input
+                                type="radio"
+                                name="cipher-direction"
+                                value="encrypt"
+                                checked={self.direction == CipherDirection::Encrypt}
+                                onchange={&on_direction_click}
+                            />
+                            <span>{ "Encrypt" }</span>
+                        </label>
+                        <label class="form-radio">
+                            <input
+                                type="radio"
+                                name="cipher-direction"
+                                value="decrypt"
+                                checked={self.direction == CipherDirection::Decrypt}
+                                onchange={&on_direction_click}
+                            />
+                            <span>{ "Decrypt" }</span>
+                        </label>
+                    </div>
+                </div>
+                <div class="controls">
+                    <div class="custom-radio-group">
+                        { for CipherAlgorithm::ALL.iter().map(|algorithm| html! {
+                            <label class="custom-radio">
+                                <input
+                                    type="radio"
+                                    name="cipher"
+                                    value={algorithm.id()}
+                                    checked={self.action == Action::Cipher(*algorithm)}
+                                    onchange={&on_action_click}
+                                />
+                                <span>{ algorithm.name() }</span>
+                            </label>
+                        }) }
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="cipher"
+                                value="fernet"
+                                checked={self.action == Action::Fernet}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "Fernet" }</span>
+                        </label>
+                    </div>
+                </div>
+                { match self.action {
+                    Action::Cipher(algorithm) => self.view_cipher_params(ctx, algorithm),
+                    Action::Fernet => self.view_fernet_params(ctx),
+                } }
+                if self.has_binary_output() {
+                    <div class="controls">
+                        if self.direction == CipherDirection::Encrypt {
+                            {"Output as:"}
+                        } else {
+                            {"Binary plaintext as:"}
+                        }
+                        <Select label={"Output format".to_string()} options={format_options} value={self.format.id().to_string()} on_change={on_format_change} />
+                    </div>
+                }
+                <div class="overlay-container">
+                    <Textarea placeholder={"Output".to_string()} value={output} read_only={true} is_output={true} />
+                    if let Some((title, message)) = error {
+                        <div class="overlay">
+                            <div class="content">
+                                <div><strong>{ title }</strong></div>
+                                <div><em>{ message }</em></div>
+                            </div>
+                        </div>
+                    }
+                </div>
+                if self.action == Action::Fernet && self.direction == CipherDirection::Decrypt {
+                    { view_fernet_token(&ctx.props().input) }
+                }
+            </div>
+        }
+    }
+}
+
+impl Encryption {
+    fn clear_password_result(&mut self) {
+        self.password_result = None;
+        self.password_generation = self.password_generation.wrapping_add(1);
+    }
+
+    fn renew_fernet_iv(&mut self) {
+        self.fernet_iv = random_fernet_iv();
+        self.fernet_timestamp = unix_now();
+    }
+
+    /// Whether the output format applies: Fernet tokens are always text
+    fn has_binary_output(&self) -> bool {
+        !(self.action == Action::Fernet && self.direction == CipherDirection::Encrypt)
+    }
+
+    /// Key source and the matching key inputs
+    fn view_cipher_params(&self, ctx: &Context<Self>, algorithm: CipherAlgorithm) -> Html {
+        let on_key_source_click = ctx.link().batch_callback(|e: Event| {
+            let key_source_el = e.target_dyn_into::<HtmlInputElement>();
+            key_source_el.map(|btn| match btn.value().as_str() {
+                "password" => Msg::SetKeySource(KeySource::Password),
+                _ => Msg::SetKeySource(KeySource::Raw),
+            })
+        });
+
+        html! {
+            <>
+                <div class="controls">
+                    {"Key from:"}
+                    <label class="custom-radio">
+                        <input
+                            type="radio"
+                            name="cipher-key-source"
+                            value="raw"
+                            checked={self.key_source == KeySource::Raw}
+                            onchange={&on_key_source_click}
+                        />
+                        { "Raw key" }
+                    </label>
+                    <label class="custom-radio">
+                        <input
+                            type="radio"
+                            name="cipher-key-source"
+                            value="password"
+                            checked={self.key_source == KeySource::Password}
+                            onchange={&on_key_source_click}
+                        />
+                        { "Password (openssl enc)" }
+                    </label>
+                </div>
+                if self.key_source == KeySource::Password {
+                    { self.view_password_params(ctx, algorithm) }
+                } else {
+                    { self.view_params(ctx, algorithm) }
+                }
+            </>
+        }
+    }
+
+    /// Ciphertext in the chosen format, or the plaintext as text when it is
+    /// valid UTF-8. Errors come with an overlay title.
+    fn output(
+        &self,
+        algorithm: CipherAlgorithm,
+        input: &str,
+    ) -> Result<String, (&'static str, String)> {
+        let failed = |e| (self.failed_title(), e);
+        let output = match self.key_source {
+            KeySource::Raw => {
+                if self.direction == CipherDirection::Decrypt && input.trim().is_empty() {
+                    return Ok(String::default());
+                }
+                let data = self
+                    .input_data(input)
+                    .map_err(|e| ("Invalid Ciphertext", e))?;
+                self.apply_raw_key(algorithm, &data)
+            }
+            KeySource::Password => self.password_result.clone().transpose(),
+        };
+        let output = match output.map_err(failed)? {
+            Some(output) => output,
+            None => return Ok(String::default()),
+        };
+
+        match self.direction {
+            CipherDirection::Encrypt
+                if self.key_source == KeySource::Password
+                    && self.format == DigestFormat::Base64 =>
+            {
+                Ok(openssl_base64(&output))
+            }
+            CipherDirection::Encrypt => Ok(format_digest(&output, &self.format)),
+            CipherDirection::Decrypt => Ok(String::from_utf8(output)
+                .unwrap_or_else(|e| format_digest(e.as_bytes(), &self.format))),
+        }
+    }
+
+    /// A new token, or the verified plaintext
+    fn fernet_output(&self, input: &str) -> Result<String, (&'static str, String)> {
+        if self.fernet_key.trim().is_empty() {
+            return Ok(String::default());
+        }
+        let key = FernetKey::parse(&self.fernet_key).map_err(|e| ("Invalid Key", e))?;
+        let failed = |e| (self.failed_title(), e);
+        match self.direction {
+            CipherDirection::Encrypt => key
+                .encrypt(input.as_bytes(), self.fernet_timestamp, &self.fernet_iv)
+                .map_err(failed),
+            CipherDirection::Decrypt if input.trim().is_empty() => Ok(String::default()),
+            CipherDirection::Decrypt => {
+                let ttl = match self.fernet_ttl.trim() {
+                    "" => None,
+                    ttl => Some(ttl.parse().map_err(|_| {
+                        ("Invalid TTL", "TTL must be a number of seconds".to_string())
+                    })?),
+                };
+                let plaintext = key.decrypt(input, ttl, unix_now()).map_err(failed)?;
+                Ok(String::from_utf8(plaintext)
+                    .unwrap_or_else(|e| format_digest(e.as_bytes(), &self.format)))
+            }
+        }
+    }
+
+    fn failed_title(&self) -> &'static str {
+        match self.direction {
+            CipherDirection::Encrypt => "Encryption Failed",
+            CipherDirection::Decrypt => "Decryption Failed",
+        }
+    }
+
+    /// `None` until a key is entered
+    fn apply_raw_key(
+        &self,
+        algorithm: CipherAlgorithm,
+        data: &[u8],
+    ) -> Result<Option<Vec<u8>>, String> {
+        if self.key.trim().is_empty() {
+            return Ok(None);
+        }
+        let key = decode_input(&self.key, &self.key_encoding)?;
+        let nonce = decode_input(&self.nonce, &self.nonce_encoding)?;
+        let aad = decode_input(&self.aad, &self.aad_encoding)?;
+        algorithm
+            .apply(self.direction, &key, &nonce, &aad, data)
+            .map(Some)
+    }
+
+    /// Plaintext bytes, or ciphertext in any digest format
+    fn input_data(&self, input: &str) -> Result<Vec<u8>, String> {
+        match self.direction {
+            CipherDirection::Encrypt => Ok(input.as_bytes().to_vec()),
+            CipherDirection::Decrypt => parse_digest(input),
+        }
+    }
+
+    /// Password, key derivation and salt, and the matching OpenSSL command
+    fn view_password_params(&self, ctx: &Context<Self>, algorithm: CipherAlgorithm) -> Html {
+        let link = ctx.link();
+        let config = &self.password_config;
+
+        let on_password_input =
+            link.callback(|value| Msg::SetPasswordParam(PasswordParam::Password, value));
+        let on_iterations_input =
+            link.callback(|value| Msg::SetPasswordParam(PasswordParam::Iterations, value));
+        let on_salt_input =
+            link.callback(|value| Msg::SetPasswordParam(PasswordParam::Salt, value));
+        let on_new_salt_click = link.callback(|_| Msg::NewSalt);
+        let on_run_click = link.callback(|_| Msg::RunOpenssl);
+        let busy = !self.pending.is_empty();
+        let on_kdf_change = link.callback(|value: String| {
+            Msg::SetKdf(KeyDerivation::from_id(&value).unwrap_or_default())
+        });
+        let on_digest_change = link.callback(|value: String| {
+            Msg::SetDigest(HashAlgorithm::from_id(&value).unwrap_or(HashAlgorithm::Sha256))
+        });
+        let on_key_size_change =
+            link.callback(|value: String| Msg::SetKeySize(value.parse().unwrap_or(32)));
+
+        let kdf_options = KeyDerivation::ALL
+            .iter()
+            .map(|kdf| (kdf.id().to_string(), kdf.name().to_string()))
+            .collect::<Vec<_>>();
+        let digest_options = OPENSSL_DIGESTS
+            .iter()
+            .map(|digest| (digest.id().to_string(), digest.name().to_string()))
+            .collect::<Vec<_>>();
+        let key_size_options = [16, 24, 32]
+            .iter()
+            .map(|size| (size.to_string(), format!("AES-{}", size * 8)))
+            .collect::<Vec<_>>();
+
+        let command = config.params(algorithm).map(|params| {
+            let mut command = params.command();
+            if self.direction == CipherDirection::Decrypt {
+                command.push_str(" -d");
+            }
+            if self.format == DigestFormat::Base64 {
+                command.push_str(" -a");
+            }
+            if config.salt.trim().is_empty() {
+                command.push_str(" -nosalt");
+            }
+            command
+        });
+
+        html! {
+            <>
+                <div class="controls">
+                    <TextInput placeholder={"Password".to_string()} value={config.password.clone()} on_change={on_password_input} />
+                    <Select label={"Key size".to_string()} options={key_size_options} value={config.key_size.to_string()} on_change={on_key_size_change} />
+                </div>
+                <div class="controls">
+                    <Select label={"Key derivation".to_string()} options={kdf_options} value={config.kdf.id().to_string()} on_change={on_kdf_change} />
+                    <Select label={"Digest".to_string()} options={digest_options} value={config.digest.id().to_string()} on_change={on_digest_change} />
+                    if config.kdf == KeyDerivation::Pbkdf2 {
+                        {"Iterations:"}
+                        <TextInput placeholder={"Iterations".to_string()} value={config.iterations.clone()} on_change={on_iterations_input} />
+                    }
+                </div>
+                if self.direction == CipherDirection::Encrypt {
+                    <div class="controls">
+                        <TextInput placeholder={"Salt (8 bytes hex, empty for -nosalt)".to_string()} value={config.salt.clone()} on_change={on_salt_input} />
+                        <button class="button" onclick={on_new_salt_click}>{ "New salt" }</button>
+                    </div>
+                }
+                <div class="controls">
+                    <button class="button" onclick={on_run_click} disabled={busy}>
+                        if self.direction == CipherDirection::Encrypt {
+                            { "Encrypt" }
+                        } else {
+                            { "Decrypt" }
+                        }
+                    </button>
+                    if busy {
+                        <span role="status">{ "Working\u{2026}" }</span>
+                    }
+                    if let Ok(command) = command {
+                        <code>{ command }</code>
+                    }
+                </div>
+            </>
+        }
+    }
+
+    /// Key, IV or nonce, and additional data for authenticated modes
+    fn view_params(&self, ctx: &Context<Self>, algorithm: CipherAlgorithm) -> Html {
+        let link = ctx.link();
+
+        let on_key_input = link.callback(|value| Msg::SetParam(CipherParam::Key, value));
+        let on_nonce_input = link.callback(|value| Msg::SetParam(CipherParam::Nonce, value));
+        let on_aad_input = link.callback(|value| Msg::SetParam(CipherParam::Aad, value));
+        let on_key_encoding_change =
+            link.callback(|encoding| Msg::SetEncoding(CipherParam::Key, encoding));
+        let on_nonce_encoding_change =
+            link.callback(|encoding| Msg::SetEncoding(CipherParam::Nonce, encoding));
+        let on_aad_encoding_change =
+            link.callback(|encoding| Msg::SetEncoding(CipherParam::Aad, encoding));
+
+        let key_sizes = algorithm
+            .key_sizes()
+            .iter()
+            .map(|size| size.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let nonce_name = if algorithm.is_aead() { "Nonce" } else { "IV" };
+
+        html! {
+            <>
+                <div class="controls">
+                    <TextInput placeholder={format!("Key ({} bytes)", key_sizes)} value={self.key.clone()} on_change={on_key_input} />
+                    <InputEncodingRadios name={"cipher-key-encoding".to_string()} value={self.key_encoding} on_change={on_key_encoding_change} />
+                </div>
+                if let Some(nonce_size) = algorithm.nonce_size() {
+                    <div class="controls">
+                        <TextInput placeholder={format!("{} ({} bytes)", nonce_name, nonce_size)} value={self.nonce.clone()} on_change={on_nonce_input} />
+                        <InputEncodingRadios name={"cipher-nonce-encoding".to_string()} value={self.nonce_encoding} on_change={on_nonce_encoding_change} label={format!("{} as:", nonce_name)} />
+                    </div>
+                }
+                if algorithm.is_aead() {
+                    <div class="controls">
+                        <TextInput placeholder={"Additional authenticated data (optional)".to_string()} value={self.aad.clone()} on_change={on_aad_input} />
+                        <InputEncodingRadios name={"cipher-aad-encoding".to_string()} value={self.aad_encoding} on_change={on_aad_encoding_change} label={"AAD as:".to_string()} />
+                    </div>
+                }
+            </>
+        }
+    }
+
+    /// Key, and the TTL when decrypting
+    fn view_fernet_params(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let on_key_input = link.callback(|value| Msg::SetFernetParam(FernetParam::Key, value));
+        let on_ttl_input = link.callback(|value| Msg::SetFernetParam(FernetParam::Ttl, value));
+        let on_generate_click = link.callback(|_| Msg::GenerateFernetKey);
+
+        html! {
+            <>
+                <div class="controls">
+                    <TextInput placeholder={"Key (32 bytes URL-safe Base64)".to_string()} value={self.fernet_key.clone()} on_change={on_key_input} />
+                    <button class="button" onclick={on_generate_click}>{ "Generate key" }</button>
+                </div>
+                if self.direction == CipherDirection::Decrypt {
+                    <div class="controls">
+                        {"TTL (seconds):"}
+                        <TextInput placeholder={"No limit".to_string()} value={self.fernet_ttl.clone()} on_change={on_ttl_input} />
+                    </div>
+                }
+            </>
+        }
+    }
+}
+
+/// Fields of the token being decrypted, readable without the key
+fn view_fernet_token(input: &str) -> Html {
+    if input.trim().is_empty() {
+        return html! {};
+    }
+    let token = match FernetToken::parse(input) {
+        Ok(token) => token,
+        Err(e) => return html! { <p>{ format!("Unrecognized token: {}", e) }</p> },
+    };
+    let fields = [
+        ("Version", format!("0x{:02x}", token.version)),
+        (
+            "Timestamp",
+            format!(
+                "{} ({})",
+                token.timestamp,
+                format_unix_time(token.timestamp)
+            ),
+        ),
+        ("IV", hex::encode(token.iv)),
+        ("Ciphertext", format!("{} bytes", token.ciphertext.len())),
+        ("HMAC", hex::encode(token.hmac)),
+    ];
+
+    html! {
+        <table class="digest-table">
+            <caption>{ "Fernet token" }</caption>
+            <tbody>
+                { for fields.into_iter().map(|(name, value)| html! {
+                    <tr>
+                        <th scope="row">{ name }</th>
+                        <td class="digest">{ value }</td>
+                    </tr>
+                }) }
+            </tbody>
+        </table>
+    }
+}
+
+fn unix_now() -> u64 {
+    (js_sys::Date::now() / 1000.0) as u64
+}
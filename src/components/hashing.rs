@@ -1,64 +1,366 @@
-use crate::components::{TextInput, Textarea};
+use crate::components::{
+    Checkbox, CopyButton, InputEncodingRadios, Select, TextInput, Textarea, Verification,
+};
 use crate::engine::{
-    blake2b512_hash, blake2s256_hash, hmac_digest_b64, hmac_digest_hex, md5_hash, sha1_hash,
-    sha224_hash, sha256_hash, sha3224_hash, sha3256_hash, sha3384_hash, sha3512_hash, sha384_hash,
-    sha512_hash,
+    age_generate_identity, blake2_digest, blake3_xof, decode_input, digests_match, format_digest,
+    hmac_digest, identify_hash, parse_digest, AgeKeys, Blake2Variant, Blake3Mode,
+    ChecksumAlgorithm, CipherDirection, CrcParams, DigestFormat, FastHashAlgorithm, HashAlgorithm,
+    InputEncoding, SeedKind, XofAlgorithm, CRC_CATALOGUE,
 };
+use crate::worker::{BackgroundWorker, WorkerRequest, WorkerResponse, WORKER_PATH};
+use gloo_worker::{Spawnable, WorkerBridge};
+use std::collections::VecDeque;
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 
+const AGE_ARMOR_BEGIN: &str = "-----BEGIN AGE ENCRYPTED FILE-----";
+
 pub enum Msg {
     SetAction(Action),
+    SetHmacAlgorithm(HashAlgorithm),
     SetHmacKey(String),
-    SetHmacEncoding(HmacEncoding),
+    SetHmacKeyEncoding(InputEncoding),
+    SetFormat(DigestFormat),
+    SetExpected(String),
+    SetPlaintext(String),
+    SetCrcPreset(String),
+    SetCrcParam(CrcParam, String),
+    SetCrcRefIn(bool),
+    SetCrcRefOut(bool),
+    SetFastHashAlgorithm(FastHashAlgorithm),
+    SetFastHashSeed(String),
+    SetBlake2Parallel(bool),
+    SetBlake2Length(String),
+    SetBlake2Param(Blake2Param, String),
+    SetBlake2Encoding(Blake2Param, InputEncoding),
+    SetBlake3Mode(Blake3Mode),
+    SetBlake3Key(String),
+    SetBlake3KeyEncoding(InputEncoding),
+    SetBlake3Context(String),
+    SetBlake3Length(String),
+    SetXofAlgorithm(XofAlgorithm),
+    SetXofKey(String),
+    SetXofKeyEncoding(InputEncoding),
+    SetXofCustomization(String),
+    SetXofLength(String),
+    SetAgeDirection(CipherDirection),
+    SetAgeKeyType(AgeKeyType),
+    SetAgeParam(AgeParam, String),
+    SetAgeArmor(bool),
+    GenerateAgeIdentity,
+    RunAge,
+    WorkerResponse(WorkerResponse),
 }
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Action {
-    Md5,
-    Sha1,
-    Sha224,
-    Sha256,
-    Sha384,
-    Sha512,
-    Blake2b512,
-    Blake2s256,
-    Sha3224,
-    Sha3256,
-    Sha3384,
-    Sha3512,
+    Digest(HashAlgorithm),
     Hmac,
+    /// An age file, encrypted in the worker
+    Age,
+    Xof,
+    All,
+    Identify,
+    Crc,
+    Adler32,
+    Fletcher16,
+    Fletcher32,
+    FastHash,
 }
 
 impl Default for Action {
     fn default() -> Self {
-        Action::Md5
+        Action::Digest(HashAlgorithm::default())
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
-pub enum HmacEncoding {
-    Hex,
-    Base64,
+#[derive(Debug, PartialEq)]
+struct HmacConfig {
+    algorithm: HashAlgorithm,
+    key: String,
+    key_encoding: InputEncoding,
+}
+
+impl Default for HmacConfig {
+    fn default() -> Self {
+        HmacConfig {
+            algorithm: HashAlgorithm::Sha256,
+            key: String::default(),
+            key_encoding: InputEncoding::default(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Blake2Param {
+    Key,
+    Salt,
+    Personal,
+}
+
+/// Optional BLAKE2 parameters, shared by the BLAKE2b and BLAKE2s entries
+#[derive(Debug, PartialEq, Default)]
+struct Blake2Config {
+    parallel: bool,
+    /// Digest length in bytes, empty for the full size
+    length: String,
+    key: String,
+    key_encoding: InputEncoding,
+    salt: String,
+    salt_encoding: InputEncoding,
+    personal: String,
+    personal_encoding: InputEncoding,
+}
+
+impl Blake2Config {
+    fn variant(&self, algorithm: HashAlgorithm) -> Blake2Variant {
+        match (algorithm, self.parallel) {
+            (HashAlgorithm::Blake2s256, false) => Blake2Variant::Blake2s,
+            (HashAlgorithm::Blake2s256, true) => Blake2Variant::Blake2sp,
+            (_, false) => Blake2Variant::Blake2b,
+            (_, true) => Blake2Variant::Blake2bp,
+        }
+    }
+
+    fn digest(&self, algorithm: HashAlgorithm, input: &[u8]) -> Result<Vec<u8>, String> {
+        let variant = self.variant(algorithm);
+        let length = match self.length.trim() {
+            "" => variant.output_size(),
+            length => parse_output_length(length)?,
+        };
+        let key = decode_input(&self.key, &self.key_encoding)?;
+        let (salt, personal) = match variant.salt_size() {
+            Some(_) => (
+                decode_input(&self.salt, &self.salt_encoding)?,
+                decode_input(&self.personal, &self.personal_encoding)?,
+            ),
+            None => (Vec::new(), Vec::new()),
+        };
+        blake2_digest(&variant, &key, &salt, &personal, input, length)
+    }
 }
 
-impl Default for HmacEncoding {
+#[derive(Debug, PartialEq)]
+struct Blake3Config {
+    mode: Blake3Mode,
+    key: String,
+    key_encoding: InputEncoding,
+    context: String,
+    length: String,
+}
+
+impl Default for Blake3Config {
     fn default() -> Self {
-        HmacEncoding::Hex
+        Blake3Config {
+            mode: Blake3Mode::default(),
+            key: String::default(),
+            key_encoding: InputEncoding::Hex,
+            context: String::default(),
+            length: "32".to_string(),
+        }
+    }
+}
+
+impl Blake3Config {
+    fn digest(&self, input: &[u8]) -> Result<Vec<u8>, String> {
+        let length = parse_output_length(&self.length)?;
+        let key = match self.mode {
+            Blake3Mode::Keyed => decode_input(&self.key, &self.key_encoding)?,
+            _ => Vec::new(),
+        };
+        blake3_xof(&self.mode, &key, &self.context, input, length)
     }
 }
 
 #[derive(Debug, PartialEq)]
-struct HmacConfig {
+struct XofConfig {
+    algorithm: XofAlgorithm,
     key: String,
-    encoding: HmacEncoding,
+    key_encoding: InputEncoding,
+    customization: String,
+    length: String,
 }
 
-impl Default for HmacConfig {
+impl Default for XofConfig {
     fn default() -> Self {
-        HmacConfig {
+        XofConfig {
+            algorithm: XofAlgorithm::default(),
             key: String::default(),
-            encoding: HmacEncoding::Hex,
+            key_encoding: InputEncoding::default(),
+            customization: String::default(),
+            length: "32".to_string(),
+        }
+    }
+}
+
+impl XofConfig {
+    fn output(&self, input: &[u8]) -> Result<Vec<u8>, String> {
+        let length = parse_output_length(&self.length)?;
+        let key = if self.algorithm.is_keyed() {
+            decode_input(&self.key, &self.key_encoding)?
+        } else {
+            Vec::new()
+        };
+        Ok(self
+            .algorithm
+            .output(&key, self.customization.as_bytes(), input, length))
+    }
+}
+
+#[derive(Debug, PartialEq, Default)]
+struct FastHashConfig {
+    algorithm: FastHashAlgorithm,
+    seed: String,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum AgeKeyType {
+    #[default]
+    X25519,
+    /// scrypt
+    Passphrase,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum AgeParam {
+    Recipients,
+    Identities,
+    Passphrase,
+}
+
+/// Keys and options for age files
+#[derive(Debug, PartialEq)]
+struct AgeConfig {
+    direction: CipherDirection,
+    key_type: AgeKeyType,
+    /// `age1...` keys to encrypt to
+    recipients: String,
+    /// `AGE-SECRET-KEY-1...` keys to decrypt with
+    identities: String,
+    passphrase: String,
+    armor: bool,
+}
+
+impl Default for AgeConfig {
+    fn default() -> Self {
+        AgeConfig {
+            direction: CipherDirection::default(),
+            key_type: AgeKeyType::default(),
+            recipients: String::default(),
+            identities: String::default(),
+            passphrase: String::default(),
+            armor: true,
+        }
+    }
+}
+
+impl AgeConfig {
+    fn keys(&self) -> Result<AgeKeys, String> {
+        let (text, kind) = match (self.key_type, self.direction) {
+            (AgeKeyType::Passphrase, _) if self.passphrase.is_empty() => {
+                return Err("Passphrase is required".to_string())
+            }
+            (AgeKeyType::Passphrase, _) => return Ok(AgeKeys::Passphrase(self.passphrase.clone())),
+            (AgeKeyType::X25519, CipherDirection::Encrypt) => (&self.recipients, "recipient"),
+            (AgeKeyType::X25519, CipherDirection::Decrypt) => (&self.identities, "identity"),
+        };
+        match AgeKeys::x25519_from_text(text) {
+            AgeKeys::X25519(keys) if keys.is_empty() => {
+                Err(format!("At least one {} is required", kind))
+            }
+            keys => Ok(keys),
+        }
+    }
+
+    fn failed_title(&self) -> &'static str {
+        match self.direction {
+            CipherDirection::Encrypt => "Encryption Failed",
+            CipherDirection::Decrypt => "Decryption Failed",
+        }
+    }
+
+    /// The age request for `input`, which is ciphertext in any digest format
+    /// or an armored file when decrypting
+    fn request(&self, input: &str) -> Result<WorkerRequest, String> {
+        let keys = self.keys()?;
+        match self.direction {
+            CipherDirection::Encrypt => Ok(WorkerRequest::AgeEncrypt {
+                plaintext: input.as_bytes().to_vec(),
+                keys,
+                armor: self.armor,
+            }),
+            CipherDirection::Decrypt => {
+                let ciphertext = if input.contains(AGE_ARMOR_BEGIN) {
+                    input.trim().as_bytes().to_vec()
+                } else {
+                    parse_digest(input)?
+                };
+                Ok(WorkerRequest::AgeDecrypt { ciphertext, keys })
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum CrcParam {
+    Width,
+    Poly,
+    Init,
+    XorOut,
+}
+
+/// Selected CRC, either a catalogue entry or custom parameters
+#[derive(Debug, PartialEq)]
+struct CrcConfig {
+    preset: String,
+    width: String,
+    poly: String,
+    init: String,
+    refin: bool,
+    refout: bool,
+    xorout: String,
+}
+
+impl Default for CrcConfig {
+    fn default() -> Self {
+        let mut config = CrcConfig {
+            preset: String::default(),
+            width: String::default(),
+            poly: String::default(),
+            init: String::default(),
+            refin: false,
+            refout: false,
+            xorout: String::default(),
+        };
+        config.load_preset(CrcParams::default());
+        config
+    }
+}
+
+impl CrcConfig {
+    /// Fill the custom fields from a preset, so it can be used as a starting point
+    fn load_preset(&mut self, params: CrcParams) {
+        let digits = usize::from(params.width).div_ceil(4);
+        self.preset = params.name.to_string();
+        self.width = params.width.to_string();
+        self.poly = format!("0x{:0digits$x}", params.poly);
+        self.init = format!("0x{:0digits$x}", params.init);
+        self.refin = params.refin;
+        self.refout = params.refout;
+        self.xorout = format!("0x{:0digits$x}", params.xorout);
+    }
+
+    fn params(&self) -> Result<CrcParams, String> {
+        match CrcParams::from_name(&self.preset) {
+            Some(params) => Ok(params),
+            None => CrcParams::custom(
+                &self.width,
+                &self.poly,
+                &self.init,
+                self.refin,
+                self.refout,
+                &self.xorout,
+            ),
         }
     }
 }
@@ -68,263 +370,1070 @@ pub struct Props {
     pub input: String,
 }
 
-#[derive(Default)]
 pub struct Hashing {
     action: Action,
     hmac_config: HmacConfig,
+    format: DigestFormat,
+    expected: String,
+    plaintext: String,
+    crc_config: CrcConfig,
+    fast_hash_config: FastHashConfig,
+    blake2_config: Blake2Config,
+    blake3_config: Blake3Config,
+    xof_config: XofConfig,
+    age_config: AgeConfig,
+    age_result: Option<Result<Vec<u8>, String>>,
+    /// Bumped whenever the age result goes stale
+    age_generation: u32,
+    /// Generations of age requests still running in the worker, oldest first
+    pending: VecDeque<u32>,
+    worker: WorkerBridge<BackgroundWorker>,
 }
 
 impl Component for Hashing {
     type Message = Msg;
     type Properties = Props;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self::default()
+    fn create(ctx: &Context<Self>) -> Self {
+        let on_response = ctx.link().callback(Msg::WorkerResponse);
+        let worker = BackgroundWorker::spawner()
+            .callback(move |response| on_response.emit(response))
+            .spawn(WORKER_PATH);
+
+        Self {
+            action: Action::default(),
+            hmac_config: HmacConfig::default(),
+            format: DigestFormat::default(),
+            expected: String::default(),
+            plaintext: String::default(),
+            crc_config: CrcConfig::default(),
+            fast_hash_config: FastHashConfig::default(),
+            blake2_config: Blake2Config::default(),
+            blake3_config: Blake3Config::default(),
+            xof_config: XofConfig::default(),
+            age_config: AgeConfig::default(),
+            age_result: None,
+            age_generation: 0,
+            pending: VecDeque::new(),
+            worker,
+        }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::SetAction(action) => {
                 self.action = action;
                 true
             }
+            Msg::SetHmacAlgorithm(algorithm) => {
+                self.hmac_config.algorithm = algorithm;
+                true
+            }
             Msg::SetHmacKey(key) => {
                 self.hmac_config.key = key;
                 true
             }
-            Msg::SetHmacEncoding(encoding) => {
-                self.hmac_config.encoding = encoding;
+            Msg::SetHmacKeyEncoding(key_encoding) => {
+                self.hmac_config.key_encoding = key_encoding;
+                true
+            }
+            Msg::SetFormat(format) => {
+                self.format = format;
+                true
+            }
+            Msg::SetExpected(expected) => {
+                if let Action::Digest(current) = self.action {
+                    if let Some(detected) = detect_algorithm(&ctx.props().input, &expected, current)
+                    {
+                        self.action = Action::Digest(detected);
+                    }
+                }
+                self.expected = expected;
+                true
+            }
+            Msg::SetPlaintext(plaintext) => {
+                self.plaintext = plaintext;
+                true
+            }
+            Msg::SetCrcPreset(preset) => {
+                match CrcParams::from_name(&preset) {
+                    Some(params) => self.crc_config.load_preset(params),
+                    None => self.crc_config.preset = preset,
+                }
+                true
+            }
+            Msg::SetCrcParam(param, value) => {
+                self.crc_config.preset = CUSTOM_CRC.to_string();
+                match param {
+                    CrcParam::Width => self.crc_config.width = value,
+                    CrcParam::Poly => self.crc_config.poly = value,
+                    CrcParam::Init => self.crc_config.init = value,
+                    CrcParam::XorOut => self.crc_config.xorout = value,
+                }
+                true
+            }
+            Msg::SetCrcRefIn(refin) => {
+                self.crc_config.preset = CUSTOM_CRC.to_string();
+                self.crc_config.refin = refin;
+                true
+            }
+            Msg::SetCrcRefOut(refout) => {
+                self.crc_config.preset = CUSTOM_CRC.to_string();
+                self.crc_config.refout = refout;
+                true
+            }
+            Msg::SetFastHashAlgorithm(algorithm) => {
+                self.fast_hash_config.algorithm = algorithm;
+                true
+            }
+            Msg::SetFastHashSeed(seed) => {
+                self.fast_hash_config.seed = seed;
+                true
+            }
+            Msg::SetBlake2Parallel(parallel) => {
+                self.blake2_config.parallel = parallel;
+                true
+            }
+            Msg::SetBlake2Length(length) => {
+                self.blake2_config.length = length;
+                true
+            }
+            Msg::SetBlake2Param(param, value) => {
+                match param {
+                    Blake2Param::Key => self.blake2_config.key = value,
+                    Blake2Param::Salt => self.blake2_config.salt = value,
+                    Blake2Param::Personal => self.blake2_config.personal = value,
+                }
+                true
+            }
+            Msg::SetBlake2Encoding(param, encoding) => {
+                match param {
+                    Blake2Param::Key => self.blake2_config.key_encoding = encoding,
+                    Blake2Param::Salt => self.blake2_config.salt_encoding = encoding,
+                    Blake2Param::Personal => self.blake2_config.personal_encoding = encoding,
+                }
+                true
+            }
+            Msg::SetBlake3Mode(mode) => {
+                self.blake3_config.mode = mode;
+                true
+            }
+            Msg::SetBlake3Key(key) => {
+                self.blake3_config.key = key;
+                true
+            }
+            Msg::SetBlake3KeyEncoding(key_encoding) => {
+                self.blake3_config.key_encoding = key_encoding;
+                true
+            }
+            Msg::SetBlake3Context(context) => {
+                self.blake3_config.context = context;
+                true
+            }
+            Msg::SetBlake3Length(length) => {
+                self.blake3_config.length = length;
+                true
+            }
+            Msg::SetXofAlgorithm(algorithm) => {
+                self.xof_config.algorithm = algorithm;
+                true
+            }
+            Msg::SetXofKey(key) => {
+                self.xof_config.key = key;
+                true
+            }
+            Msg::SetXofKeyEncoding(key_encoding) => {
+                self.xof_config.key_encoding = key_encoding;
+                true
+            }
+            Msg::SetXofCustomization(customization) => {
+                self.xof_config.customization = customization;
+                true
+            }
+            Msg::SetXofLength(length) => {
+                self.xof_config.length = length;
+                true
+            }
+            Msg::SetAgeDirection(direction) => {
+                self.age_config.direction = direction;
+                self.clear_age_result();
+                true
+            }
+            Msg::SetAgeKeyType(key_type) => {
+                self.age_config.key_type = key_type;
+                self.clear_age_result();
+                true
+            }
+            Msg::SetAgeParam(param, value) => {
+                let config = &mut self.age_config;
+                match param {
+                    AgeParam::Recipients => config.recipients = value,
+                    AgeParam::Identities => config.identities = value,
+                    AgeParam::Passphrase => config.passphrase = value,
+                }
+                self.clear_age_result();
+                true
+            }
+            Msg::SetAgeArmor(armor) => {
+                self.age_config.armor = armor;
+                self.clear_age_result();
+                true
+            }
+            Msg::GenerateAgeIdentity => {
+                let (identity, recipient) = age_generate_identity();
+                self.age_config.identities = identity;
+                self.age_config.recipients = recipient;
+                self.clear_age_result();
+                true
+            }
+            Msg::RunAge => {
+                match self.age_config.request(&ctx.props().input) {
+                    Ok(request) => {
+                        self.clear_age_result();
+                        self.pending.push_back(self.age_generation);
+                        self.worker.send(request);
+                    }
+                    Err(e) => self.age_result = Some(Err(e)),
+                }
+                true
+            }
+            Msg::WorkerResponse(response) => {
+                // Drop results for an input or options that have since changed
+                if self.pending.pop_front() != Some(self.age_generation) {
+                    return true;
+                }
+                if let WorkerResponse::Age(result) = response {
+                    self.age_result = Some(result);
+                }
                 true
             }
         }
     }
 
+    fn changed(&mut self, _ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        // The age result was for the previous input
+        self.clear_age_result();
+        true
+    }
+
     fn view(&self, ctx: &Context<Self>) -> Html {
         let link = ctx.link();
 
         let on_action_click = link.batch_callback(|e: Event| {
             let action_el = e.target_dyn_into::<HtmlInputElement>();
             action_el.map(|btn| match btn.value().as_str() {
-                "md5" => Msg::SetAction(Action::Md5),
-                "sha1" => Msg::SetAction(Action::Sha1),
-                "sha224" => Msg::SetAction(Action::Sha224),
-                "sha256" => Msg::SetAction(Action::Sha256),
-                "sha384" => Msg::SetAction(Action::Sha384),
-                "sha512" => Msg::SetAction(Action::Sha512),
-                "blake2b512" => Msg::SetAction(Action::Blake2b512),
-                "blake2s256" => Msg::SetAction(Action::Blake2s256),
-                "sha3224" => Msg::SetAction(Action::Sha3224),
-                "sha3256" => Msg::SetAction(Action::Sha3256),
-                "sha3384" => Msg::SetAction(Action::Sha3384),
-                "sha3512" => Msg::SetAction(Action::Sha3512),
                 "hmac" => Msg::SetAction(Action::Hmac),
-                _ => Msg::SetAction(Action::default()),
+                "age" => Msg::SetAction(Action::Age),
+                "xof" => Msg::SetAction(Action::Xof),
+                "all" => Msg::SetAction(Action::All),
+                "identify" => Msg::SetAction(Action::Identify),
+                "crc" => Msg::SetAction(Action::Crc),
+                "adler32" => Msg::SetAction(Action::Adler32),
+                "fletcher16" => Msg::SetAction(Action::Fletcher16),
+                "fletcher32" => Msg::SetAction(Action::Fletcher32),
+                "fast" => Msg::SetAction(Action::FastHash),
+                id => Msg::SetAction(
+                    HashAlgorithm::from_id(id)
+                        .map(Action::Digest)
+                        .unwrap_or_default(),
+                ),
             })
         });
 
-        let on_secret_input = link.callback(|value| Msg::SetHmacKey(value));
+        let on_algorithm_change = link.callback(|value: String| {
+            Msg::SetHmacAlgorithm(HashAlgorithm::from_id(&value).unwrap_or_default())
+        });
 
-        let on_encoding_click = link.batch_callback(|e: Event| {
-            let encoding_btn = e.target_dyn_into::<HtmlInputElement>();
-            encoding_btn.map(|btn| match btn.value().as_str() {
-                "hex" => Msg::SetHmacEncoding(HmacEncoding::Hex),
-                "base64" => Msg::SetHmacEncoding(HmacEncoding::Base64),
-                _ => Msg::SetHmacEncoding(HmacEncoding::default()),
-            })
+        let on_secret_input = link.callback(Msg::SetHmacKey);
+
+        let on_key_encoding_change = link.callback(Msg::SetHmacKeyEncoding);
+
+        let on_format_change = link.callback(|value: String| {
+            Msg::SetFormat(DigestFormat::from_id(&value).unwrap_or_default())
         });
 
-        let output;
+        let on_expected_input = link.callback(Msg::SetExpected);
+
+        let on_plaintext_input = link.callback(Msg::SetPlaintext);
+
+        let mut output = String::default();
+        let mut verification = None;
+        let mut failed = false;
+        let mut error_title = "";
+        let mut error_message = String::default();
         let input = ctx.props().input.as_str();
 
-        match &self.action {
-            Action::Md5 => output = md5_hash(input),
-            Action::Sha1 => output = sha1_hash(input),
-            Action::Sha224 => output = sha224_hash(input),
-            Action::Sha256 => output = sha256_hash(input),
-            Action::Sha384 => output = sha384_hash(input),
-            Action::Sha512 => output = sha512_hash(input),
-            Action::Blake2b512 => output = blake2b512_hash(input),
-            Action::Blake2s256 => output = blake2s256_hash(input),
-            Action::Sha3224 => output = sha3224_hash(input),
-            Action::Sha3256 => output = sha3256_hash(input),
-            Action::Sha3384 => output = sha3384_hash(input),
-            Action::Sha3512 => output = sha3512_hash(input),
-            Action::Hmac => match &self.hmac_config.encoding {
-                HmacEncoding::Hex => {
-                    output = hmac_digest_hex(self.hmac_config.key.as_str(), input);
+        let digest = match &self.action {
+            Action::Digest(HashAlgorithm::Blake3) => {
+                match self.blake3_config.digest(input.as_bytes()) {
+                    Ok(digest) => Some(digest),
+                    Err(e) => {
+                        failed = true;
+                        error_title = "Invalid Parameters";
+                        error_message = e;
+                        None
+                    }
                 }
-                HmacEncoding::Base64 => {
-                    output = hmac_digest_b64(self.hmac_config.key.as_str(), input);
+            }
+            Action::Digest(algorithm @ (HashAlgorithm::Blake2b512 | HashAlgorithm::Blake2s256)) => {
+                match self.blake2_config.digest(*algorithm, input.as_bytes()) {
+                    Ok(digest) => Some(digest),
+                    Err(e) => {
+                        failed = true;
+                        error_title = "Invalid Parameters";
+                        error_message = e;
+                        None
+                    }
+                }
+            }
+            Action::Digest(algorithm) => Some(algorithm.digest(input.as_bytes())),
+            Action::Hmac => {
+                let code = decode_input(&self.hmac_config.key, &self.hmac_config.key_encoding)
+                    .and_then(|key| {
+                        hmac_digest(self.hmac_config.algorithm, &key, input.as_bytes())
+                    });
+                match code {
+                    Ok(code) => Some(code),
+                    Err(e) => {
+                        failed = true;
+                        error_title = "Invalid Key";
+                        error_message = e;
+                        None
+                    }
+                }
+            }
+            Action::Xof => match self.xof_config.output(input.as_bytes()) {
+                Ok(output) => Some(output),
+                Err(e) => {
+                    failed = true;
+                    error_title = "Invalid Parameters";
+                    error_message = e;
+                    None
                 }
             },
+            Action::Crc => match self.crc_config.params() {
+                Ok(params) => Some(ChecksumAlgorithm::Crc(params).checksum(input.as_bytes())),
+                Err(e) => {
+                    failed = true;
+                    error_title = "Invalid Parameters";
+                    error_message = e;
+                    None
+                }
+            },
+            Action::Adler32 => Some(ChecksumAlgorithm::Adler32.checksum(input.as_bytes())),
+            Action::Fletcher16 => Some(ChecksumAlgorithm::Fletcher16.checksum(input.as_bytes())),
+            Action::Fletcher32 => Some(ChecksumAlgorithm::Fletcher32.checksum(input.as_bytes())),
+            Action::FastHash => {
+                let config = &self.fast_hash_config;
+                match config.algorithm.hash(input.as_bytes(), &config.seed) {
+                    Ok(hash) => Some(hash),
+                    Err(e) => {
+                        failed = true;
+                        error_title = "Invalid Seed";
+                        error_message = e;
+                        None
+                    }
+                }
+            }
+            Action::All | Action::Identify | Action::Age => None,
+        };
+
+        if let Some(digest) = digest {
+            output = format_digest(&digest, &self.format);
+            if !self.expected.trim().is_empty() {
+                verification = Some(
+                    parse_digest(&self.expected).map(|expected| digests_match(&expected, &digest)),
+                );
+            }
         }
 
+        let algorithm_options = HashAlgorithm::ALL
+            .iter()
+            .map(|algorithm| (algorithm.id().to_string(), algorithm.name().to_string()))
+            .collect::<Vec<_>>();
+
+        let format_options = DigestFormat::ALL
+            .iter()
+            .map(|format| (format.id().to_string(), format.name().to_string()))
+            .collect::<Vec<_>>();
+
         html! {
             <div class="action-component">
                 <div class="control">
                     <div class="custom-radio-group">
+                        { for HashAlgorithm::ALL.iter().map(|algorithm| html! {
+                            <label class="custom-radio">
+                                <input
+                                    type="radio"
+                                    name="action"
+                                    value={algorithm.id()}
+                                    checked={self.action == Action::Digest(*algorithm)}
+                                    onchange={&on_action_click}
+                                />
+                                <span>{ algorithm.name() }</span>
+                            </label>
+                        }) }
                         <label class="custom-radio">
                             <input
                                 type="radio"
                                 name="action"
-                                value="md5"
-                                checked={self.action == Action::Md5}
-                                onchange={&on_action_click}
-                            />
-                            <span>{ "MD5" }</span>
-                        </label>
-                        <label class="custom-radio">
-                            <input
-                                type="radio"
-                                name="action"
-                                value="sha1"
-                                checked={self.action == Action::Sha1}
-                                onchange={&on_action_click}
-                            />
-                            <span>{ "SHA-1" }</span>
-                        </label>
-                        <label class="custom-radio">
-                            <input
-                                type="radio"
-                                name="action"
-                                value="sha224"
-                                checked={self.action == Action::Sha224}
-                                onchange={&on_action_click}
-                            />
-                            <span>{ "SHA-224" }</span>
-                        </label>
-                        <label class="custom-radio">
-                            <input
-                                type="radio"
-                                name="action"
-                                value="sha256"
-                                checked={self.action == Action::Sha256}
+                                value="hmac"
+                                checked={self.action == Action::Hmac}
                                 onchange={&on_action_click}
                             />
-                            <span>{ "SHA-256" }</span>
+                            <span>{ "HMAC Digest" }</span>
                         </label>
                         <label class="custom-radio">
                             <input
                                 type="radio"
                                 name="action"
-                                value="sha384"
-                                checked={self.action == Action::Sha384}
+                                value="age"
+                                checked={self.action == Action::Age}
                                 onchange={&on_action_click}
                             />
-                            <span>{ "SHA-384" }</span>
+                            <span>{ "age" }</span>
                         </label>
                         <label class="custom-radio">
                             <input
                                 type="radio"
                                 name="action"
-                                value="sha512"
-                                checked={self.action == Action::Sha512}
+                                value="xof"
+                                checked={self.action == Action::Xof}
                                 onchange={&on_action_click}
                             />
-                            <span>{ "SHA-512" }</span>
+                            <span>{ "SHAKE / KMAC" }</span>
                         </label>
                         <label class="custom-radio">
                             <input
                                 type="radio"
                                 name="action"
-                                value="blake2b512"
-                                checked={self.action == Action::Blake2b512}
+                                value="all"
+                                checked={self.action == Action::All}
                                 onchange={&on_action_click}
                             />
-                            <span>{ "BLAKE2b-512" }</span>
+                            <span>{ "All Algorithms" }</span>
                         </label>
                         <label class="custom-radio">
                             <input
                                 type="radio"
                                 name="action"
-                                value="blake2s256"
-                                checked={self.action == Action::Blake2s256}
+                                value="identify"
+                                checked={self.action == Action::Identify}
                                 onchange={&on_action_click}
                             />
-                            <span>{ "BLAKE2s-256" }</span>
+                            <span>{ "Identify Hash" }</span>
                         </label>
+                    </div>
+                    <div class="custom-radio-group">
                         <label class="custom-radio">
                             <input
                                 type="radio"
                                 name="action"
-                                value="sha3224"
-                                checked={self.action == Action::Sha3224}
+                                value="crc"
+                                checked={self.action == Action::Crc}
                                 onchange={&on_action_click}
                             />
-                            <span>{ "SHA3-224" }</span>
+                            <span>{ "CRC" }</span>
                         </label>
                         <label class="custom-radio">
                             <input
                                 type="radio"
                                 name="action"
-                                value="sha3256"
-                                checked={self.action == Action::Sha3256}
+                                value="adler32"
+                                checked={self.action == Action::Adler32}
                                 onchange={&on_action_click}
                             />
-                            <span>{ "SHA3-256" }</span>
+                            <span>{ "Adler-32" }</span>
                         </label>
                         <label class="custom-radio">
                             <input
                                 type="radio"
                                 name="action"
-                                value="sha3384"
-                                checked={self.action == Action::Sha3384}
+                                value="fletcher16"
+                                checked={self.action == Action::Fletcher16}
                                 onchange={&on_action_click}
                             />
-                            <span>{ "SHA3-384" }</span>
+                            <span>{ "Fletcher-16" }</span>
                         </label>
                         <label class="custom-radio">
                             <input
                                 type="radio"
                                 name="action"
-                                value="sha3512"
-                                checked={self.action == Action::Sha3512}
+                                value="fletcher32"
+                                checked={self.action == Action::Fletcher32}
                                 onchange={&on_action_click}
                             />
-                            <span>{ "SHA3-512" }</span>
+                            <span>{ "Fletcher-32" }</span>
                         </label>
                         <label class="custom-radio">
                             <input
                                 type="radio"
                                 name="action"
-                                value="hmac"
-                                checked={self.action == Action::Hmac}
+                                value="fast"
+                                checked={self.action == Action::FastHash}
                                 onchange={&on_action_click}
                             />
-                            <span>{ "HMAC Digest" }</span>
+                            <span>{ "Fast Hash" }</span>
                         </label>
                     </div>
                 </div>
                 <div class="controls">
+                    if self.action == Action::Crc {
+                        { self.view_crc_options(ctx) }
+                    }
+                    if self.action == Action::FastHash {
+                        { self.view_fast_hash_options(ctx) }
+                    }
+                    if self.action == Action::Xof {
+                        { self.view_xof_options(ctx) }
+                    }
+                    if let Action::Digest(
+                        algorithm @ (HashAlgorithm::Blake2b512 | HashAlgorithm::Blake2s256),
+                    ) = self.action
+                    {
+                        { self.view_blake2_options(ctx, algorithm) }
+                    }
+                    if self.action == Action::Digest(HashAlgorithm::Blake3) {
+                        { self.view_blake3_options(ctx) }
+                    }
                     if self.action == Action::Hmac {
+                        <Select label={"HMAC hash algorithm".to_string()} options={algorithm_options} value={self.hmac_config.algorithm.id().to_string()} on_change={on_algorithm_change} />
                         <TextInput placeholder={"Enter secret key".to_string()} value={self.hmac_config.key.clone()} on_change={on_secret_input} />
+                        <InputEncodingRadios name={"hmac-key-encoding".to_string()} value={self.hmac_config.key_encoding} on_change={on_key_encoding_change} />
+                    }
+                </div>
+                if self.action == Action::Identify {
+                    <div class="controls">
+                        <TextInput placeholder={"Known plaintext (optional)".to_string()} value={self.plaintext.clone()} on_change={on_plaintext_input} />
+                    </div>
+                } else if self.action != Action::Age {
+                    <div class="controls">
+                        <TextInput placeholder={"Expected digest (optional)".to_string()} value={self.expected.clone()} on_change={on_expected_input} />
+                        <Verification result={verification} />
+                    </div>
+                    <div class="controls">
                         {"Output as:"}
-                        <label class="custom-radio">
+                        <Select label={"Output format".to_string()} options={format_options} value={self.format.id().to_string()} on_change={on_format_change} />
+                    </div>
+                }
+                if self.action == Action::All {
+                    { self.view_all(input) }
+                } else if self.action == Action::Identify {
+                    { self.view_identify(input) }
+                } else if self.action == Action::Age {
+                    { self.view_age(ctx) }
+                } else {
+                    <div class="overlay-container">
+                        <Textarea placeholder={"Output".to_string()} value={output} read_only={true} is_output={true} />
+                        if failed {
+                            <div class="overlay">
+                                <div class="content">
+                                    <div><strong>{ error_title }</strong></div>
+                                    <div><em>{ error_message }</em></div>
+                                </div>
+                            </div>
+                        }
+                    </div>
+                }
+            </div>
+        }
+    }
+}
+
+impl Hashing {
+    fn clear_age_result(&mut self) {
+        self.age_result = None;
+        self.age_generation = self.age_generation.wrapping_add(1);
+    }
+
+    /// The finished age file, armored or in the chosen format, or the
+    /// decrypted plaintext
+    fn age_output(&self) -> Result<String, (&'static str, String)> {
+        let output = match &self.age_result {
+            Some(Ok(output)) => output,
+            Some(Err(e)) => return Err((self.age_config.failed_title(), e.clone())),
+            None => return Ok(String::default()),
+        };
+        match self.age_config.direction {
+            CipherDirection::Encrypt if self.age_config.armor => {
+                Ok(String::from_utf8_lossy(output).into_owned())
+            }
+            CipherDirection::Encrypt => Ok(format_digest(output, &self.format)),
+            CipherDirection::Decrypt => Ok(String::from_utf8(output.clone())
+                .unwrap_or_else(|e| format_digest(e.as_bytes(), &self.format))),
+        }
+    }
+
+    /// Direction, key type, recipients, identities or passphrase, the button
+    /// that runs age in the worker, and its output
+    fn view_age(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let config = &self.age_config;
+
+        let on_direction_click = link.batch_callback(|e: Event| {
+            let direction_el = e.target_dyn_into::<HtmlInputElement>();
+            direction_el.map(|btn| match btn.value().as_str() {
+                "decrypt" => Msg::SetAgeDirection(CipherDirection::Decrypt),
+                _ => Msg::SetAgeDirection(CipherDirection::Encrypt),
+            })
+        });
+        let on_key_type_click = link.batch_callback(|e: Event| {
+            let key_type_el = e.target_dyn_into::<HtmlInputElement>();
+            key_type_el.map(|btn| match btn.value().as_str() {
+                "passphrase" => Msg::SetAgeKeyType(AgeKeyType::Passphrase),
+                _ => Msg::SetAgeKeyType(AgeKeyType::X25519),
+            })
+        });
+        let on_recipients_input =
+            link.callback(|value| Msg::SetAgeParam(AgeParam::Recipients, value));
+        let on_identities_input =
+            link.callback(|value| Msg::SetAgeParam(AgeParam::Identities, value));
+        let on_passphrase_input =
+            link.callback(|value| Msg::SetAgeParam(AgeParam::Passphrase, value));
+        let on_armor_change = link.callback(Msg::SetAgeArmor);
+        let on_generate_click = link.callback(|_| Msg::GenerateAgeIdentity);
+        let on_run_click = link.callback(|_| Msg::RunAge);
+        let on_format_change = link.callback(|value: String| {
+            Msg::SetFormat(DigestFormat::from_id(&value).unwrap_or_default())
+        });
+        let busy = !self.pending.is_empty();
+        let encrypt = config.direction == CipherDirection::Encrypt;
+
+        let format_options = DigestFormat::ALL
+            .iter()
+            .map(|format| (format.id().to_string(), format.name().to_string()))
+            .collect::<Vec<_>>();
+        let (output, error) = match self.age_output() {
+            Ok(output) => (output, None),
+            Err((title, message)) => (String::default(), Some((title, message))),
+        };
+
+        html! {
+            <>
+                <div class="controls">
+                    <div class="form-radio-group">
+                        <label class="form-radio">
                             <input
                                 type="radio"
-                                name="hmac-encoding"
-                                value="hex"
-                                checked={self.hmac_config.encoding == HmacEncoding::Hex}
-                                onchange={&on_encoding_click}
+                                name="age-direction"
+                                value="encrypt"
+                                checked={encrypt}
+                                onchange={&on_direction_click}
                             />
-                            { "Hex" }
+                            <span>{ "Encrypt" }</span>
                         </label>
-                        <label class="custom-radio">
+                        <label class="form-radio">
                             <input
                                 type="radio"
-                                name="hmac-encoding"
-                                value="base64"
-                                checked={self.hmac_config.encoding == HmacEncoding::Base64}
-                                onchange={&on_encoding_click}
+                                name="age-direction"
+                                value="decrypt"
+                                checked={!encrypt}
+                                onchange={&on_direction_click}
                             />
-                            { "Base64" }
+                            <span>{ "Decrypt" }</span>
                         </label>
+                    </div>
+                </div>
+                <div class="controls">
+                    {"Keys:"}
+                    <label class="custom-radio">
+                        <input
+                            type="radio"
+                            name="age-key-type"
+                            value="x25519"
+                            checked={config.key_type == AgeKeyType::X25519}
+                            onchange={&on_key_type_click}
+                        />
+                        { "X25519 keys" }
+                    </label>
+                    <label class="custom-radio">
+                        <input
+                            type="radio"
+                            name="age-key-type"
+                            value="passphrase"
+                            checked={config.key_type == AgeKeyType::Passphrase}
+                            onchange={&on_key_type_click}
+                        />
+                        { "Passphrase" }
+                    </label>
+                </div>
+                <div class="controls">
+                    if config.key_type == AgeKeyType::Passphrase {
+                        <TextInput placeholder={"Passphrase".to_string()} value={config.passphrase.clone()} on_change={on_passphrase_input} />
+                    } else if encrypt {
+                        <TextInput placeholder={"Recipients (age1\u{2026}, separated by spaces or commas)".to_string()} value={config.recipients.clone()} on_change={on_recipients_input} />
+                    } else {
+                        <TextInput placeholder={"Identities (AGE-SECRET-KEY-1\u{2026}, separated by spaces or commas)".to_string()} value={config.identities.clone()} on_change={on_identities_input} />
+                    }
+                    if config.key_type == AgeKeyType::X25519 {
+                        <button class="button" onclick={on_generate_click}>{ "Generate identity" }</button>
                     }
                 </div>
-                <div>
+                if config.key_type == AgeKeyType::X25519 && !config.identities.is_empty() && encrypt {
+                    <div class="controls">
+                        {"Identity:"}
+                        <code>{ config.identities.clone() }</code>
+                    </div>
+                }
+                <div class="controls">
+                    if encrypt {
+                        <Checkbox label={"ASCII armor".to_string()} checked={config.armor} on_change={on_armor_change} />
+                    }
+                    <button class="button" onclick={on_run_click} disabled={busy}>
+                        if encrypt {
+                            { "Encrypt" }
+                        } else {
+                            { "Decrypt" }
+                        }
+                    </button>
+                    if busy {
+                        <span role="status">{ "Working\u{2026}" }</span>
+                    }
+                </div>
+                // Armored files are always text
+                if !(encrypt && config.armor) {
+                    <div class="controls">
+                        if encrypt {
+                            {"Output as:"}
+                        } else {
+                            {"Binary plaintext as:"}
+                        }
+                        <Select label={"Output format".to_string()} options={format_options} value={self.format.id().to_string()} on_change={on_format_change} />
+                    </div>
+                }
+                <div class="overlay-container">
                     <Textarea placeholder={"Output".to_string()} value={output} read_only={true} is_output={true} />
+                    if let Some((title, message)) = error {
+                        <div class="overlay">
+                            <div class="content">
+                                <div><strong>{ title }</strong></div>
+                                <div><em>{ message }</em></div>
+                            </div>
+                        </div>
+                    }
                 </div>
-            </div>
+            </>
+        }
+    }
+
+    /// BLAKE2 digest length, key, salt and personalization, or the parallel variant
+    fn view_blake2_options(&self, ctx: &Context<Self>, algorithm: HashAlgorithm) -> Html {
+        let link = ctx.link();
+        let config = &self.blake2_config;
+        let variant = config.variant(algorithm);
+
+        let on_parallel_change = link.callback(Msg::SetBlake2Parallel);
+        let on_length_input = link.callback(Msg::SetBlake2Length);
+        let on_key_input = link.callback(|value| Msg::SetBlake2Param(Blake2Param::Key, value));
+        let on_salt_input = link.callback(|value| Msg::SetBlake2Param(Blake2Param::Salt, value));
+        let on_personal_input =
+            link.callback(|value| Msg::SetBlake2Param(Blake2Param::Personal, value));
+        let on_key_encoding_change =
+            link.callback(|encoding| Msg::SetBlake2Encoding(Blake2Param::Key, encoding));
+        let on_salt_encoding_change =
+            link.callback(|encoding| Msg::SetBlake2Encoding(Blake2Param::Salt, encoding));
+        let on_personal_encoding_change =
+            link.callback(|encoding| Msg::SetBlake2Encoding(Blake2Param::Personal, encoding));
+
+        let parallel_label = match algorithm {
+            HashAlgorithm::Blake2s256 => "Parallel (BLAKE2sp)",
+            _ => "Parallel (BLAKE2bp)",
+        };
+
+        html! {
+            <>
+                <Checkbox label={parallel_label.to_string()} checked={config.parallel} on_change={on_parallel_change} />
+                {"Output bytes:"}
+                <TextInput placeholder={format!("Output length (default {})", variant.output_size())} value={config.length.clone()} on_change={on_length_input} />
+                <TextInput placeholder={format!("Key (optional, up to {} bytes)", variant.output_size())} value={config.key.clone()} on_change={on_key_input} />
+                <InputEncodingRadios name={"blake2-key-encoding".to_string()} value={config.key_encoding} on_change={on_key_encoding_change} />
+                if let Some(size) = variant.salt_size() {
+                    <TextInput placeholder={format!("Salt (optional, up to {} bytes)", size)} value={config.salt.clone()} on_change={on_salt_input} />
+                    <InputEncodingRadios name={"blake2-salt-encoding".to_string()} label={"Salt as:".to_string()} value={config.salt_encoding} on_change={on_salt_encoding_change} />
+                    <TextInput placeholder={format!("Personalization (optional, up to {} bytes)", size)} value={config.personal.clone()} on_change={on_personal_input} />
+                    <InputEncodingRadios name={"blake2-personal-encoding".to_string()} label={"Personalization as:".to_string()} value={config.personal_encoding} on_change={on_personal_encoding_change} />
+                }
+            </>
         }
     }
+
+    /// BLAKE3 mode, key/context and output length
+    fn view_blake3_options(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let config = &self.blake3_config;
+
+        let on_mode_click = link.batch_callback(|e: Event| {
+            let mode_btn = e.target_dyn_into::<HtmlInputElement>();
+            mode_btn.map(|btn| match btn.value().as_str() {
+                "keyed" => Msg::SetBlake3Mode(Blake3Mode::Keyed),
+                "derive-key" => Msg::SetBlake3Mode(Blake3Mode::DeriveKey),
+                _ => Msg::SetBlake3Mode(Blake3Mode::Hash),
+            })
+        });
+        let on_key_input = link.callback(Msg::SetBlake3Key);
+        let on_key_encoding_change = link.callback(Msg::SetBlake3KeyEncoding);
+        let on_context_input = link.callback(Msg::SetBlake3Context);
+        let on_length_input = link.callback(Msg::SetBlake3Length);
+
+        html! {
+            <>
+                <label class="custom-radio">
+                    <input
+                        type="radio"
+                        name="blake3-mode"
+                        value="hash"
+                        checked={config.mode == Blake3Mode::Hash}
+                        onchange={&on_mode_click}
+                    />
+                    { "Hash" }
+                </label>
+                <label class="custom-radio">
+                    <input
+                        type="radio"
+                        name="blake3-mode"
+                        value="keyed"
+                        checked={config.mode == Blake3Mode::Keyed}
+                        onchange={&on_mode_click}
+                    />
+                    { "Keyed" }
+                </label>
+                <label class="custom-radio">
+                    <input
+                        type="radio"
+                        name="blake3-mode"
+                        value="derive-key"
+                        checked={config.mode == Blake3Mode::DeriveKey}
+                        onchange={&on_mode_click}
+                    />
+                    { "Derive key" }
+                </label>
+                if config.mode == Blake3Mode::Keyed {
+                    <TextInput placeholder={"32-byte key".to_string()} value={config.key.clone()} on_change={on_key_input} />
+                    <InputEncodingRadios name={"blake3-key-encoding".to_string()} value={config.key_encoding} on_change={on_key_encoding_change} />
+                }
+                if config.mode == Blake3Mode::DeriveKey {
+                    <TextInput placeholder={"Context string".to_string()} value={config.context.clone()} on_change={on_context_input} />
+                }
+                {"Output bytes:"}
+                <TextInput placeholder={"Output length".to_string()} value={config.length.clone()} on_change={on_length_input} />
+            </>
+        }
+    }
+
+    /// SHAKE/cSHAKE/KMAC picker, with key, customization and output length
+    fn view_xof_options(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let config = &self.xof_config;
+
+        let on_algorithm_change = link.callback(|value: String| {
+            Msg::SetXofAlgorithm(XofAlgorithm::from_id(&value).unwrap_or_default())
+        });
+        let on_key_input = link.callback(Msg::SetXofKey);
+        let on_key_encoding_change = link.callback(Msg::SetXofKeyEncoding);
+        let on_customization_input = link.callback(Msg::SetXofCustomization);
+        let on_length_input = link.callback(Msg::SetXofLength);
+
+        let algorithm_options = XofAlgorithm::ALL
+            .iter()
+            .map(|algorithm| (algorithm.id().to_string(), algorithm.name().to_string()))
+            .collect::<Vec<_>>();
+
+        html! {
+            <>
+                <Select label={"Extendable-output function".to_string()} options={algorithm_options} value={config.algorithm.id().to_string()} on_change={on_algorithm_change} />
+                if config.algorithm.is_keyed() {
+                    <TextInput placeholder={"Enter key".to_string()} value={config.key.clone()} on_change={on_key_input} />
+                    <InputEncodingRadios name={"xof-key-encoding".to_string()} value={config.key_encoding} on_change={on_key_encoding_change} />
+                }
+                if config.algorithm.is_customizable() {
+                    <TextInput placeholder={"Customization string".to_string()} value={config.customization.clone()} on_change={on_customization_input} />
+                }
+                {"Output bytes:"}
+                <TextInput placeholder={"Output length".to_string()} value={config.length.clone()} on_change={on_length_input} />
+            </>
+        }
+    }
+
+    /// Fast hash picker, with a seed or key input when the algorithm takes one
+    fn view_fast_hash_options(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let config = &self.fast_hash_config;
+
+        let on_algorithm_change = link.callback(|value: String| {
+            Msg::SetFastHashAlgorithm(FastHashAlgorithm::from_id(&value).unwrap_or_default())
+        });
+        let on_seed_input = link.callback(Msg::SetFastHashSeed);
+
+        let algorithm_options = FastHashAlgorithm::ALL
+            .iter()
+            .map(|algorithm| (algorithm.id().to_string(), algorithm.name().to_string()))
+            .collect::<Vec<_>>();
+
+        let seed_placeholder = match config.algorithm.seed_kind() {
+            SeedKind::None => None,
+            SeedKind::U32 => Some("Seed (32-bit, decimal or 0x hex)".to_string()),
+            SeedKind::U64 => Some("Seed (64-bit, decimal or 0x hex)".to_string()),
+            SeedKind::Key(size) => Some(format!("Key ({} bytes, hex)", size)),
+        };
+
+        html! {
+            <>
+                <Select label={"Fast hash algorithm".to_string()} options={algorithm_options} value={config.algorithm.id().to_string()} on_change={on_algorithm_change} />
+                if let Some(placeholder) = seed_placeholder {
+                    <TextInput {placeholder} value={config.seed.clone()} on_change={on_seed_input} />
+                }
+                <p>{ "Output is the hash as an integer, most significant byte first. Results made of several 64-bit words put the last word first, so byte-listed reference vectors such as SipHash's and MurmurHash3 x64_128's read reversed." }</p>
+            </>
+        }
+    }
+
+    /// CRC preset picker, with editable parameters
+    fn view_crc_options(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let config = &self.crc_config;
+
+        let on_preset_change = link.callback(Msg::SetCrcPreset);
+        let on_width_input = link.callback(|value| Msg::SetCrcParam(CrcParam::Width, value));
+        let on_poly_input = link.callback(|value| Msg::SetCrcParam(CrcParam::Poly, value));
+        let on_init_input = link.callback(|value| Msg::SetCrcParam(CrcParam::Init, value));
+        let on_xorout_input = link.callback(|value| Msg::SetCrcParam(CrcParam::XorOut, value));
+        let on_refin_change = link.callback(Msg::SetCrcRefIn);
+        let on_refout_change = link.callback(Msg::SetCrcRefOut);
+
+        let preset_options = CRC_CATALOGUE
+            .iter()
+            .map(|crc| (crc.name.to_string(), crc.name.to_string()))
+            .chain([(CUSTOM_CRC.to_string(), "Custom".to_string())])
+            .collect::<Vec<_>>();
+
+        html! {
+            <>
+                <Select label={"CRC algorithm".to_string()} options={preset_options} value={config.preset.clone()} on_change={on_preset_change} />
+                {"Width:"}
+                <TextInput placeholder={"Width".to_string()} value={config.width.clone()} on_change={on_width_input} />
+                {"Poly:"}
+                <TextInput placeholder={"Poly (hex)".to_string()} value={config.poly.clone()} on_change={on_poly_input} />
+                {"Init:"}
+                <TextInput placeholder={"Init (hex)".to_string()} value={config.init.clone()} on_change={on_init_input} />
+                <Checkbox label={"RefIn".to_string()} checked={config.refin} on_change={on_refin_change} />
+                <Checkbox label={"RefOut".to_string()} checked={config.refout} on_change={on_refout_change} />
+                {"XorOut:"}
+                <TextInput placeholder={"XorOut (hex)".to_string()} value={config.xorout.clone()} on_change={on_xorout_input} />
+            </>
+        }
+    }
+
+    /// Candidate algorithms for the hash in `input`, tested against the known plaintext
+    fn view_identify(&self, input: &str) -> Html {
+        let candidates = identify_hash(input);
+
+        if candidates.is_empty() {
+            return html! {
+                <p>{ "Paste a hash into the input to identify it." }</p>
+            };
+        }
+
+        html! {
+            <table class="digest-table">
+                <thead>
+                    <tr>
+                        <th scope="col">{ "Algorithm" }</th>
+                        <th scope="col">{ "Format" }</th>
+                        <th scope="col">{ "Plaintext" }</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for candidates.iter().map(|candidate| {
+                        let result = if self.plaintext.is_empty() {
+                            None
+                        } else {
+                            candidate.test(&self.plaintext)
+                        };
+                        html! {
+                            <tr class={classes!((result == Some(true)).then_some("match"))}>
+                                <th scope="row">{ candidate.name.clone() }</th>
+                                <td>{ candidate.format }</td>
+                                <td>
+                                    { match result {
+                                        Some(true) => "\u{2714} Match",
+                                        Some(false) => "\u{2718} No match",
+                                        None => "\u{2014}",
+                                    } }
+                                </td>
+                            </tr>
+                        }
+                    }) }
+                </tbody>
+            </table>
+        }
+    }
+
+    /// Table of every digest algorithm, highlighting the one matching `expected`
+    fn view_all(&self, input: &str) -> Html {
+        let expected = parse_digest(&self.expected).ok();
+
+        html! {
+            <table class="digest-table">
+                <thead>
+                    <tr>
+                        <th scope="col">{ "Algorithm" }</th>
+                        <th scope="col">{ "Digest" }</th>
+                        <th scope="col"><span class="sr-only">{ "Copy" }</span></th>
+                    </tr>
+                </thead>
+                <tbody>
+                    { for HashAlgorithm::ALL.iter().map(|algorithm| {
+                        let bytes = algorithm.digest(input.as_bytes());
+                        let is_match = expected
+                            .as_ref()
+                            .is_some_and(|expected| digests_match(expected, &bytes));
+                        let digest = format_digest(&bytes, &self.format);
+                        html! {
+                            <tr class={classes!(is_match.then_some("match"))}>
+                                <th scope="row">{ algorithm.name() }</th>
+                                <td class="digest">{ digest.clone() }</td>
+                                <td>
+                                    <CopyButton value={digest} label={format!("Copy {} digest to clipboard", algorithm.name())} />
+                                </td>
+                            </tr>
+                        }
+                    }) }
+                </tbody>
+            </table>
+        }
+    }
+}
+
+/// Largest output accepted for extendable-output functions, in bytes
+const MAX_OUTPUT_LENGTH: usize = 8192;
+
+fn parse_output_length(length: &str) -> Result<usize, String> {
+    length
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|length| (1..=MAX_OUTPUT_LENGTH).contains(length))
+        .ok_or_else(|| format!("Output length must be between 1 and {}", MAX_OUTPUT_LENGTH))
+}
+
+/// Select value for user-defined CRC parameters
+const CUSTOM_CRC: &str = "custom";
+
+/// Pick the algorithm an expected digest was most likely produced by, based on
+/// its length, preferring one whose digest of `input` actually matches.
+/// Returns `None` if `current` is as good a guess as any.
+fn detect_algorithm(input: &str, expected: &str, current: HashAlgorithm) -> Option<HashAlgorithm> {
+    let expected = parse_digest(expected).ok()?;
+    let candidates = HashAlgorithm::with_output_size(expected.len());
+
+    if let Some(matching) = candidates
+        .iter()
+        .find(|algorithm| digests_match(&expected, &algorithm.digest(input.as_bytes())))
+    {
+        return Some(*matching);
+    }
+    if current.output_size() == expected.len() {
+        return None;
+    }
+    candidates.first().copied()
 }
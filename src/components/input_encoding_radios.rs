@@ -0,0 +1,70 @@
+use crate::engine::InputEncoding;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    /// Radio group name, must be unique on the page
+    pub name: String,
+    pub value: InputEncoding,
+    pub on_change: Callback<InputEncoding>,
+    #[prop_or("Key as:".to_string())]
+    pub label: String,
+}
+
+/// Radio buttons to choose how binary input (keys, salts, etc.) is entered
+#[function_component(InputEncodingRadios)]
+pub fn input_encoding_radios(props: &Props) -> Html {
+    let Props {
+        name,
+        value,
+        on_change,
+        label,
+    } = props.clone();
+
+    let onchange = on_change.reform(|e: Event| {
+        let encoding_btn = e.target_unchecked_into::<HtmlInputElement>();
+        match encoding_btn.value().as_str() {
+            "utf8" => InputEncoding::Utf8,
+            "hex" => InputEncoding::Hex,
+            "base64" => InputEncoding::Base64,
+            _ => InputEncoding::default(),
+        }
+    });
+
+    html! {
+        <>
+            { label }
+            <label class="custom-radio">
+                <input
+                    type="radio"
+                    name={name.clone()}
+                    value="utf8"
+                    checked={value == InputEncoding::Utf8}
+                    onchange={&onchange}
+                />
+                { "UTF-8" }
+            </label>
+            <label class="custom-radio">
+                <input
+                    type="radio"
+                    name={name.clone()}
+                    value="hex"
+                    checked={value == InputEncoding::Hex}
+                    onchange={&onchange}
+                />
+                { "Hex" }
+            </label>
+            <label class="custom-radio">
+                <input
+                    type="radio"
+                    {name}
+                    value="base64"
+                    checked={value == InputEncoding::Base64}
+                    onchange={&onchange}
+                />
+                { "Base64" }
+            </label>
+        </>
+    }
+}
@@ -1,11 +1,29 @@
+mod checkbox;
+mod copy_button;
+mod crypto_tools;
+mod encryption;
 mod hashing;
 mod header;
+mod input_encoding_radios;
+mod passwords;
+mod select;
+mod signatures;
 mod text_encoding;
 mod text_input;
 mod textarea;
+mod verification;
 
+pub use checkbox::Checkbox;
+pub use copy_button::CopyButton;
+pub use crypto_tools::CryptoTools;
+pub use encryption::Encryption;
 pub use hashing::Hashing;
 pub use header::Header;
+pub use input_encoding_radios::InputEncodingRadios;
+pub use passwords::Passwords;
+pub use select::Select;
+pub use signatures::Signatures;
 pub use text_encoding::TextEncoding;
 pub use text_input::TextInput;
 pub use textarea::Textarea;
+pub use verification::Verification;
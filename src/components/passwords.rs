@@ -0,0 +1,714 @@
+use crate::components::{Select, TextInput, Textarea, Verification};
+use crate::engine::{
+    parse_password_hash, Argon2Variant, LdapScheme, PasswordAlgorithm, Pbkdf2Hash,
+};
+use crate::worker::{BackgroundWorker, WorkerRequest, WorkerResponse, WORKER_PATH};
+use gloo_worker::{Spawnable, WorkerBridge};
+use std::collections::VecDeque;
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+pub enum Msg {
+    SetAction(Action),
+    SetParam(PasswordParam, String),
+    SetArgon2Variant(Argon2Variant),
+    SetPbkdf2Hash(Pbkdf2Hash),
+    SetHash(String),
+    Generate,
+    Verify,
+    WorkerResponse(WorkerResponse),
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum Action {
+    #[default]
+    Bcrypt,
+    Scrypt,
+    Argon2,
+    Pbkdf2,
+    Md5Crypt,
+    Apr1,
+    Sha256Crypt,
+    Sha512Crypt,
+    DesCrypt,
+    LdapSha,
+    Nt,
+    Lm,
+    MysqlNative,
+    MysqlOld,
+    PostgresMd5,
+    PostgresScram,
+    Django,
+    Ssha,
+    Ssha512,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum PasswordParam {
+    BcryptCost,
+    ScryptLogN,
+    ScryptR,
+    ScryptP,
+    Argon2Memory,
+    Argon2Iterations,
+    Argon2Parallelism,
+    Pbkdf2Iterations,
+    ShaCryptRounds,
+    PostgresUser,
+    ScramIterations,
+    DjangoIterations,
+}
+
+/// Cost parameters as entered, defaulting to the OWASP recommendations
+#[derive(Debug, PartialEq)]
+struct CostConfig {
+    bcrypt_cost: String,
+    scrypt_log_n: String,
+    scrypt_r: String,
+    scrypt_p: String,
+    argon2_variant: Argon2Variant,
+    argon2_memory: String,
+    argon2_iterations: String,
+    argon2_parallelism: String,
+    pbkdf2_hash: Pbkdf2Hash,
+    pbkdf2_iterations: String,
+    /// Empty for the default, which is left out of the hash
+    sha_crypt_rounds: String,
+    postgres_user: String,
+    scram_iterations: String,
+    django_iterations: String,
+}
+
+impl Default for CostConfig {
+    fn default() -> Self {
+        CostConfig {
+            bcrypt_cost: "10".to_string(),
+            scrypt_log_n: "17".to_string(),
+            scrypt_r: "8".to_string(),
+            scrypt_p: "1".to_string(),
+            argon2_variant: Argon2Variant::default(),
+            argon2_memory: "19456".to_string(),
+            argon2_iterations: "2".to_string(),
+            argon2_parallelism: "1".to_string(),
+            pbkdf2_hash: Pbkdf2Hash::default(),
+            pbkdf2_iterations: "600000".to_string(),
+            sha_crypt_rounds: String::default(),
+            postgres_user: "postgres".to_string(),
+            scram_iterations: "4096".to_string(),
+            django_iterations: "1000000".to_string(),
+        }
+    }
+}
+
+impl CostConfig {
+    fn algorithm(&self, action: Action) -> Result<PasswordAlgorithm, String> {
+        let algorithm = match action {
+            Action::Bcrypt => PasswordAlgorithm::Bcrypt {
+                cost: parse_param("Cost", &self.bcrypt_cost)?,
+            },
+            Action::Scrypt => PasswordAlgorithm::Scrypt {
+                log_n: parse_param("log\u{2082}(N)", &self.scrypt_log_n)?,
+                r: parse_param("r", &self.scrypt_r)?,
+                p: parse_param("p", &self.scrypt_p)?,
+            },
+            Action::Argon2 => PasswordAlgorithm::Argon2 {
+                variant: self.argon2_variant,
+                memory: parse_param("Memory", &self.argon2_memory)?,
+                iterations: parse_param("Iterations", &self.argon2_iterations)?,
+                parallelism: parse_param("Parallelism", &self.argon2_parallelism)?,
+            },
+            Action::Pbkdf2 => PasswordAlgorithm::Pbkdf2 {
+                hash: self.pbkdf2_hash,
+                iterations: parse_param("Iterations", &self.pbkdf2_iterations)?,
+            },
+            Action::Md5Crypt => PasswordAlgorithm::Md5Crypt,
+            Action::Apr1 => PasswordAlgorithm::Apr1,
+            Action::Sha256Crypt => PasswordAlgorithm::Sha256Crypt {
+                rounds: self.sha_crypt_rounds()?,
+            },
+            Action::Sha512Crypt => PasswordAlgorithm::Sha512Crypt {
+                rounds: self.sha_crypt_rounds()?,
+            },
+            Action::DesCrypt => PasswordAlgorithm::DesCrypt,
+            Action::LdapSha => PasswordAlgorithm::LdapSha,
+            Action::Nt => PasswordAlgorithm::Nt,
+            Action::Lm => PasswordAlgorithm::Lm,
+            Action::MysqlNative => PasswordAlgorithm::MysqlNative,
+            Action::MysqlOld => PasswordAlgorithm::MysqlOld,
+            Action::PostgresMd5 => match self.postgres_user.trim() {
+                "" => return Err("Role name is required".to_string()),
+                user => PasswordAlgorithm::PostgresMd5 {
+                    user: user.to_string(),
+                },
+            },
+            Action::PostgresScram => PasswordAlgorithm::PostgresScram {
+                iterations: parse_param("Iterations", &self.scram_iterations)?,
+            },
+            Action::Django => PasswordAlgorithm::Django {
+                iterations: parse_param("Iterations", &self.django_iterations)?,
+            },
+            Action::Ssha => PasswordAlgorithm::LdapSalted {
+                scheme: LdapScheme::Ssha,
+            },
+            Action::Ssha512 => PasswordAlgorithm::LdapSalted {
+                scheme: LdapScheme::Ssha512,
+            },
+        };
+        Ok(algorithm)
+    }
+
+    fn sha_crypt_rounds(&self) -> Result<Option<u32>, String> {
+        match self.sha_crypt_rounds.trim() {
+            "" => Ok(None),
+            rounds => parse_param("Rounds", rounds).map(Some),
+        }
+    }
+}
+
+fn parse_param<T: std::str::FromStr>(label: &str, value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("{} must be a positive number", label))
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    /// The password
+    pub input: String,
+}
+
+pub struct Passwords {
+    action: Action,
+    cost_config: CostConfig,
+    output: String,
+    error: Option<String>,
+    hash: String,
+    verification: Option<Result<bool, String>>,
+    /// Bumped whenever the generated hash goes stale
+    hash_generation: u32,
+    /// Bumped whenever the verification result goes stale
+    verify_generation: u32,
+    /// Generations of requests still running in the worker, oldest first
+    pending: VecDeque<u32>,
+    worker: WorkerBridge<BackgroundWorker>,
+}
+
+impl Component for Passwords {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let on_response = ctx.link().callback(Msg::WorkerResponse);
+        let worker = BackgroundWorker::spawner()
+            .callback(move |response| on_response.emit(response))
+            .spawn(WORKER_PATH);
+
+        Self {
+            action: Action::default(),
+            cost_config: CostConfig::default(),
+            output: String::default(),
+            error: None,
+            hash: String::default(),
+            verification: None,
+            hash_generation: 0,
+            verify_generation: 0,
+            pending: VecDeque::new(),
+            worker,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SetAction(action) => {
+                self.action = action;
+                self.clear_hash_result();
+                true
+            }
+            Msg::SetParam(param, value) => {
+                let config = &mut self.cost_config;
+                match param {
+                    PasswordParam::BcryptCost => config.bcrypt_cost = value,
+                    PasswordParam::ScryptLogN => config.scrypt_log_n = value,
+                    PasswordParam::ScryptR => config.scrypt_r = value,
+                    PasswordParam::ScryptP => config.scrypt_p = value,
+                    PasswordParam::Argon2Memory => config.argon2_memory = value,
+                    PasswordParam::Argon2Iterations => config.argon2_iterations = value,
+                    PasswordParam::Argon2Parallelism => config.argon2_parallelism = value,
+                    PasswordParam::Pbkdf2Iterations => config.pbkdf2_iterations = value,
+                    PasswordParam::ShaCryptRounds => config.sha_crypt_rounds = value,
+                    PasswordParam::PostgresUser => config.postgres_user = value,
+                    PasswordParam::ScramIterations => config.scram_iterations = value,
+                    PasswordParam::DjangoIterations => config.django_iterations = value,
+                }
+                self.clear_hash_result();
+                true
+            }
+            Msg::SetArgon2Variant(variant) => {
+                self.cost_config.argon2_variant = variant;
+                self.clear_hash_result();
+                true
+            }
+            Msg::SetPbkdf2Hash(hash) => {
+                self.cost_config.pbkdf2_hash = hash;
+                self.clear_hash_result();
+                true
+            }
+            Msg::SetHash(hash) => {
+                self.hash = hash;
+                self.clear_verification();
+                true
+            }
+            Msg::Generate => {
+                match self.cost_config.algorithm(self.action) {
+                    Ok(algorithm) => {
+                        self.clear_hash_result();
+                        self.pending.push_back(self.hash_generation);
+                        self.worker.send(WorkerRequest::Hash {
+                            password: ctx.props().input.clone(),
+                            algorithm,
+                        });
+                    }
+                    Err(e) => self.error = Some(e),
+                }
+                true
+            }
+            Msg::Verify => {
+                self.clear_verification();
+                self.pending.push_back(self.verify_generation);
+                self.worker.send(WorkerRequest::Verify {
+                    password: ctx.props().input.clone(),
+                    hash: self.hash.clone(),
+                });
+                true
+            }
+            Msg::WorkerResponse(response) => {
+                // Drop results for a password, hash or options that have since changed
+                let generation = self.pending.pop_front();
+                match response {
+                    WorkerResponse::Hashed(result) if generation == Some(self.hash_generation) => {
+                        match result {
+                            Ok(hash) => self.output = hash,
+                            Err(e) => self.error = Some(e),
+                        }
+                    }
+                    WorkerResponse::Verified(result)
+                        if generation == Some(self.verify_generation) =>
+                    {
+                        self.verification = Some(result)
+                    }
+                    _ => {}
+                }
+                true
+            }
+        }
+    }
+
+    fn changed(&mut self, _ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        // Results were for the previous password
+        self.clear_hash_result();
+        self.clear_verification();
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+
+        let on_action_click = link.batch_callback(|e: Event| {
+            let action_el = e.target_dyn_into::<HtmlInputElement>();
+            action_el.map(|btn| match btn.value().as_str() {
+                "scrypt" => Msg::SetAction(Action::Scrypt),
+                "argon2" => Msg::SetAction(Action::Argon2),
+                "pbkdf2" => Msg::SetAction(Action::Pbkdf2),
+                "md5-crypt" => Msg::SetAction(Action::Md5Crypt),
+                "apr1" => Msg::SetAction(Action::Apr1),
+                "sha256-crypt" => Msg::SetAction(Action::Sha256Crypt),
+                "sha512-crypt" => Msg::SetAction(Action::Sha512Crypt),
+                "des-crypt" => Msg::SetAction(Action::DesCrypt),
+                "ldap-sha" => Msg::SetAction(Action::LdapSha),
+                "ntlm" => Msg::SetAction(Action::Nt),
+                "lm" => Msg::SetAction(Action::Lm),
+                "mysql" => Msg::SetAction(Action::MysqlNative),
+                "mysql-old" => Msg::SetAction(Action::MysqlOld),
+                "postgres-md5" => Msg::SetAction(Action::PostgresMd5),
+                "postgres-scram" => Msg::SetAction(Action::PostgresScram),
+                "django" => Msg::SetAction(Action::Django),
+                "ssha" => Msg::SetAction(Action::Ssha),
+                "ssha512" => Msg::SetAction(Action::Ssha512),
+                _ => Msg::SetAction(Action::Bcrypt),
+            })
+        });
+        let on_generate_click = link.callback(|_| Msg::Generate);
+        let on_verify_click = link.callback(|_| Msg::Verify);
+        let on_hash_input = link.callback(Msg::SetHash);
+        let busy = !self.pending.is_empty();
+
+        html! {
+            <div class="action-component">
+                <div class="control">
+                    <div class="custom-radio-group">
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="bcrypt"
+                                checked={self.action == Action::Bcrypt}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "bcrypt" }</span>
+                        </label>
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="scrypt"
+                                checked={self.action == Action::Scrypt}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "scrypt" }</span>
+                        </label>
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="argon2"
+                                checked={self.action == Action::Argon2}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "Argon2" }</span>
+                        </label>
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="pbkdf2"
+                                checked={self.action == Action::Pbkdf2}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "PBKDF2" }</span>
+                        </label>
+                    </div>
+                    <div class="custom-radio-group">
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="sha512-crypt"
+                                checked={self.action == Action::Sha512Crypt}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "SHA-512-crypt ($6$)" }</span>
+                        </label>
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="sha256-crypt"
+                                checked={self.action == Action::Sha256Crypt}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "SHA-256-crypt ($5$)" }</span>
+                        </label>
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="md5-crypt"
+                                checked={self.action == Action::Md5Crypt}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "MD5-crypt ($1$)" }</span>
+                        </label>
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="apr1"
+                                checked={self.action == Action::Apr1}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "Apache MD5 ($apr1$)" }</span>
+                        </label>
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="ldap-sha"
+                                checked={self.action == Action::LdapSha}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "{SHA}" }</span>
+                        </label>
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="des-crypt"
+                                checked={self.action == Action::DesCrypt}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "DES crypt" }</span>
+                        </label>
+                    </div>
+                    <div class="custom-radio-group">
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="ntlm"
+                                checked={self.action == Action::Nt}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "NTLM" }</span>
+                        </label>
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="lm"
+                                checked={self.action == Action::Lm}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "LM" }</span>
+                        </label>
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="mysql"
+                                checked={self.action == Action::MysqlNative}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "MySQL 4.1+" }</span>
+                        </label>
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="mysql-old"
+                                checked={self.action == Action::MysqlOld}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "MySQL (old)" }</span>
+                        </label>
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="postgres-md5"
+                                checked={self.action == Action::PostgresMd5}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "PostgreSQL md5" }</span>
+                        </label>
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="postgres-scram"
+                                checked={self.action == Action::PostgresScram}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "PostgreSQL SCRAM" }</span>
+                        </label>
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="django"
+                                checked={self.action == Action::Django}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "Django" }</span>
+                        </label>
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="ssha"
+                                checked={self.action == Action::Ssha}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "{SSHA}" }</span>
+                        </label>
+                        <label class="custom-radio">
+                            <input
+                                type="radio"
+                                name="password-action"
+                                value="ssha512"
+                                checked={self.action == Action::Ssha512}
+                                onchange={&on_action_click}
+                            />
+                            <span>{ "{SSHA512}" }</span>
+                        </label>
+                    </div>
+                </div>
+                <div class="controls">
+                    { self.view_cost_options(ctx) }
+                    <button class="button" onclick={on_generate_click} disabled={busy}>
+                        { "Hash password" }
+                    </button>
+                </div>
+                <div class="overlay-container">
+                    <Textarea placeholder={"Password hash".to_string()} value={self.output.clone()} read_only={true} is_output={true} />
+                    if let Some(error) = &self.error {
+                        <div class="overlay">
+                            <div class="content">
+                                <div><strong>{ "Hashing Failed" }</strong></div>
+                                <div><em>{ error }</em></div>
+                            </div>
+                        </div>
+                    }
+                </div>
+                <div class="controls">
+                    <TextInput placeholder={"Hash or user:hash line to verify".to_string()} value={self.hash.clone()} on_change={on_hash_input} />
+                    <button class="button" onclick={on_verify_click} disabled={busy || self.hash.trim().is_empty()}>
+                        { "Verify password" }
+                    </button>
+                    <Verification result={self.verification.clone()} />
+                    if busy {
+                        <span role="status">{ "Working\u{2026}" }</span>
+                    }
+                </div>
+                { self.view_hash_info() }
+            </div>
+        }
+    }
+}
+
+impl Passwords {
+    fn clear_hash_result(&mut self) {
+        self.output = String::default();
+        self.error = None;
+        self.hash_generation = self.hash_generation.wrapping_add(1);
+    }
+
+    fn clear_verification(&mut self) {
+        self.verification = None;
+        self.verify_generation = self.verify_generation.wrapping_add(1);
+    }
+
+    /// Cost inputs for the selected scheme
+    fn view_cost_options(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let config = &self.cost_config;
+        let param_callback =
+            |param: PasswordParam| link.callback(move |value| Msg::SetParam(param, value));
+
+        match self.action {
+            Action::Bcrypt => html! {
+                <>
+                    {"Cost:"}
+                    <TextInput placeholder={"Cost (4-31)".to_string()} value={config.bcrypt_cost.clone()} on_change={param_callback(PasswordParam::BcryptCost)} />
+                </>
+            },
+            Action::Scrypt => html! {
+                <>
+                    {"log\u{2082}(N):"}
+                    <TextInput placeholder={"log\u{2082}(N)".to_string()} value={config.scrypt_log_n.clone()} on_change={param_callback(PasswordParam::ScryptLogN)} />
+                    {"r:"}
+                    <TextInput placeholder={"Block size".to_string()} value={config.scrypt_r.clone()} on_change={param_callback(PasswordParam::ScryptR)} />
+                    {"p:"}
+                    <TextInput placeholder={"Parallelism".to_string()} value={config.scrypt_p.clone()} on_change={param_callback(PasswordParam::ScryptP)} />
+                </>
+            },
+            Action::Argon2 => {
+                let on_variant_change = link.callback(|value: String| {
+                    Msg::SetArgon2Variant(Argon2Variant::from_id(&value).unwrap_or_default())
+                });
+                let variant_options = Argon2Variant::ALL
+                    .iter()
+                    .map(|variant| (variant.id().to_string(), variant.name().to_string()))
+                    .collect::<Vec<_>>();
+
+                html! {
+                    <>
+                        <Select label={"Argon2 variant".to_string()} options={variant_options} value={config.argon2_variant.id().to_string()} on_change={on_variant_change} />
+                        {"Memory (KiB):"}
+                        <TextInput placeholder={"Memory (KiB)".to_string()} value={config.argon2_memory.clone()} on_change={param_callback(PasswordParam::Argon2Memory)} />
+                        {"Iterations:"}
+                        <TextInput placeholder={"Iterations".to_string()} value={config.argon2_iterations.clone()} on_change={param_callback(PasswordParam::Argon2Iterations)} />
+                        {"Parallelism:"}
+                        <TextInput placeholder={"Parallelism".to_string()} value={config.argon2_parallelism.clone()} on_change={param_callback(PasswordParam::Argon2Parallelism)} />
+                    </>
+                }
+            }
+            Action::Pbkdf2 => {
+                let on_hash_change = link.callback(|value: String| {
+                    Msg::SetPbkdf2Hash(Pbkdf2Hash::from_id(&value).unwrap_or_default())
+                });
+                let hash_options = Pbkdf2Hash::ALL
+                    .iter()
+                    .map(|hash| (hash.id().to_string(), hash.name().to_string()))
+                    .collect::<Vec<_>>();
+
+                html! {
+                    <>
+                        <Select label={"PBKDF2 hash".to_string()} options={hash_options} value={config.pbkdf2_hash.id().to_string()} on_change={on_hash_change} />
+                        {"Iterations:"}
+                        <TextInput placeholder={"Iterations".to_string()} value={config.pbkdf2_iterations.clone()} on_change={param_callback(PasswordParam::Pbkdf2Iterations)} />
+                    </>
+                }
+            }
+            Action::Sha256Crypt | Action::Sha512Crypt => html! {
+                <>
+                    {"Rounds:"}
+                    <TextInput placeholder={"Default (5000)".to_string()} value={config.sha_crypt_rounds.clone()} on_change={param_callback(PasswordParam::ShaCryptRounds)} />
+                </>
+            },
+            Action::PostgresMd5 => html! {
+                <>
+                    {"Role:"}
+                    <TextInput placeholder={"Role name".to_string()} value={config.postgres_user.clone()} on_change={param_callback(PasswordParam::PostgresUser)} />
+                </>
+            },
+            Action::PostgresScram => html! {
+                <>
+                    {"Iterations:"}
+                    <TextInput placeholder={"Iterations".to_string()} value={config.scram_iterations.clone()} on_change={param_callback(PasswordParam::ScramIterations)} />
+                </>
+            },
+            Action::Django => html! {
+                <>
+                    {"Iterations:"}
+                    <TextInput placeholder={"Iterations".to_string()} value={config.django_iterations.clone()} on_change={param_callback(PasswordParam::DjangoIterations)} />
+                </>
+            },
+            Action::Md5Crypt
+            | Action::Apr1
+            | Action::DesCrypt
+            | Action::LdapSha
+            | Action::Nt
+            | Action::Lm
+            | Action::MysqlNative
+            | Action::MysqlOld
+            | Action::Ssha
+            | Action::Ssha512 => html! {},
+        }
+    }
+
+    /// Parameters parsed from the hash being verified
+    fn view_hash_info(&self) -> Html {
+        if self.hash.trim().is_empty() {
+            return html! {};
+        }
+        let info = match parse_password_hash(&self.hash) {
+            Ok(info) => info,
+            Err(e) => return html! { <p>{ format!("Unrecognized hash: {}", e) }</p> },
+        };
+
+        html! {
+            <table class="digest-table">
+                <caption>{ info.scheme }</caption>
+                <tbody>
+                    { for info.fields.into_iter().map(|(name, value)| html! {
+                        <tr>
+                            <th scope="row">{ name }</th>
+                            <td class="digest">{ value }</td>
+                        </tr>
+                    }) }
+                </tbody>
+            </table>
+        }
+    }
+}
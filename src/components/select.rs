@@ -0,0 +1,50 @@
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::{Event, HtmlSelectElement};
+use yew::prelude::*;
+
+fn create_default_on_change_callback() -> Callback<String> {
+    Callback::from(|_| ())
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub value: String,
+    /// Pairs of (value, label)
+    pub options: Vec<(String, String)>,
+    #[prop_or_else(create_default_on_change_callback)]
+    pub on_change: Callback<String>,
+    #[prop_or_default]
+    pub label: String,
+}
+
+fn get_value_from_event(e: Event) -> String {
+    let event_target = e.target().unwrap_throw();
+    let target: HtmlSelectElement = event_target.dyn_into().unwrap_throw();
+    target.value()
+}
+
+/// Controlled Select Component
+#[function_component(Select)]
+pub fn select(props: &Props) -> Html {
+    let Props {
+        value,
+        options,
+        on_change,
+        label,
+    } = props.clone();
+
+    let onchange = Callback::from(move |event: Event| {
+        on_change.emit(get_value_from_event(event));
+    });
+
+    html! {
+        <select class="select" aria-label={label} {onchange}>
+            { for options.into_iter().map(|(option_value, option_label)| {
+                let selected = option_value == value;
+                html! {
+                    <option {selected} value={option_value}>{ option_label }</option>
+                }
+            }) }
+        </select>
+    }
+}
@@ -0,0 +1,259 @@
+use crate::components::{Select, TextInput, Textarea, Verification};
+use crate::engine::{
+    format_digest, parse_digest, DigestFormat, EcdsaEncoding, HashAlgorithm, SignatureAlgorithm,
+    SignatureParams, RSA_SIGNATURE_HASHES,
+};
+use web_sys::HtmlInputElement;
+use yew::prelude::*;
+
+#[allow(clippy::enum_variant_names)]
+pub enum Msg {
+    SetMode(Mode),
+    SetAlgorithm(SignatureAlgorithm),
+    SetHash(HashAlgorithm),
+    SetEcdsaEncoding(EcdsaEncoding),
+    SetKey(String),
+    SetSignature(String),
+    SetFormat(DigestFormat),
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum Mode {
+    #[default]
+    Sign,
+    Verify,
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    /// The message being signed or verified
+    pub input: String,
+}
+
+pub struct Signatures {
+    mode: Mode,
+    algorithm: SignatureAlgorithm,
+    hash: HashAlgorithm,
+    ecdsa_encoding: EcdsaEncoding,
+    key: String,
+    signature: String,
+    format: DigestFormat,
+    /// Signature of the current input, `None` until a key is entered
+    signed: Option<Result<Vec<u8>, String>>,
+}
+
+impl Component for Signatures {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            mode: Mode::default(),
+            algorithm: SignatureAlgorithm::default(),
+            hash: HashAlgorithm::Sha256,
+            ecdsa_encoding: EcdsaEncoding::default(),
+            key: String::default(),
+            signature: String::default(),
+            format: DigestFormat::Base64,
+            signed: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SetMode(mode) => self.mode = mode,
+            Msg::SetAlgorithm(algorithm) => self.algorithm = algorithm,
+            Msg::SetHash(hash) => self.hash = hash,
+            Msg::SetEcdsaEncoding(encoding) => self.ecdsa_encoding = encoding,
+            Msg::SetKey(key) => self.key = key,
+            Msg::SetSignature(signature) => {
+                self.signature = signature;
+                return true;
+            }
+            Msg::SetFormat(format) => {
+                self.format = format;
+                return true;
+            }
+        }
+        self.sign(&ctx.props().input);
+        true
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        // The signature was for the previous input
+        self.sign(&ctx.props().input);
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let link = ctx.link();
+        let algorithm = self.algorithm;
+
+        let on_mode_click = link.batch_callback(|e: Event| {
+            let mode_el = e.target_dyn_into::<HtmlInputElement>();
+            mode_el.map(|btn| match btn.value().as_str() {
+                "verify" => Msg::SetMode(Mode::Verify),
+                _ => Msg::SetMode(Mode::Sign),
+            })
+        });
+        let on_algorithm_click = link.batch_callback(|e: Event| {
+            let algorithm_el = e.target_dyn_into::<HtmlInputElement>();
+            algorithm_el.map(|btn| {
+                Msg::SetAlgorithm(SignatureAlgorithm::from_id(&btn.value()).unwrap_or_default())
+            })
+        });
+        let on_hash_change = link.callback(|value: String| {
+            Msg::SetHash(HashAlgorithm::from_id(&value).unwrap_or(HashAlgorithm::Sha256))
+        });
+        let on_encoding_change = link.callback(|value: String| {
+            Msg::SetEcdsaEncoding(EcdsaEncoding::from_id(&value).unwrap_or_default())
+        });
+        let on_format_change = link.callback(|value: String| {
+            Msg::SetFormat(DigestFormat::from_id(&value).unwrap_or_default())
+        });
+        let on_key_input = link.callback(Msg::SetKey);
+        let on_signature_input = link.callback(Msg::SetSignature);
+
+        let hash_options = RSA_SIGNATURE_HASHES
+            .iter()
+            .map(|hash| (hash.id().to_string(), hash.name().to_string()))
+            .collect::<Vec<_>>();
+        let encoding_options = EcdsaEncoding::ALL
+            .iter()
+            .map(|encoding| (encoding.id().to_string(), encoding.name().to_string()))
+            .collect::<Vec<_>>();
+        let format_options = DigestFormat::ALL
+            .iter()
+            .map(|format| (format.id().to_string(), format.name().to_string()))
+            .collect::<Vec<_>>();
+
+        let key_placeholder = match self.mode {
+            Mode::Sign => "Private key (PEM, DER, JWK or raw)",
+            Mode::Verify => "Public or private key (PEM, DER, JWK or raw)",
+        };
+
+        html! {
+            <div class="action-component">
+                <div class="controls">
+                    <div class="form-radio-group">
+                        <label class="form-radio">
+                            <input
+                                type="radio"
+                                name="signature-mode"
+                                value="sign"
+                                checked={self.mode == Mode::Sign}
+                                onchange={&on_mode_click}
+                            />
+                            <span>{ "Sign" }</span>
+                        </label>
+                        <label class="form-radio">
+                            <input
+                                type="radio"
+                                name="signature-mode"
+                                value="verify"
+                                checked={self.mode == Mode::Verify}
+                                onchange={&on_mode_click}
+                            />
+                            <span>{ "Verify" }</span>
+                        </label>
+                    </div>
+                </div>
+                <div class="controls">
+                    <div class="custom-radio-group">
+                        { for SignatureAlgorithm::ALL.iter().map(|algorithm| html! {
+                            <label class="custom-radio">
+                                <input
+                                    type="radio"
+                                    name="signature-algorithm"
+                                    value={algorithm.id()}
+                                    checked={self.algorithm == *algorithm}
+                                    onchange={&on_algorithm_click}
+                                />
+                                <span>{ algorithm.name() }</span>
+                            </label>
+                        }) }
+                    </div>
+                </div>
+                <div class="controls">
+                    <TextInput placeholder={key_placeholder.to_string()} value={self.key.clone()} on_change={on_key_input} />
+                    if algorithm.is_rsa() {
+                        <Select label={"Digest".to_string()} options={hash_options} value={self.hash.id().to_string()} on_change={on_hash_change} />
+                    }
+                    if algorithm.is_ecdsa() && self.mode == Mode::Sign {
+                        <Select label={"Signature encoding".to_string()} options={encoding_options} value={self.ecdsa_encoding.id().to_string()} on_change={on_encoding_change} />
+                    }
+                </div>
+                if self.mode == Mode::Sign {
+                    <div class="controls">
+                        {"Output as:"}
+                        <Select label={"Output format".to_string()} options={format_options} value={self.format.id().to_string()} on_change={on_format_change} />
+                    </div>
+                    { self.view_signature() }
+                } else {
+                    <div class="controls">
+                        <TextInput placeholder={"Signature (hex or Base64)".to_string()} value={self.signature.clone()} on_change={on_signature_input} />
+                        <Verification result={self.verification(&ctx.props().input)} />
+                    </div>
+                }
+            </div>
+        }
+    }
+}
+
+impl Signatures {
+    fn params(&self) -> SignatureParams {
+        SignatureParams {
+            algorithm: self.algorithm,
+            hash: self.hash,
+            ecdsa_encoding: self.ecdsa_encoding,
+        }
+    }
+
+    /// Signs the input with the current key and parameters, only in sign mode.
+    /// Randomised schemes such as RSA-PSS would otherwise change on every render.
+    fn sign(&mut self, input: &str) {
+        self.signed = if self.mode != Mode::Sign || self.key.trim().is_empty() {
+            None
+        } else {
+            Some(self.params().sign(&self.key, input.as_bytes()))
+        };
+    }
+
+    /// Stored signature in the chosen format
+    fn view_signature(&self) -> Html {
+        let (output, error) = match &self.signed {
+            Some(Ok(signature)) => (format_digest(signature, &self.format), None),
+            Some(Err(e)) => (String::default(), Some(e.clone())),
+            None => (String::default(), None),
+        };
+
+        html! {
+            <div class="overlay-container">
+                <Textarea placeholder={"Signature".to_string()} value={output} read_only={true} is_output={true} />
+                if let Some(error) = error {
+                    <div class="overlay">
+                        <div class="content">
+                            <div><strong>{ "Signing Failed" }</strong></div>
+                            <div><em>{ error }</em></div>
+                        </div>
+                    </div>
+                }
+            </div>
+        }
+    }
+
+    /// `None` until both a key and a signature are entered
+    fn verification(&self, input: &str) -> Option<Result<bool, String>> {
+        if self.key.trim().is_empty() || self.signature.trim().is_empty() {
+            return None;
+        }
+        let signature = match parse_digest(&self.signature) {
+            Ok(signature) => signature,
+            Err(e) => return Some(Err(e)),
+        };
+        Some(
+            self.params()
+                .verify(&self.key, input.as_bytes(), &signature),
+        )
+    }
+}
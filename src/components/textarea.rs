@@ -1,7 +1,7 @@
-use wasm_bindgen::{prelude::wasm_bindgen, JsCast, JsValue, UnwrapThrowExt};
-use web_sys::{Event, HtmlTextAreaElement, InputEvent, MouseEvent};
+use crate::components::CopyButton;
+use wasm_bindgen::{JsCast, UnwrapThrowExt};
+use web_sys::{Event, HtmlTextAreaElement, InputEvent};
 use yew::prelude::*;
-use yew_icons::{Icon, IconId};
 
 fn create_default_on_change_callback() -> Callback<String> {
     Callback::from(|_| ())
@@ -42,42 +42,14 @@ pub fn textarea(props: &Props) -> Html {
         on_change.emit(get_value_from_input_event(input_event));
     });
 
-    let copy_to_clipboard = {
-        let v = value.clone();
-        move |_: MouseEvent| {
-            let v = v.clone();
-            wasm_bindgen_futures::spawn_local(async move {
-                copy_to_clipboard(v).await.unwrap();
-            });
-        }
-    };
-
     html! {
         <div class="textarea-container">
-            <textarea class="textarea" {placeholder} readonly={read_only} value={value} {oninput} />
+            <textarea class="textarea" {placeholder} readonly={read_only} value={value.clone()} {oninput} />
             if is_output {
                 <div class="output-controls">
-                    <button type="button" class="button-icon" aria-label="Copy output to clipboard" onclick={copy_to_clipboard}>
-                        <Icon icon_id={IconId::FeatherCopy} />
-                    </button>
+                    <CopyButton {value} />
                 </div>
             }
         </div>
     }
 }
-
-#[wasm_bindgen(inline_js=r#"
-export function copy_to_clipboard(value) {
-    try {
-        return window.navigator.clipboard.writeText(value);
-    } catch(e) {
-        console.log(e);
-        return Promise.reject(e)
-    }
-}
-"#)]
-#[rustfmt::skip]
-extern "C" { 
-    #[wasm_bindgen(catch)]
-    async fn copy_to_clipboard(value: String) -> Result<(), JsValue>;
-}
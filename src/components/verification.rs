@@ -0,0 +1,24 @@
+use yew::prelude::*;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    /// Nothing is shown for `None`
+    pub result: Option<Result<bool, String>>,
+}
+
+/// Match/mismatch badge for a verification result
+#[function_component(Verification)]
+pub fn verification(props: &Props) -> Html {
+    match &props.result {
+        None => html! {},
+        Some(Ok(true)) => html! {
+            <span class="verification match" role="status">{ "\u{2714} Match" }</span>
+        },
+        Some(Ok(false)) => html! {
+            <span class="verification mismatch" role="status">{ "\u{2718} Mismatch" }</span>
+        },
+        Some(Err(e)) => html! {
+            <span class="verification mismatch" role="status">{ format!("\u{2718} {}", e) }</span>
+        },
+    }
+}
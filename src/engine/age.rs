@@ -0,0 +1,175 @@
+//! age file encryption (https://age-encryption.org/v1)
+
+use std::io::{Read, Write};
+use std::iter;
+use std::str::FromStr;
+
+use age::armor::{ArmoredReader, ArmoredWriter, Format};
+use age::secrecy::{ExposeSecret, SecretString};
+use age::{scrypt, x25519, Decryptor, Encryptor, Identity, Recipient};
+use serde::{Deserialize, Serialize};
+
+/// What an age file is encrypted to. X25519 keys are recipients (`age1...`)
+/// when encrypting and identities (`AGE-SECRET-KEY-1...`) when decrypting.
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+pub enum AgeKeys {
+    X25519(Vec<String>),
+    Passphrase(String),
+}
+
+impl AgeKeys {
+    /// Keys separated by whitespace or commas, skipping `#` comment lines as
+    /// found in identity files
+    pub fn x25519_from_text(text: &str) -> Self {
+        let keys = text
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .flat_map(|line| line.split(|c: char| c == ',' || c.is_whitespace()))
+            .filter(|key| !key.is_empty())
+            .map(str::to_string)
+            .collect();
+        AgeKeys::X25519(keys)
+    }
+}
+
+/// Encrypt to an age file, ASCII armored if `armor` is set
+pub fn age_encrypt(plaintext: &[u8], keys: &AgeKeys, armor: bool) -> Result<Vec<u8>, String> {
+    let encryptor = match keys {
+        AgeKeys::X25519(recipients) => {
+            let recipients = recipients
+                .iter()
+                .map(|recipient| {
+                    x25519::Recipient::from_str(recipient)
+                        .map_err(|e| format!("Invalid recipient {}: {}", recipient, e))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Encryptor::with_recipients(
+                recipients
+                    .iter()
+                    .map(|recipient| recipient as &dyn Recipient),
+            )
+            .map_err(|e| e.to_string())?
+        }
+        AgeKeys::Passphrase(passphrase) => {
+            Encryptor::with_user_passphrase(SecretString::from(passphrase.clone()))
+        }
+    };
+
+    let format = if armor {
+        Format::AsciiArmor
+    } else {
+        Format::Binary
+    };
+    let mut output = Vec::new();
+    let armored = ArmoredWriter::wrap_output(&mut output, format).map_err(|e| e.to_string())?;
+    let mut writer = encryptor.wrap_output(armored).map_err(|e| e.to_string())?;
+    writer.write_all(plaintext).map_err(|e| e.to_string())?;
+    writer
+        .finish()
+        .and_then(|armored| armored.finish())
+        .map_err(|e| e.to_string())?;
+    Ok(output)
+}
+
+/// Decrypt an age file, which may be ASCII armored
+pub fn age_decrypt(data: &[u8], keys: &AgeKeys) -> Result<Vec<u8>, String> {
+    let decryptor = Decryptor::new_buffered(ArmoredReader::new(data))
+        .map_err(|e| format!("Not an age file: {}", e))?;
+    let reader = match keys {
+        AgeKeys::X25519(identities) => {
+            let identities = identities
+                .iter()
+                .map(|identity| {
+                    x25519::Identity::from_str(identity)
+                        .map_err(|e| format!("Invalid identity: {}", e))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            decryptor.decrypt(identities.iter().map(|identity| identity as &dyn Identity))
+        }
+        AgeKeys::Passphrase(passphrase) => {
+            let identity = scrypt::Identity::new(SecretString::from(passphrase.clone()));
+            decryptor.decrypt(iter::once(&identity as &dyn Identity))
+        }
+    };
+
+    let mut plaintext = Vec::new();
+    reader
+        .map_err(|e| e.to_string())?
+        .read_to_end(&mut plaintext)
+        .map_err(|e| e.to_string())?;
+    Ok(plaintext)
+}
+
+/// A new X25519 identity and its recipient
+pub fn age_generate_identity() -> (String, String) {
+    let identity = x25519::Identity::generate();
+    (
+        identity.to_string().expose_secret().to_string(),
+        identity.to_public().to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // From the C2SP age test kit (`x25519`, `armor` and `scrypt`), written by
+    // the reference Go implementation
+    const IDENTITY: &str =
+        "AGE-SECRET-KEY-1EGTZVFFV20835NWYV6270LXYVK2VKNX2MMDKWYKLMGR48UAWX40Q2P2LM0";
+    const ARMORED: &str = "-----BEGIN AGE ENCRYPTED FILE-----
+YWdlLWVuY3J5cHRpb24ub3JnL3YxCi0+IFgyNTUxOSBURWlGMHlwcXIrYnB2Y3FY
+TnlDVkpwTDdPdXdQZFZ3UEw3S1FFYkZET0NjCmhqYWJHWHdTTFE5YzNTNkx3Mmkr
+UzJUdTJmaXdRSEhzbGJCTjZCNDFGTEUKLS0tIFd5SnA5Ri85Rk9aaDdnSmRoZXEy
+V0lKY3dIZ1ljOE5JVmgzZGR3aHJjTmcK7s9ix86RtDMnTmjU8vkTTLdMW/73vqpS
+yPC8DpksHoMx+2Y=
+-----END AGE ENCRYPTED FILE-----
+";
+    const SCRYPT: &str = "6167652d656e6372797074696f6e2e6f72672f76310a2d3e20736372797074207246302f4e77626c55484854706751675270653543512031300a67556a45796d464b4d565851454b644d4d484c32346f5965786a4533544943304f307a4753714a326155590a2d2d2d20494f5869515953746b6f54316d765a573274464f715a64685256766a353865674142782f7357665a5162630a1b35c6e687dd00da3ac379ac9f742c21fd185a1b9e3ded739d14ac6a9a50124db866d8";
+
+    #[test]
+    fn decrypts_reference_x25519() {
+        let keys = AgeKeys::X25519(vec![IDENTITY.to_string()]);
+        assert_eq!(age_decrypt(ARMORED.as_bytes(), &keys).unwrap(), b"age");
+    }
+
+    #[test]
+    fn decrypts_reference_scrypt() {
+        let keys = AgeKeys::Passphrase("password".to_string());
+        let file = hex::decode(SCRYPT).unwrap();
+        assert_eq!(age_decrypt(&file, &keys).unwrap(), b"age");
+
+        let keys = AgeKeys::Passphrase("wrong".to_string());
+        assert!(age_decrypt(&file, &keys).is_err());
+    }
+
+    #[test]
+    fn x25519_round_trip() {
+        let (identity, recipient) = age_generate_identity();
+        let (other_identity, other_recipient) = age_generate_identity();
+        let plaintext = b"Hello, age!";
+
+        for armor in [false, true] {
+            let recipients = AgeKeys::x25519_from_text(&format!(
+                "# two recipients\n{}, {}",
+                recipient, other_recipient
+            ));
+            let file = age_encrypt(plaintext, &recipients, armor).unwrap();
+            assert_eq!(file.starts_with(b"-----BEGIN AGE"), armor);
+
+            for identity in [&identity, &other_identity] {
+                let keys = AgeKeys::X25519(vec![identity.clone()]);
+                assert_eq!(age_decrypt(&file, &keys).unwrap(), plaintext);
+            }
+            let keys = AgeKeys::X25519(vec![IDENTITY.to_string()]);
+            assert!(age_decrypt(&file, &keys).is_err());
+        }
+    }
+
+    #[test]
+    fn passphrase_round_trip() {
+        let keys = AgeKeys::Passphrase("correct horse battery staple".to_string());
+        let file = age_encrypt(b"Hello, age!", &keys, true).unwrap();
+        assert_eq!(age_decrypt(&file, &keys).unwrap(), b"Hello, age!");
+    }
+}
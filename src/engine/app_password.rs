@@ -0,0 +1,189 @@
+//! Password hashes specific to operating systems, databases and frameworks
+
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use super::crypt::des_encrypt_block;
+use super::{hmac_digest, md4_hash, md5_hash, sha1_hash, sha256_hash, sha512_hash, HashAlgorithm};
+
+/// LM hash stored when a password has none, e.g. when longer than 14 characters
+pub const EMPTY_LM_HASH: &str = "aad3b435b51404eeaad3b435b51404ee";
+
+/// Windows NT hash: MD4 of the UTF-16LE password
+pub fn nt_hash(password: &str) -> Vec<u8> {
+    let utf16 = password
+        .encode_utf16()
+        .flat_map(u16::to_le_bytes)
+        .collect::<Vec<_>>();
+    md4_hash(&utf16)
+}
+
+/// Windows LAN Manager hash, for ASCII passwords of up to 14 characters
+pub fn lm_hash(password: &str) -> Result<Vec<u8>, String> {
+    if !password.is_ascii() {
+        return Err("LM hashes only support ASCII passwords".to_string());
+    }
+    if password.len() > 14 {
+        return Err("LM hashes only support passwords of up to 14 characters".to_string());
+    }
+
+    let mut key = [0; 14];
+    key[..password.len()].copy_from_slice(password.to_ascii_uppercase().as_bytes());
+    Ok(key
+        .chunks(7)
+        .flat_map(|half| des_encrypt_block(lm_des_key(half), *b"KGS!@#$%"))
+        .collect())
+}
+
+/// Spread 7 key bytes over the high 7 bits of 8 DES key bytes
+fn lm_des_key(half: &[u8]) -> [u8; 8] {
+    let bits = half
+        .iter()
+        .fold(0u64, |bits, &byte| (bits << 8) | u64::from(byte));
+    let mut key = [0; 8];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = (((bits >> (49 - 7 * i)) & 0x7f) as u8) << 1;
+    }
+    key
+}
+
+/// MySQL's pre-4.1 `PASSWORD()`, later `OLD_PASSWORD()`, as 16 hex digits
+pub fn mysql_old_password(password: &str) -> String {
+    let (mut nr, mut nr2, mut add) = (1345345333u32, 0x12345671u32, 7u32);
+    for byte in password
+        .bytes()
+        .filter(|&byte| byte != b' ' && byte != b'\t')
+    {
+        let byte = u32::from(byte);
+        nr ^= (nr & 63)
+            .wrapping_add(add)
+            .wrapping_mul(byte)
+            .wrapping_add(nr << 8);
+        nr2 = nr2.wrapping_add((nr2 << 8) ^ nr);
+        add = add.wrapping_add(byte);
+    }
+    format!("{:08x}{:08x}", nr & 0x7fff_ffff, nr2 & 0x7fff_ffff)
+}
+
+/// MySQL 4.1+ `PASSWORD()`, as used by `mysql_native_password`
+pub fn mysql_native_password(password: &str) -> String {
+    let digest = sha1_hash(&sha1_hash(password.as_bytes()));
+    format!("*{}", hex::encode_upper(digest))
+}
+
+/// PostgreSQL `md5` verifier, salted with the role name
+pub fn postgres_md5(password: &str, user: &str) -> String {
+    let digest = md5_hash(format!("{}{}", password, user).as_bytes());
+    format!("md5{}", hex::encode(digest))
+}
+
+/// PostgreSQL SCRAM-SHA-256 verifier.
+///
+/// The password is not SASLprep normalised, which only matters for non-ASCII.
+pub fn scram_sha256(password: &str, salt: &[u8], iterations: u32) -> Result<String, String> {
+    let mut salted = [0; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt, iterations, &mut salted);
+    let client_key = hmac_digest(HashAlgorithm::Sha256, &salted, b"Client Key")?;
+    let server_key = hmac_digest(HashAlgorithm::Sha256, &salted, b"Server Key")?;
+    Ok(format!(
+        "SCRAM-SHA-256${}:{}${}:{}",
+        iterations,
+        base64::encode(salt),
+        base64::encode(sha256_hash(&client_key)),
+        base64::encode(server_key)
+    ))
+}
+
+/// Django's `pbkdf2_sha256$iterations$salt$hash`
+pub fn django_pbkdf2_sha256(password: &str, salt: &str, iterations: u32) -> String {
+    let mut hash = [0; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), salt.as_bytes(), iterations, &mut hash);
+    format!(
+        "pbkdf2_sha256${}${}${}",
+        iterations,
+        salt,
+        base64::encode(hash)
+    )
+}
+
+/// Salted LDAP `userPassword` schemes: Base64 of the digest followed by the salt
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum LdapScheme {
+    #[default]
+    Ssha,
+    Ssha512,
+}
+
+impl LdapScheme {
+    pub const ALL: [LdapScheme; 2] = [LdapScheme::Ssha, LdapScheme::Ssha512];
+
+    pub fn prefix(&self) -> &'static str {
+        match self {
+            LdapScheme::Ssha => "{SSHA}",
+            LdapScheme::Ssha512 => "{SSHA512}",
+        }
+    }
+
+    /// Scheme of a `{SSHA}...` string, and the Base64 after the prefix
+    pub fn from_hash(hash: &str) -> Option<(Self, &str)> {
+        Self::ALL
+            .into_iter()
+            .find_map(|scheme| Some((scheme, hash.strip_prefix(scheme.prefix())?)))
+    }
+
+    pub fn digest_size(&self) -> usize {
+        match self {
+            LdapScheme::Ssha => 20,
+            LdapScheme::Ssha512 => 64,
+        }
+    }
+
+    pub fn digest(&self, msg: &[u8]) -> Vec<u8> {
+        match self {
+            LdapScheme::Ssha => sha1_hash(msg),
+            LdapScheme::Ssha512 => sha512_hash(msg),
+        }
+    }
+}
+
+pub fn ldap_salted_hash(scheme: LdapScheme, password: &str, salt: &[u8]) -> String {
+    let mut value = scheme.digest(&[password.as_bytes(), salt].concat());
+    value.extend_from_slice(salt);
+    format!("{}{}", scheme.prefix(), base64::encode(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn windows_hashes() {
+        assert_eq!(
+            hex::encode(lm_hash("password").unwrap()),
+            "e52cac67419a9a224a3b108f3fa6cb6d"
+        );
+        assert_eq!(hex::encode(lm_hash("").unwrap()), EMPTY_LM_HASH);
+        assert_eq!(
+            hex::encode(nt_hash("password")),
+            "8846f7eaee8fb117ad06bdd830b7586c"
+        );
+    }
+
+    #[test]
+    fn mysql_hashes() {
+        assert_eq!(mysql_old_password("password"), "5d2e19393cc5ef67");
+        assert_eq!(
+            mysql_native_password("password"),
+            "*2470C0C06DEE42FD1618BB99005ADCA2EC9D1E19"
+        );
+    }
+
+    #[test]
+    fn postgres_scram_matches_pg_authid() {
+        let salt = base64::decode("K0ko0/P1UCRygtURI8o1bA==").unwrap();
+        assert_eq!(
+            scram_sha256("password", &salt, 4096).unwrap(),
+            "SCRAM-SHA-256$4096:K0ko0/P1UCRygtURI8o1bA==$3g/WJGiajOl6QDFa8FcTq74Pri0grMkoZwzxtHypOGU=:xd5rv58J/NCLFuHhQbg3mQ4V/e9kRu3Dh7GcMPWz5CU="
+        );
+    }
+}
@@ -0,0 +1,673 @@
+//! ASN.1 BER/DER parsing for inspecting certificates, keys and other structures
+
+use super::{days_from_civil, decode_pem, parse_digest};
+
+/// Deepest nesting accepted, so crafted input can't overflow the stack
+const MAX_DEPTH: usize = 64;
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum TagClass {
+    Universal,
+    Application,
+    ContextSpecific,
+    Private,
+}
+
+/// A parsed element, with offsets into the whole input
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Asn1Element {
+    pub offset: usize,
+    pub header_length: usize,
+    /// Length of the contents, excluding any end-of-contents octets
+    pub length: usize,
+    /// Whether the BER indefinite length form was used
+    pub indefinite: bool,
+    pub class: TagClass,
+    pub constructed: bool,
+    pub tag: u32,
+    /// Contents of a primitive element
+    pub contents: Vec<u8>,
+    /// Elements of a constructed element, or DER encapsulated in a primitive
+    /// OCTET STRING or BIT STRING
+    pub children: Vec<Asn1Element>,
+}
+
+impl Asn1Element {
+    pub fn is_universal(&self, tag: u32) -> bool {
+        self.class == TagClass::Universal && self.tag == tag
+    }
+
+    /// Tag name, such as `SEQUENCE` or `[0]` for context-specific tags
+    pub fn name(&self) -> String {
+        match self.class {
+            TagClass::Universal => match universal_name(self.tag) {
+                Some(name) => name.to_string(),
+                None => format!("[UNIVERSAL {}]", self.tag),
+            },
+            TagClass::Application => format!("[APPLICATION {}]", self.tag),
+            TagClass::ContextSpecific => format!("[{}]", self.tag),
+            TagClass::Private => format!("[PRIVATE {}]", self.tag),
+        }
+    }
+
+    /// Contents of a primitive element rendered for display
+    pub fn value(&self) -> Option<String> {
+        if self.constructed || !self.children.is_empty() || self.contents.is_empty() {
+            return None;
+        }
+        let contents = &self.contents;
+        if self.class != TagClass::Universal {
+            return Some(match printable(contents) {
+                Some(text) => format!("'{}'", text),
+                None => hex::encode(contents),
+            });
+        }
+        let value = match self.tag {
+            1 if contents.len() == 1 => match contents[0] {
+                0 => "FALSE".to_string(),
+                _ => "TRUE".to_string(),
+            },
+            2 | 10 => format_integer(contents),
+            3 => match contents[0] {
+                0 => hex::encode(&contents[1..]),
+                unused => format!("{} ({} unused bits)", hex::encode(&contents[1..]), unused),
+            },
+            6 => match self.oid() {
+                Some(oid) => match oid_name(&oid) {
+                    Some(name) => format!("{} ({})", oid, name),
+                    None => oid,
+                },
+                None => format!("Invalid: {}", hex::encode(contents)),
+            },
+            23 | 24 => match self.time() {
+                Some(time) => format!("{} ({})", String::from_utf8_lossy(contents), time),
+                None => format!("'{}'", String::from_utf8_lossy(contents)),
+            },
+            _ => match self.text() {
+                Some(text) => format!("'{}'", text),
+                None => hex::encode(contents),
+            },
+        };
+        Some(value)
+    }
+
+    /// Contents of a character string type as text
+    pub fn text(&self) -> Option<String> {
+        if self.class != TagClass::Universal || self.constructed {
+            return None;
+        }
+        let contents = &self.contents;
+        let text = match self.tag {
+            12 | 18 | 19 | 20 | 21 | 22 | 25 | 26 | 27 => {
+                String::from_utf8_lossy(contents).into_owned()
+            }
+            28 => contents
+                .chunks(4)
+                .map(|c| {
+                    let code = c.iter().fold(0u32, |code, byte| code << 8 | *byte as u32);
+                    char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+                })
+                .collect(),
+            30 => {
+                let units = contents
+                    .chunks(2)
+                    .map(|c| c.iter().fold(0u16, |unit, byte| unit << 8 | *byte as u16))
+                    .collect::<Vec<_>>();
+                String::from_utf16_lossy(&units)
+            }
+            _ => return None,
+        };
+        Some(text)
+    }
+
+    /// Dotted form of an OBJECT IDENTIFIER
+    pub fn oid(&self) -> Option<String> {
+        if !self.is_universal(6) || self.contents.is_empty() {
+            return None;
+        }
+        let mut arcs = Vec::new();
+        let mut arc: u128 = 0;
+        for byte in &self.contents {
+            if arc > u128::MAX >> 7 {
+                return None;
+            }
+            arc = arc << 7 | (byte & 0x7f) as u128;
+            if byte & 0x80 == 0 {
+                arcs.push(arc);
+                arc = 0;
+            }
+        }
+        if self.contents.last()? & 0x80 != 0 {
+            return None;
+        }
+        let (first, second) = match arcs[0] {
+            arc @ 0..=39 => (0, arc),
+            arc @ 40..=79 => (1, arc - 40),
+            arc => (2, arc - 80),
+        };
+        let oid = [first, second]
+            .iter()
+            .chain(&arcs[1..])
+            .map(|arc| arc.to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+        Some(oid)
+    }
+
+    /// A UTCTime or GeneralizedTime as `YYYY-MM-DD HH:MM:SS UTC`, or with the
+    /// offset it was written with
+    pub fn time(&self) -> Option<String> {
+        let text = std::str::from_utf8(&self.contents).ok()?;
+        let (year, rest) = match self.tag {
+            23 if self.class == TagClass::Universal => {
+                let year: u32 = text.get(..2)?.parse().ok()?;
+                // RFC 5280: two digit years from 50 are in the 1900s
+                (
+                    if year >= 50 { 1900 + year } else { 2000 + year },
+                    &text[2..],
+                )
+            }
+            24 if self.class == TagClass::Universal => (text.get(..4)?.parse().ok()?, &text[4..]),
+            _ => return None,
+        };
+        // Month, day, hour and minute, with optional seconds
+        let digits = rest.get(..8)?;
+        if !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let (seconds, zone) = match rest.get(8..10) {
+            Some(seconds) if seconds.bytes().all(|b| b.is_ascii_digit()) => (seconds, &rest[10..]),
+            _ => ("00", &rest[8..]),
+        };
+        let (fraction, zone) = match zone.strip_prefix('.') {
+            Some(zone) => {
+                let end = zone
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(zone.len());
+                (&zone[..end], &zone[end..])
+            }
+            None => ("", zone),
+        };
+        let zone = match zone {
+            "Z" => "UTC".to_string(),
+            "" => "local time".to_string(),
+            offset
+                if offset.len() == 5
+                    && (offset.starts_with('+') || offset.starts_with('-'))
+                    && offset[1..].bytes().all(|b| b.is_ascii_digit()) =>
+            {
+                format!("UTC{}:{}", &offset[..3], &offset[3..])
+            }
+            _ => return None,
+        };
+        Some(format!(
+            "{}-{}-{} {}:{}:{}{} {}",
+            year,
+            &digits[0..2],
+            &digits[2..4],
+            &digits[4..6],
+            &digits[6..8],
+            seconds,
+            if fraction.is_empty() {
+                String::default()
+            } else {
+                format!(".{}", fraction)
+            },
+            zone
+        ))
+    }
+
+    /// Seconds since the Unix epoch of a UTCTime or GeneralizedTime in the
+    /// `YYMMDDHHMMSSZ` or `YYYYMMDDHHMMSSZ` form required by RFC 5280
+    pub fn unix_time(&self) -> Option<i64> {
+        if self.class != TagClass::Universal {
+            return None;
+        }
+        let text = std::str::from_utf8(&self.contents)
+            .ok()?
+            .strip_suffix('Z')?;
+        if !text.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let (year, rest) = match (self.tag, text.len()) {
+            (23, 12) => {
+                let year: i64 = text[..2].parse().ok()?;
+                (
+                    if year >= 50 { 1900 + year } else { 2000 + year },
+                    &text[2..],
+                )
+            }
+            (24, 14) => (text[..4].parse().ok()?, &text[4..]),
+            _ => return None,
+        };
+        let field = |index: usize| rest[index * 2..index * 2 + 2].parse::<i64>().ok();
+        let (month, day) = (field(0)?, field(1)?);
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return None;
+        }
+        let days = days_from_civil(year, month, day);
+        Some(days * 86400 + field(2)? * 3600 + field(3)? * 60 + field(4)?)
+    }
+}
+
+/// DER from a PEM block, hex or Base64, with the PEM label if there was one
+pub fn decode_der_input(text: &str) -> Result<(Option<String>, Vec<u8>), String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Input is empty".to_string());
+    }
+    if text.contains("-----BEGIN ") {
+        let (label, der, _) = decode_pem(text)?;
+        return Ok((Some(label.to_string()), der));
+    }
+    parse_digest(text)
+        .map(|der| (None, der))
+        .map_err(|_| "Input is not PEM, hex or Base64".to_string())
+}
+
+/// Parse BER, which includes DER, into its top level elements
+pub fn parse_asn1(data: &[u8]) -> Result<Vec<Asn1Element>, String> {
+    if data.is_empty() {
+        return Err("Input is empty".to_string());
+    }
+    parse_elements(data, 0, 0)
+}
+
+/// Render elements as an indented tree of offsets, lengths, tags and values,
+/// in the style of `dumpasn1`
+pub fn format_asn1(elements: &[Asn1Element]) -> String {
+    let mut output = String::new();
+    format_elements(elements, 0, &mut output);
+    output
+}
+
+fn format_elements(elements: &[Asn1Element], depth: usize, output: &mut String) {
+    for element in elements {
+        let length = if element.indefinite {
+            "inf".to_string()
+        } else {
+            element.length.to_string()
+        };
+        let mut line = format!(
+            "{:>6} {:>6}: {}{}",
+            element.offset,
+            length,
+            "  ".repeat(depth),
+            element.name()
+        );
+        if !element.constructed && !element.children.is_empty() {
+            line.push_str(", encapsulates");
+        }
+        if let Some(value) = element.value() {
+            line.push(' ');
+            line.push_str(&value);
+        }
+        output.push_str(&line);
+        output.push('\n');
+        format_elements(&element.children, depth + 1, output);
+    }
+}
+
+fn parse_elements(data: &[u8], base: usize, depth: usize) -> Result<Vec<Asn1Element>, String> {
+    let mut elements = Vec::new();
+    let mut position = 0;
+    while position < data.len() {
+        let (element, next) = parse_element(data, position, base, depth)?;
+        elements.push(element);
+        position = next;
+    }
+    Ok(elements)
+}
+
+/// Parse the element starting at `start`, returning it and the position after it
+fn parse_element(
+    data: &[u8],
+    start: usize,
+    base: usize,
+    depth: usize,
+) -> Result<(Asn1Element, usize), String> {
+    let offset = base + start;
+    if depth > MAX_DEPTH {
+        return Err(format!("Nesting deeper than {} levels", MAX_DEPTH));
+    }
+    let truncated = || format!("Truncated element at offset {}", offset);
+    let mut bytes = data[start..].iter();
+    let identifier = *bytes.next().ok_or_else(truncated)?;
+    let class = match identifier >> 6 {
+        0 => TagClass::Universal,
+        1 => TagClass::Application,
+        2 => TagClass::ContextSpecific,
+        _ => TagClass::Private,
+    };
+    let constructed = identifier & 0x20 != 0;
+    let mut tag = (identifier & 0x1f) as u32;
+    if tag == 0x1f {
+        tag = 0;
+        loop {
+            let byte = *bytes.next().ok_or_else(truncated)?;
+            if tag > u32::MAX >> 7 {
+                return Err(format!("Tag number too large at offset {}", offset));
+            }
+            tag = tag << 7 | (byte & 0x7f) as u32;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+    }
+
+    let length_byte = *bytes.next().ok_or_else(truncated)?;
+    let length = match length_byte {
+        0..=0x7f => Some(length_byte as usize),
+        0x80 if constructed => None,
+        0x80 => {
+            return Err(format!(
+                "Indefinite length on a primitive element at offset {}",
+                offset
+            ))
+        }
+        0x81..=0x84 => {
+            let mut length = 0;
+            for _ in 0..length_byte & 0x7f {
+                length = length << 8 | *bytes.next().ok_or_else(truncated)? as usize;
+            }
+            Some(length)
+        }
+        _ => return Err(format!("Unsupported length at offset {}", offset)),
+    };
+    let position = data.len() - bytes.len();
+    let header_length = position - start;
+
+    let mut element = Asn1Element {
+        offset,
+        header_length,
+        length: 0,
+        indefinite: length.is_none(),
+        class,
+        constructed,
+        tag,
+        contents: Vec::new(),
+        children: Vec::new(),
+    };
+
+    let Some(length) = length else {
+        // Indefinite length: elements up to the end-of-contents octets
+        let mut position = position;
+        loop {
+            match data.get(position..position + 2) {
+                Some([0, 0]) => break,
+                Some(_) => {
+                    let (child, next) = parse_element(data, position, base, depth + 1)?;
+                    element.children.push(child);
+                    position = next;
+                }
+                None => {
+                    return Err(format!(
+                        "Missing end-of-contents for the element at offset {}",
+                        offset
+                    ))
+                }
+            }
+        }
+        element.length = position - start - header_length;
+        return Ok((element, position + 2));
+    };
+
+    let end = match position.checked_add(length) {
+        Some(end) if end <= data.len() => end,
+        _ => {
+            return Err(format!(
+                "Element at offset {} has length {} but only {} bytes remain",
+                offset,
+                length,
+                data.len() - position
+            ))
+        }
+    };
+    let contents = &data[position..end];
+    element.length = length;
+    if constructed {
+        element.children = parse_elements(contents, base + position, depth + 1)?;
+    } else {
+        element.children = encapsulated(&element, contents, base + position, depth);
+        element.contents = contents.to_vec();
+    }
+    Ok((element, end))
+}
+
+/// DER nested inside an OCTET STRING or BIT STRING, as used for certificate
+/// extensions and public keys. This is a guess, so anything that doesn't
+/// parse cleanly is left as bytes.
+fn encapsulated(
+    element: &Asn1Element,
+    contents: &[u8],
+    base: usize,
+    depth: usize,
+) -> Vec<Asn1Element> {
+    let (inner, base) = match element.tag {
+        3 if element.class == TagClass::Universal && contents.first() == Some(&0) => {
+            (&contents[1..], base + 1)
+        }
+        4 if element.class == TagClass::Universal => (contents, base),
+        _ => return Vec::new(),
+    };
+    if inner.len() < 2 || inner[0] & 0xc0 != 0 || !(1..=30).contains(&(inner[0] & 0x1f)) {
+        return Vec::new();
+    }
+    parse_elements(inner, base, depth + 1).unwrap_or_default()
+}
+
+/// Signed integers up to 128 bits in decimal, larger ones in hex
+fn format_integer(contents: &[u8]) -> String {
+    if contents.len() > 16 {
+        return format!("0x{}", hex::encode(contents));
+    }
+    let negative = contents[0] & 0x80 != 0;
+    let initial = if negative { -1i128 } else { 0 };
+    let value = contents
+        .iter()
+        .fold(initial, |value, byte| value << 8 | *byte as i128);
+    value.to_string()
+}
+
+fn printable(contents: &[u8]) -> Option<&str> {
+    if contents.iter().all(|byte| (0x20..0x7f).contains(byte)) {
+        std::str::from_utf8(contents).ok()
+    } else {
+        None
+    }
+}
+
+fn universal_name(tag: u32) -> Option<&'static str> {
+    let name = match tag {
+        0 => "END OF CONTENTS",
+        1 => "BOOLEAN",
+        2 => "INTEGER",
+        3 => "BIT STRING",
+        4 => "OCTET STRING",
+        5 => "NULL",
+        6 => "OBJECT IDENTIFIER",
+        7 => "ObjectDescriptor",
+        8 => "EXTERNAL",
+        9 => "REAL",
+        10 => "ENUMERATED",
+        11 => "EMBEDDED PDV",
+        12 => "UTF8String",
+        13 => "RELATIVE-OID",
+        16 => "SEQUENCE",
+        17 => "SET",
+        18 => "NumericString",
+        19 => "PrintableString",
+        20 => "T61String",
+        21 => "VideotexString",
+        22 => "IA5String",
+        23 => "UTCTime",
+        24 => "GeneralizedTime",
+        25 => "GraphicString",
+        26 => "VisibleString",
+        27 => "GeneralString",
+        28 => "UniversalString",
+        30 => "BMPString",
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// Name of a well-known object identifier, in dotted form
+pub fn oid_name(oid: &str) -> Option<&'static str> {
+    OID_NAMES
+        .iter()
+        .find(|(known, _)| *known == oid)
+        .map(|(_, name)| *name)
+}
+
+const OID_NAMES: &[(&str, &str)] = &[
+    // Public key and signature algorithms
+    ("1.2.840.113549.1.1.1", "rsaEncryption"),
+    ("1.2.840.113549.1.1.4", "md5WithRSAEncryption"),
+    ("1.2.840.113549.1.1.5", "sha1WithRSAEncryption"),
+    ("1.2.840.113549.1.1.7", "RSAES-OAEP"),
+    ("1.2.840.113549.1.1.8", "MGF1"),
+    ("1.2.840.113549.1.1.10", "RSASSA-PSS"),
+    ("1.2.840.113549.1.1.11", "sha256WithRSAEncryption"),
+    ("1.2.840.113549.1.1.12", "sha384WithRSAEncryption"),
+    ("1.2.840.113549.1.1.13", "sha512WithRSAEncryption"),
+    ("1.2.840.113549.1.1.14", "sha224WithRSAEncryption"),
+    ("1.2.840.10040.4.1", "dsa"),
+    ("1.2.840.10040.4.3", "dsa-with-sha1"),
+    ("1.2.840.10045.2.1", "ecPublicKey"),
+    ("1.2.840.10045.3.1.7", "prime256v1"),
+    ("1.2.840.10045.4.1", "ecdsa-with-SHA1"),
+    ("1.2.840.10045.4.3.1", "ecdsa-with-SHA224"),
+    ("1.2.840.10045.4.3.2", "ecdsa-with-SHA256"),
+    ("1.2.840.10045.4.3.3", "ecdsa-with-SHA384"),
+    ("1.2.840.10045.4.3.4", "ecdsa-with-SHA512"),
+    ("1.3.132.0.10", "secp256k1"),
+    ("1.3.132.0.34", "secp384r1"),
+    ("1.3.132.0.35", "secp521r1"),
+    ("1.3.101.110", "X25519"),
+    ("1.3.101.111", "X448"),
+    ("1.3.101.112", "Ed25519"),
+    ("1.3.101.113", "Ed448"),
+    // Hashes, MACs and ciphers
+    ("1.2.840.113549.2.5", "md5"),
+    ("1.3.14.3.2.26", "sha1"),
+    ("2.16.840.1.101.3.4.2.1", "sha256"),
+    ("2.16.840.1.101.3.4.2.2", "sha384"),
+    ("2.16.840.1.101.3.4.2.3", "sha512"),
+    ("2.16.840.1.101.3.4.2.4", "sha224"),
+    ("2.16.840.1.101.3.4.2.8", "sha3-256"),
+    ("2.16.840.1.101.3.4.2.9", "sha3-384"),
+    ("2.16.840.1.101.3.4.2.10", "sha3-512"),
+    ("1.2.840.113549.2.7", "hmacWithSHA1"),
+    ("1.2.840.113549.2.9", "hmacWithSHA256"),
+    ("1.2.840.113549.2.10", "hmacWithSHA384"),
+    ("1.2.840.113549.2.11", "hmacWithSHA512"),
+    ("2.16.840.1.101.3.4.1.2", "aes128-CBC"),
+    ("2.16.840.1.101.3.4.1.6", "aes128-GCM"),
+    ("2.16.840.1.101.3.4.1.22", "aes192-CBC"),
+    ("2.16.840.1.101.3.4.1.42", "aes256-CBC"),
+    ("2.16.840.1.101.3.4.1.46", "aes256-GCM"),
+    ("1.2.840.113549.3.7", "des-ede3-cbc"),
+    // PKCS #5, #7, #9 and #12
+    ("1.2.840.113549.1.5.12", "PBKDF2"),
+    ("1.2.840.113549.1.5.13", "PBES2"),
+    ("1.2.840.113549.1.7.1", "data"),
+    ("1.2.840.113549.1.7.2", "signedData"),
+    ("1.2.840.113549.1.7.3", "envelopedData"),
+    ("1.2.840.113549.1.7.6", "encryptedData"),
+    ("1.2.840.113549.1.9.1", "emailAddress"),
+    ("1.2.840.113549.1.9.3", "contentType"),
+    ("1.2.840.113549.1.9.4", "messageDigest"),
+    ("1.2.840.113549.1.9.5", "signingTime"),
+    ("1.2.840.113549.1.9.7", "challengePassword"),
+    ("1.2.840.113549.1.9.14", "extensionRequest"),
+    ("1.2.840.113549.1.9.20", "friendlyName"),
+    ("1.2.840.113549.1.9.21", "localKeyID"),
+    ("1.2.840.113549.1.12.10.1.2", "pkcs8ShroudedKeyBag"),
+    ("1.2.840.113549.1.12.10.1.3", "certBag"),
+    // Distinguished name attributes
+    ("2.5.4.3", "commonName"),
+    ("2.5.4.4", "surname"),
+    ("2.5.4.5", "serialNumber"),
+    ("2.5.4.6", "countryName"),
+    ("2.5.4.7", "localityName"),
+    ("2.5.4.8", "stateOrProvinceName"),
+    ("2.5.4.9", "streetAddress"),
+    ("2.5.4.10", "organizationName"),
+    ("2.5.4.11", "organizationalUnitName"),
+    ("2.5.4.12", "title"),
+    ("2.5.4.17", "postalCode"),
+    ("2.5.4.42", "givenName"),
+    ("2.5.4.46", "dnQualifier"),
+    ("2.5.4.97", "organizationIdentifier"),
+    ("0.9.2342.19200300.100.1.1", "userId"),
+    ("0.9.2342.19200300.100.1.25", "domainComponent"),
+    ("1.3.6.1.4.1.311.60.2.1.3", "jurisdictionCountryName"),
+    ("2.5.4.15", "businessCategory"),
+    // Certificate and CRL extensions
+    ("2.5.29.14", "subjectKeyIdentifier"),
+    ("2.5.29.15", "keyUsage"),
+    ("2.5.29.17", "subjectAltName"),
+    ("2.5.29.18", "issuerAltName"),
+    ("2.5.29.19", "basicConstraints"),
+    ("2.5.29.20", "cRLNumber"),
+    ("2.5.29.21", "cRLReason"),
+    ("2.5.29.24", "invalidityDate"),
+    ("2.5.29.27", "deltaCRLIndicator"),
+    ("2.5.29.28", "issuingDistributionPoint"),
+    ("2.5.29.30", "nameConstraints"),
+    ("2.5.29.31", "cRLDistributionPoints"),
+    ("2.5.29.32", "certificatePolicies"),
+    ("2.5.29.32.0", "anyPolicy"),
+    ("2.5.29.35", "authorityKeyIdentifier"),
+    ("2.5.29.36", "policyConstraints"),
+    ("2.5.29.37", "extKeyUsage"),
+    ("2.5.29.37.0", "anyExtendedKeyUsage"),
+    ("1.3.6.1.5.5.7.1.1", "authorityInfoAccess"),
+    ("1.3.6.1.5.5.7.1.11", "subjectInfoAccess"),
+    ("1.3.6.1.5.5.7.1.24", "tlsFeature"),
+    ("1.3.6.1.4.1.11129.2.4.2", "signedCertificateTimestampList"),
+    ("1.3.6.1.4.1.11129.2.4.3", "precertificatePoison"),
+    ("1.3.6.1.5.5.7.48.1", "ocsp"),
+    ("1.3.6.1.5.5.7.48.2", "caIssuers"),
+    ("1.3.6.1.5.5.7.2.1", "cps"),
+    ("1.3.6.1.5.5.7.2.2", "unotice"),
+    ("2.23.140.1.1", "ev-guidelines"),
+    ("2.23.140.1.2.1", "domain-validated"),
+    ("2.23.140.1.2.2", "organization-validated"),
+    ("2.23.140.1.2.3", "individual-validated"),
+    // Extended key usages
+    ("1.3.6.1.5.5.7.3.1", "serverAuth"),
+    ("1.3.6.1.5.5.7.3.2", "clientAuth"),
+    ("1.3.6.1.5.5.7.3.3", "codeSigning"),
+    ("1.3.6.1.5.5.7.3.4", "emailProtection"),
+    ("1.3.6.1.5.5.7.3.8", "timeStamping"),
+    ("1.3.6.1.5.5.7.3.9", "OCSPSigning"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc_time(contents: &str) -> Vec<u8> {
+        let mut der = vec![0x17, contents.len() as u8];
+        der.extend_from_slice(contents.as_bytes());
+        der
+    }
+
+    #[test]
+    fn time_with_offset() {
+        let elements = parse_asn1(&utc_time("2301010000+0530")).unwrap();
+        assert_eq!(
+            elements[0].time().as_deref(),
+            Some("2023-01-01 00:00:00 UTC+05:30")
+        );
+    }
+
+    #[test]
+    fn time_with_non_ascii_offset() {
+        let elements = parse_asn1(&utc_time("2301010000+a\u{e9}b")).unwrap();
+        assert_eq!(elements[0].time(), None);
+        format_asn1(&elements);
+    }
+}
@@ -0,0 +1,488 @@
+/// Parameters of a CRC in the Rocksoft model, as used by the CRC RevEng catalogue
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub struct CrcParams {
+    pub name: &'static str,
+    /// Width in bits, 1 to 64
+    pub width: u8,
+    pub poly: u64,
+    pub init: u64,
+    pub refin: bool,
+    pub refout: bool,
+    pub xorout: u64,
+    /// CRC of the ASCII string `123456789`, as listed in the catalogue
+    pub check: u64,
+}
+
+#[allow(clippy::too_many_arguments)]
+const fn params(
+    name: &'static str,
+    width: u8,
+    poly: u64,
+    init: u64,
+    refin: bool,
+    refout: bool,
+    xorout: u64,
+    check: u64,
+) -> CrcParams {
+    CrcParams {
+        name,
+        width,
+        poly,
+        init,
+        refin,
+        refout,
+        xorout,
+        check,
+    }
+}
+
+/// Input the catalogue's check values are computed over
+const CHECK_INPUT: &[u8] = b"123456789";
+
+/// CRC-32/ISO-HDLC, as used by zip, PNG, Ethernet, etc.
+pub const CRC32_ISO_HDLC: CrcParams = params(
+    "CRC-32/ISO-HDLC",
+    32,
+    0x04c11db7,
+    0xffffffff,
+    true,
+    true,
+    0xffffffff,
+    0xcbf43926,
+);
+
+/// Well-known CRCs
+pub const CRC_CATALOGUE: &[CrcParams] = &[
+    params("CRC-5/USB", 5, 0x05, 0x1f, true, true, 0x1f, 0x19),
+    params("CRC-7/MMC", 7, 0x09, 0x00, false, false, 0x00, 0x75),
+    params("CRC-8/SMBUS", 8, 0x07, 0x00, false, false, 0x00, 0xf4),
+    params("CRC-8/I-432-1", 8, 0x07, 0x00, false, false, 0x55, 0xa1),
+    params("CRC-8/ROHC", 8, 0x07, 0xff, true, true, 0x00, 0xd0),
+    params("CRC-8/MAXIM-DOW", 8, 0x31, 0x00, true, true, 0x00, 0xa1),
+    params("CRC-8/AUTOSAR", 8, 0x2f, 0xff, false, false, 0xff, 0xdf),
+    params("CRC-8/BLUETOOTH", 8, 0xa7, 0x00, true, true, 0x00, 0x26),
+    params("CRC-8/CDMA2000", 8, 0x9b, 0xff, false, false, 0x00, 0xda),
+    params("CRC-8/DVB-S2", 8, 0xd5, 0x00, false, false, 0x00, 0xbc),
+    params("CRC-10/ATM", 10, 0x233, 0x000, false, false, 0x000, 0x199),
+    params(
+        "CRC-15/CAN",
+        15,
+        0x4599,
+        0x0000,
+        false,
+        false,
+        0x0000,
+        0x059e,
+    ),
+    params("CRC-16/ARC", 16, 0x8005, 0x0000, true, true, 0x0000, 0xbb3d),
+    params(
+        "CRC-16/IBM-3740 (CCITT-FALSE)",
+        16,
+        0x1021,
+        0xffff,
+        false,
+        false,
+        0x0000,
+        0x29b1,
+    ),
+    params(
+        "CRC-16/KERMIT (CCITT)",
+        16,
+        0x1021,
+        0x0000,
+        true,
+        true,
+        0x0000,
+        0x2189,
+    ),
+    params(
+        "CRC-16/XMODEM",
+        16,
+        0x1021,
+        0x0000,
+        false,
+        false,
+        0x0000,
+        0x31c3,
+    ),
+    params(
+        "CRC-16/SPI-FUJITSU (AUG-CCITT)",
+        16,
+        0x1021,
+        0x1d0f,
+        false,
+        false,
+        0x0000,
+        0xe5cc,
+    ),
+    params(
+        "CRC-16/GENIBUS",
+        16,
+        0x1021,
+        0xffff,
+        false,
+        false,
+        0xffff,
+        0xd64e,
+    ),
+    params(
+        "CRC-16/IBM-SDLC (X-25)",
+        16,
+        0x1021,
+        0xffff,
+        true,
+        true,
+        0xffff,
+        0x906e,
+    ),
+    params(
+        "CRC-16/MODBUS",
+        16,
+        0x8005,
+        0xffff,
+        true,
+        true,
+        0x0000,
+        0x4b37,
+    ),
+    params("CRC-16/USB", 16, 0x8005, 0xffff, true, true, 0xffff, 0xb4c8),
+    params(
+        "CRC-16/MAXIM-DOW",
+        16,
+        0x8005,
+        0x0000,
+        true,
+        true,
+        0xffff,
+        0x44c2,
+    ),
+    params(
+        "CRC-16/UMTS (BUYPASS)",
+        16,
+        0x8005,
+        0x0000,
+        false,
+        false,
+        0x0000,
+        0xfee8,
+    ),
+    params("CRC-16/DNP", 16, 0x3d65, 0x0000, true, true, 0xffff, 0xea82),
+    params(
+        "CRC-24/OPENPGP",
+        24,
+        0x864cfb,
+        0xb704ce,
+        false,
+        false,
+        0x000000,
+        0x21cf02,
+    ),
+    CRC32_ISO_HDLC,
+    params(
+        "CRC-32/ISCSI (CRC-32C)",
+        32,
+        0x1edc6f41,
+        0xffffffff,
+        true,
+        true,
+        0xffffffff,
+        0xe3069283,
+    ),
+    params(
+        "CRC-32/BZIP2",
+        32,
+        0x04c11db7,
+        0xffffffff,
+        false,
+        false,
+        0xffffffff,
+        0xfc891918,
+    ),
+    params(
+        "CRC-32/MPEG-2",
+        32,
+        0x04c11db7,
+        0xffffffff,
+        false,
+        false,
+        0x00000000,
+        0x0376e6e7,
+    ),
+    params(
+        "CRC-32/CKSUM",
+        32,
+        0x04c11db7,
+        0x00000000,
+        false,
+        false,
+        0xffffffff,
+        0x765e7680,
+    ),
+    params(
+        "CRC-32/JAMCRC",
+        32,
+        0x04c11db7,
+        0xffffffff,
+        true,
+        true,
+        0x00000000,
+        0x340bc6d9,
+    ),
+    params(
+        "CRC-32/AUTOSAR",
+        32,
+        0xf4acfb13,
+        0xffffffff,
+        true,
+        true,
+        0xffffffff,
+        0x1697d06a,
+    ),
+    params(
+        "CRC-32/BASE91-D",
+        32,
+        0xa833982b,
+        0xffffffff,
+        true,
+        true,
+        0xffffffff,
+        0x87315576,
+    ),
+    params(
+        "CRC-32/XFER",
+        32,
+        0x000000af,
+        0x00000000,
+        false,
+        false,
+        0x00000000,
+        0xbd0be338,
+    ),
+    params(
+        "CRC-64/XZ",
+        64,
+        0x42f0e1eba9ea3693,
+        0xffffffffffffffff,
+        true,
+        true,
+        0xffffffffffffffff,
+        0x995dc9bbdf1939fa,
+    ),
+    params(
+        "CRC-64/ECMA-182",
+        64,
+        0x42f0e1eba9ea3693,
+        0,
+        false,
+        false,
+        0,
+        0x6c40df5f0b497347,
+    ),
+    params(
+        "CRC-64/GO-ISO",
+        64,
+        0x000000000000001b,
+        0xffffffffffffffff,
+        true,
+        true,
+        0xffffffffffffffff,
+        0xb90956c775a41001,
+    ),
+    params(
+        "CRC-64/WE",
+        64,
+        0x42f0e1eba9ea3693,
+        0xffffffffffffffff,
+        false,
+        false,
+        0xffffffffffffffff,
+        0x62ec59e3f1a4f00a,
+    ),
+    params(
+        "CRC-64/REDIS",
+        64,
+        0xad93d23594c935a9,
+        0,
+        true,
+        true,
+        0,
+        0xe9c6d914c4b8d9ca,
+    ),
+    params(
+        "CRC-64/NVME",
+        64,
+        0xad93d23594c93659,
+        0xffffffffffffffff,
+        true,
+        true,
+        0xffffffffffffffff,
+        0xae8b14860a799888,
+    ),
+];
+
+impl Default for CrcParams {
+    fn default() -> Self {
+        CRC32_ISO_HDLC
+    }
+}
+
+impl CrcParams {
+    /// Build parameters from user-entered values, with hex numbers
+    pub fn custom(
+        width: &str,
+        poly: &str,
+        init: &str,
+        refin: bool,
+        refout: bool,
+        xorout: &str,
+    ) -> Result<Self, String> {
+        let width = width
+            .trim()
+            .parse::<u8>()
+            .ok()
+            .filter(|width| (1..=64).contains(width))
+            .ok_or_else(|| "Width must be between 1 and 64".to_string())?;
+
+        let mut params = CrcParams {
+            name: "Custom",
+            width,
+            poly: parse_hex_param("Poly", poly, width)?,
+            init: parse_hex_param("Init", init, width)?,
+            refin,
+            refout,
+            xorout: parse_hex_param("XorOut", xorout, width)?,
+            check: 0,
+        };
+        params.check = crc(&params, CHECK_INPUT);
+        Ok(params)
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        CRC_CATALOGUE.iter().find(|crc| crc.name == name).copied()
+    }
+
+    fn mask(&self) -> u64 {
+        u64::MAX >> (64 - u32::from(self.width))
+    }
+}
+
+fn parse_hex_param(label: &str, value: &str, width: u8) -> Result<u64, String> {
+    let value = value.trim();
+    let digits = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(value);
+    let parsed = u64::from_str_radix(if digits.is_empty() { "0" } else { digits }, 16)
+        .map_err(|e| format!("{}: {}", label, e))?;
+    if width < 64 && parsed >> width != 0 {
+        return Err(format!("{} is wider than {} bits", label, width));
+    }
+    Ok(parsed)
+}
+
+/// Compute a CRC bit by bit, which supports any width at the cost of speed
+pub fn crc(params: &CrcParams, data: &[u8]) -> u64 {
+    let mask = params.mask();
+    let top = 1u64 << (params.width - 1);
+    let mut register = params.init & mask;
+
+    for &byte in data {
+        let byte = if params.refin {
+            byte.reverse_bits()
+        } else {
+            byte
+        };
+        for i in (0..8).rev() {
+            let bit = (byte >> i) & 1 == 1;
+            let msb = register & top != 0;
+            register = (register << 1) & mask;
+            if bit != msb {
+                register ^= params.poly;
+            }
+        }
+    }
+
+    if params.refout {
+        register = register.reverse_bits() >> (64 - u32::from(params.width));
+    }
+    (register ^ params.xorout) & mask
+}
+
+/// Adler-32, as used by zlib
+pub fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in data {
+        a = (a + u32::from(byte)) % MOD;
+        b = (b + a) % MOD;
+    }
+    (b << 16) | a
+}
+
+/// Fletcher-16 over bytes
+pub fn fletcher16(data: &[u8]) -> u16 {
+    let (mut a, mut b) = (0u16, 0u16);
+    for &byte in data {
+        a = (a + u16::from(byte)) % 255;
+        b = (b + a) % 255;
+    }
+    (b << 8) | a
+}
+
+/// Fletcher-32 over little-endian 16-bit words, zero-padding an odd final byte
+pub fn fletcher32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (0u32, 0u32);
+    for word in data.chunks(2) {
+        let word = u16::from_le_bytes([word[0], word.get(1).copied().unwrap_or(0)]);
+        a = (a + u32::from(word)) % 65535;
+        b = (b + a) % 65535;
+    }
+    (b << 16) | a
+}
+
+/// Supported checksums
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum ChecksumAlgorithm {
+    Crc(CrcParams),
+    Adler32,
+    Fletcher16,
+    Fletcher32,
+}
+
+impl ChecksumAlgorithm {
+    /// Compute the checksum of `data` as big-endian bytes
+    pub fn checksum(&self, data: &[u8]) -> Vec<u8> {
+        match self {
+            ChecksumAlgorithm::Crc(params) => {
+                let bytes = usize::from(params.width).div_ceil(8);
+                crc(params, data).to_be_bytes()[8 - bytes..].to_vec()
+            }
+            ChecksumAlgorithm::Adler32 => adler32(data).to_be_bytes().to_vec(),
+            ChecksumAlgorithm::Fletcher16 => fletcher16(data).to_be_bytes().to_vec(),
+            ChecksumAlgorithm::Fletcher32 => fletcher32(data).to_be_bytes().to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn catalogue_check_values() {
+        for params in CRC_CATALOGUE {
+            assert_eq!(
+                crc(params, CHECK_INPUT),
+                params.check,
+                "{} check value",
+                params.name
+            );
+        }
+    }
+
+    #[test]
+    fn default_is_crc32() {
+        assert_eq!(CrcParams::default().name, "CRC-32/ISO-HDLC");
+        assert_eq!(crc(&CrcParams::default(), CHECK_INPUT), 0xcbf43926);
+    }
+}
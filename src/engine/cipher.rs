@@ -0,0 +1,218 @@
+//! Symmetric ciphers: AES in the common modes, and ChaCha20-Poly1305
+
+use aes::cipher::block_padding::Pkcs7;
+use aes::cipher::consts::{U12, U16};
+use aes::cipher::{
+    BlockCipher, BlockDecrypt, BlockDecryptMut, BlockEncrypt, BlockEncryptMut, KeyInit, KeyIvInit,
+    StreamCipher,
+};
+use aes::{Aes128, Aes192, Aes256};
+use aes_gcm::aead::{Aead, AeadCore, Nonce, Payload};
+use aes_gcm::AesGcm;
+use chacha20poly1305::{ChaCha20Poly1305, XChaCha20Poly1305};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum CipherDirection {
+    #[default]
+    Encrypt,
+    Decrypt,
+}
+
+/// Supported ciphers. AES-128, -192 or -256 is chosen by the key length.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum CipherAlgorithm {
+    #[default]
+    AesGcm,
+    /// With PKCS#7 padding
+    AesCbc,
+    AesCtr,
+    /// With PKCS#7 padding
+    AesEcb,
+    ChaCha20Poly1305,
+    XChaCha20Poly1305,
+}
+
+impl CipherAlgorithm {
+    pub const ALL: [CipherAlgorithm; 6] = [
+        CipherAlgorithm::AesGcm,
+        CipherAlgorithm::AesCbc,
+        CipherAlgorithm::AesCtr,
+        CipherAlgorithm::AesEcb,
+        CipherAlgorithm::ChaCha20Poly1305,
+        CipherAlgorithm::XChaCha20Poly1305,
+    ];
+
+    /// Stable identifier, used for form values
+    pub fn id(&self) -> &'static str {
+        match self {
+            CipherAlgorithm::AesGcm => "aes-gcm",
+            CipherAlgorithm::AesCbc => "aes-cbc",
+            CipherAlgorithm::AesCtr => "aes-ctr",
+            CipherAlgorithm::AesEcb => "aes-ecb",
+            CipherAlgorithm::ChaCha20Poly1305 => "chacha20-poly1305",
+            CipherAlgorithm::XChaCha20Poly1305 => "xchacha20-poly1305",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|algorithm| algorithm.id() == id)
+    }
+
+    /// Human readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            CipherAlgorithm::AesGcm => "AES-GCM",
+            CipherAlgorithm::AesCbc => "AES-CBC",
+            CipherAlgorithm::AesCtr => "AES-CTR",
+            CipherAlgorithm::AesEcb => "AES-ECB",
+            CipherAlgorithm::ChaCha20Poly1305 => "ChaCha20-Poly1305",
+            CipherAlgorithm::XChaCha20Poly1305 => "XChaCha20-Poly1305",
+        }
+    }
+
+    /// Accepted key lengths in bytes
+    pub fn key_sizes(&self) -> &'static [usize] {
+        match self {
+            CipherAlgorithm::ChaCha20Poly1305 | CipherAlgorithm::XChaCha20Poly1305 => &[32],
+            _ => &[16, 24, 32],
+        }
+    }
+
+    /// Length of the IV or nonce in bytes, or `None` if the mode has none
+    pub fn nonce_size(&self) -> Option<usize> {
+        match self {
+            CipherAlgorithm::AesGcm | CipherAlgorithm::ChaCha20Poly1305 => Some(12),
+            CipherAlgorithm::AesCbc | CipherAlgorithm::AesCtr => Some(16),
+            CipherAlgorithm::AesEcb => None,
+            CipherAlgorithm::XChaCha20Poly1305 => Some(24),
+        }
+    }
+
+    /// Authenticated modes, which take additional data and append a 16 byte tag
+    pub fn is_aead(&self) -> bool {
+        matches!(
+            self,
+            CipherAlgorithm::AesGcm
+                | CipherAlgorithm::ChaCha20Poly1305
+                | CipherAlgorithm::XChaCha20Poly1305
+        )
+    }
+
+    /// Encrypt or decrypt `input`. `aad` is ignored by unauthenticated modes.
+    pub fn apply(
+        &self,
+        direction: CipherDirection,
+        key: &[u8],
+        nonce: &[u8],
+        aad: &[u8],
+        input: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        if let Some(size) = self.nonce_size() {
+            if nonce.len() != size {
+                return Err(format!("{} nonce must be {} bytes", self.name(), size));
+            }
+        }
+
+        match self {
+            CipherAlgorithm::AesGcm => aes(AesMode::Gcm, direction, key, nonce, aad, input),
+            CipherAlgorithm::AesCbc => aes(AesMode::Cbc, direction, key, nonce, aad, input),
+            CipherAlgorithm::AesCtr => aes(AesMode::Ctr, direction, key, nonce, aad, input),
+            CipherAlgorithm::AesEcb => aes(AesMode::Ecb, direction, key, nonce, aad, input),
+            CipherAlgorithm::ChaCha20Poly1305 => {
+                aead::<ChaCha20Poly1305>(direction, key, nonce, aad, input)
+            }
+            CipherAlgorithm::XChaCha20Poly1305 => {
+                aead::<XChaCha20Poly1305>(direction, key, nonce, aad, input)
+            }
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum AesMode {
+    Gcm,
+    Cbc,
+    Ctr,
+    Ecb,
+}
+
+fn aes(
+    mode: AesMode,
+    direction: CipherDirection,
+    key: &[u8],
+    iv: &[u8],
+    aad: &[u8],
+    input: &[u8],
+) -> Result<Vec<u8>, String> {
+    match key.len() {
+        16 => aes_mode::<Aes128>(mode, direction, key, iv, aad, input),
+        24 => aes_mode::<Aes192>(mode, direction, key, iv, aad, input),
+        32 => aes_mode::<Aes256>(mode, direction, key, iv, aad, input),
+        _ => Err("AES key must be 16, 24 or 32 bytes".to_string()),
+    }
+}
+
+fn aes_mode<C>(
+    mode: AesMode,
+    direction: CipherDirection,
+    key: &[u8],
+    iv: &[u8],
+    aad: &[u8],
+    input: &[u8],
+) -> Result<Vec<u8>, String>
+where
+    C: BlockCipher<BlockSize = U16> + BlockEncrypt + BlockDecrypt + KeyInit,
+{
+    let padding_error = |_| "Invalid padding: wrong key or IV".to_string();
+    match (mode, direction) {
+        (AesMode::Gcm, _) => aead::<AesGcm<C, U12>>(direction, key, iv, aad, input),
+        (AesMode::Cbc, CipherDirection::Encrypt) => {
+            Ok(new_cipher::<cbc::Encryptor<C>>(key, iv)?.encrypt_padded_vec_mut::<Pkcs7>(input))
+        }
+        (AesMode::Cbc, CipherDirection::Decrypt) => new_cipher::<cbc::Decryptor<C>>(key, iv)?
+            .decrypt_padded_vec_mut::<Pkcs7>(input)
+            .map_err(padding_error),
+        (AesMode::Ctr, _) => {
+            let mut output = input.to_vec();
+            new_cipher::<ctr::Ctr128BE<C>>(key, iv)?.apply_keystream(&mut output);
+            Ok(output)
+        }
+        (AesMode::Ecb, CipherDirection::Encrypt) => {
+            let cipher = ecb::Encryptor::<C>::new_from_slice(key).map_err(|e| e.to_string())?;
+            Ok(cipher.encrypt_padded_vec_mut::<Pkcs7>(input))
+        }
+        (AesMode::Ecb, CipherDirection::Decrypt) => {
+            let cipher = ecb::Decryptor::<C>::new_from_slice(key).map_err(|e| e.to_string())?;
+            cipher
+                .decrypt_padded_vec_mut::<Pkcs7>(input)
+                .map_err(padding_error)
+        }
+    }
+}
+
+fn new_cipher<C: KeyIvInit>(key: &[u8], iv: &[u8]) -> Result<C, String> {
+    C::new_from_slices(key, iv).map_err(|_| "Invalid key or IV length".to_string())
+}
+
+/// The ciphertext has the authentication tag appended
+fn aead<C: Aead + AeadCore + KeyInit>(
+    direction: CipherDirection,
+    key: &[u8],
+    nonce: &[u8],
+    aad: &[u8],
+    input: &[u8],
+) -> Result<Vec<u8>, String> {
+    let cipher =
+        C::new_from_slice(key).map_err(|_| format!("Key must be {} bytes", C::key_size()))?;
+    let nonce = Nonce::<C>::from_slice(nonce);
+    let payload = Payload { msg: input, aad };
+    match direction {
+        CipherDirection::Encrypt => cipher
+            .encrypt(nonce, payload)
+            .map_err(|_| "Encryption failed".to_string()),
+        CipherDirection::Decrypt => cipher.decrypt(nonce, payload).map_err(|_| {
+            "Authentication failed: wrong key, nonce or AAD, or modified ciphertext".to_string()
+        }),
+    }
+}
@@ -0,0 +1,482 @@
+//! Unix crypt(3) password hashes: DES, MD5-crypt (and Apache's apr1 variant)
+//! and the SHA-256/SHA-512 schemes used in `/etc/shadow`.
+
+use md5::Md5;
+use sha2::{Digest, Sha256, Sha512};
+
+/// Alphabet of the crypt base-64 encoding, which differs from standard Base64
+pub const CRYPT_ALPHABET: &[u8; 64] =
+    b"./0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+const SHA_CRYPT_DEFAULT_ROUNDS: u32 = 5000;
+const SHA_CRYPT_ROUNDS: std::ops::RangeInclusive<u32> = 1000..=999_999_999;
+
+/// Recompute a crypt(3) hash from a password and a full hash or setting string,
+/// such as `$6$rounds=10000$salt` or a 2-character DES salt.
+pub fn crypt(password: &[u8], setting: &str) -> Result<String, String> {
+    if let Some(rest) = setting.strip_prefix("$1$") {
+        Ok(md5_crypt(password, salt_field(rest), "$1$"))
+    } else if let Some(rest) = setting.strip_prefix("$apr1$") {
+        Ok(md5_crypt(password, salt_field(rest), "$apr1$"))
+    } else if let Some(rest) = setting.strip_prefix("$5$") {
+        let (rounds, salt) = split_rounds(rest)?;
+        Ok(sha256_crypt(password, salt, rounds))
+    } else if let Some(rest) = setting.strip_prefix("$6$") {
+        let (rounds, salt) = split_rounds(rest)?;
+        Ok(sha512_crypt(password, salt, rounds))
+    } else {
+        des_crypt(password, setting.get(..2).unwrap_or(setting))
+    }
+}
+
+fn salt_field(rest: &str) -> &str {
+    rest.split('$').next().unwrap_or_default()
+}
+
+/// Split an optional `rounds=N$` prefix from the salt
+fn split_rounds(rest: &str) -> Result<(Option<u32>, &str), String> {
+    match rest.strip_prefix("rounds=") {
+        Some(rest) => {
+            let (rounds, rest) = rest.split_once('$').ok_or("Missing salt")?;
+            let rounds = rounds
+                .parse::<u32>()
+                .map_err(|_| "Rounds must be a number".to_string())?;
+            Ok((Some(rounds), salt_field(rest)))
+        }
+        None => Ok((None, salt_field(rest))),
+    }
+}
+
+/// MD5-crypt, with `magic` being `$1$` or Apache's `$apr1$`
+pub fn md5_crypt(password: &[u8], salt: &str, magic: &str) -> String {
+    let salt = &salt.as_bytes()[..salt.len().min(8)];
+
+    let alternate = Md5::new()
+        .chain_update(password)
+        .chain_update(salt)
+        .chain_update(password)
+        .finalize();
+
+    let mut hasher = Md5::new()
+        .chain_update(password)
+        .chain_update(magic)
+        .chain_update(salt);
+    for chunk in password.chunks(16) {
+        hasher.update(&alternate[..chunk.len()]);
+    }
+    let mut length = password.len();
+    while length > 0 {
+        if length & 1 == 1 {
+            hasher.update([0]);
+        } else {
+            hasher.update(&password[..1]);
+        }
+        length >>= 1;
+    }
+    let mut digest = hasher.finalize();
+
+    for round in 0..1000 {
+        let mut hasher = Md5::new();
+        if round & 1 == 1 {
+            hasher.update(password);
+        } else {
+            hasher.update(digest);
+        }
+        if round % 3 != 0 {
+            hasher.update(salt);
+        }
+        if round % 7 != 0 {
+            hasher.update(password);
+        }
+        if round & 1 == 1 {
+            hasher.update(digest);
+        } else {
+            hasher.update(password);
+        }
+        digest = hasher.finalize();
+    }
+
+    const ORDER: [(usize, usize, usize); 5] =
+        [(0, 6, 12), (1, 7, 13), (2, 8, 14), (3, 9, 15), (4, 10, 5)];
+    let mut encoded = String::new();
+    for (a, b, c) in ORDER {
+        encode_triple(&mut encoded, digest[a], digest[b], digest[c], 4);
+    }
+    encode_triple(&mut encoded, 0, 0, digest[11], 2);
+
+    format!("{}{}${}", magic, String::from_utf8_lossy(salt), encoded)
+}
+
+/// SHA-256-crypt (`$5$`)
+pub fn sha256_crypt(password: &[u8], salt: &str, rounds: Option<u32>) -> String {
+    const ORDER: [(usize, usize, usize); 10] = [
+        (0, 10, 20),
+        (21, 1, 11),
+        (12, 22, 2),
+        (3, 13, 23),
+        (24, 4, 14),
+        (15, 25, 5),
+        (6, 16, 26),
+        (27, 7, 17),
+        (18, 28, 8),
+        (9, 19, 29),
+    ];
+    let (setting, digest) = sha_crypt::<Sha256>(password, salt, rounds, "$5$");
+    let mut encoded = String::new();
+    for (a, b, c) in ORDER {
+        encode_triple(&mut encoded, digest[a], digest[b], digest[c], 4);
+    }
+    encode_triple(&mut encoded, 0, digest[31], digest[30], 3);
+    format!("{}${}", setting, encoded)
+}
+
+/// SHA-512-crypt (`$6$`)
+pub fn sha512_crypt(password: &[u8], salt: &str, rounds: Option<u32>) -> String {
+    let (setting, digest) = sha_crypt::<Sha512>(password, salt, rounds, "$6$");
+    let mut encoded = String::new();
+    for i in 0..21 {
+        let (a, b, c) = (i, i + 21, i + 42);
+        // Each group rotates which of the three thirds supplies the high byte
+        let (high, middle, low) = match i % 3 {
+            0 => (a, b, c),
+            1 => (b, c, a),
+            _ => (c, a, b),
+        };
+        encode_triple(&mut encoded, digest[high], digest[middle], digest[low], 4);
+    }
+    encode_triple(&mut encoded, 0, 0, digest[63], 2);
+    format!("{}${}", setting, encoded)
+}
+
+/// The SHA-crypt algorithm shared by `$5$` and `$6$`, returning the setting
+/// string (magic, rounds and salt) and the raw digest
+fn sha_crypt<D: Digest>(
+    password: &[u8],
+    salt: &str,
+    rounds: Option<u32>,
+    magic: &str,
+) -> (String, Vec<u8>) {
+    let salt = &salt.as_bytes()[..salt.len().min(16)];
+    let rounds =
+        rounds.map(|rounds| rounds.clamp(*SHA_CRYPT_ROUNDS.start(), *SHA_CRYPT_ROUNDS.end()));
+    let size = <D as Digest>::output_size();
+
+    let alternate = D::new()
+        .chain_update(password)
+        .chain_update(salt)
+        .chain_update(password)
+        .finalize();
+
+    let mut hasher = D::new().chain_update(password).chain_update(salt);
+    for chunk in password.chunks(size) {
+        hasher.update(&alternate[..chunk.len()]);
+    }
+    let mut length = password.len();
+    while length > 0 {
+        if length & 1 == 1 {
+            hasher.update(&alternate);
+        } else {
+            hasher.update(password);
+        }
+        length >>= 1;
+    }
+    let mut digest = hasher.finalize().to_vec();
+
+    let mut hasher = D::new();
+    for _ in 0..password.len() {
+        hasher.update(password);
+    }
+    let password_digest = hasher.finalize();
+    let p_bytes: Vec<u8> = password_digest
+        .iter()
+        .copied()
+        .cycle()
+        .take(password.len())
+        .collect();
+
+    let mut hasher = D::new();
+    for _ in 0..16 + usize::from(digest[0]) {
+        hasher.update(salt);
+    }
+    let salt_digest = hasher.finalize();
+    let s_bytes = &salt_digest[..salt.len()];
+
+    for round in 0..rounds.unwrap_or(SHA_CRYPT_DEFAULT_ROUNDS) {
+        let mut hasher = D::new();
+        if round & 1 == 1 {
+            hasher.update(&p_bytes);
+        } else {
+            hasher.update(&digest);
+        }
+        if round % 3 != 0 {
+            hasher.update(s_bytes);
+        }
+        if round % 7 != 0 {
+            hasher.update(&p_bytes);
+        }
+        if round & 1 == 1 {
+            hasher.update(&digest);
+        } else {
+            hasher.update(&p_bytes);
+        }
+        digest = hasher.finalize().to_vec();
+    }
+
+    let setting = match rounds {
+        Some(rounds) => format!(
+            "{}rounds={}${}",
+            magic,
+            rounds,
+            String::from_utf8_lossy(salt)
+        ),
+        None => format!("{}{}", magic, String::from_utf8_lossy(salt)),
+    };
+    (setting, digest)
+}
+
+/// Append the low `chars` 6-bit groups of a 24-bit value, least significant first
+fn encode_triple(out: &mut String, high: u8, middle: u8, low: u8, chars: usize) {
+    let mut value = (u32::from(high) << 16) | (u32::from(middle) << 8) | u32::from(low);
+    for _ in 0..chars {
+        out.push(CRYPT_ALPHABET[(value & 0x3f) as usize] as char);
+        value >>= 6;
+    }
+}
+
+fn crypt_char_value(c: u8) -> Option<u8> {
+    CRYPT_ALPHABET
+        .iter()
+        .position(|&a| a == c)
+        .map(|position| position as u8)
+}
+
+/// Traditional DES crypt. Only the first 8 characters of the password are used.
+pub fn des_crypt(password: &[u8], salt: &str) -> Result<String, String> {
+    let salt_values = salt
+        .bytes()
+        .map(crypt_char_value)
+        .collect::<Option<Vec<_>>>()
+        .filter(|values| values.len() == 2)
+        .ok_or("DES salt must be 2 characters from [./0-9A-Za-z]")?;
+
+    let key = password
+        .iter()
+        .take(8)
+        .enumerate()
+        .fold(0u64, |key, (i, &byte)| {
+            key | (u64::from(byte << 1) << (56 - 8 * i))
+        });
+    let subkeys = des_key_schedule(key);
+
+    // Each salt bit swaps a pair of E-box outputs, 24 positions apart
+    let salt_bits = u32::from(salt_values[0]) | (u32::from(salt_values[1]) << 6);
+    let salt_mask = (0..12)
+        .filter(|i| salt_bits >> i & 1 == 1)
+        .fold(0u64, |mask, i| mask | (1 << (23 - i)));
+
+    let block = (0..25).fold(0u64, |block, _| des_encrypt(block, &subkeys, salt_mask));
+
+    let value = u128::from(block) << 2;
+    let encoded: String = (0..11)
+        .map(|i| CRYPT_ALPHABET[((value >> (60 - 6 * i)) & 0x3f) as usize] as char)
+        .collect();
+    Ok(format!("{}{}", salt, encoded))
+}
+
+/// Single DES block encryption, with the parity bits of `key` ignored
+pub(super) fn des_encrypt_block(key: [u8; 8], block: [u8; 8]) -> [u8; 8] {
+    let subkeys = des_key_schedule(u64::from_be_bytes(key));
+    des_encrypt(u64::from_be_bytes(block), &subkeys, 0).to_be_bytes()
+}
+
+const DES_PC1: [u8; 56] = [
+    57, 49, 41, 33, 25, 17, 9, 1, 58, 50, 42, 34, 26, 18, 10, 2, 59, 51, 43, 35, 27, 19, 11, 3, 60,
+    52, 44, 36, 63, 55, 47, 39, 31, 23, 15, 7, 62, 54, 46, 38, 30, 22, 14, 6, 61, 53, 45, 37, 29,
+    21, 13, 5, 28, 20, 12, 4,
+];
+
+const DES_PC2: [u8; 48] = [
+    14, 17, 11, 24, 1, 5, 3, 28, 15, 6, 21, 10, 23, 19, 12, 4, 26, 8, 16, 7, 27, 20, 13, 2, 41, 52,
+    31, 37, 47, 55, 30, 40, 51, 45, 33, 48, 44, 49, 39, 56, 34, 53, 46, 42, 50, 36, 29, 32,
+];
+
+const DES_SHIFTS: [u32; 16] = [1, 1, 2, 2, 2, 2, 2, 2, 1, 2, 2, 2, 2, 2, 2, 1];
+
+const DES_IP: [u8; 64] = [
+    58, 50, 42, 34, 26, 18, 10, 2, 60, 52, 44, 36, 28, 20, 12, 4, 62, 54, 46, 38, 30, 22, 14, 6,
+    64, 56, 48, 40, 32, 24, 16, 8, 57, 49, 41, 33, 25, 17, 9, 1, 59, 51, 43, 35, 27, 19, 11, 3, 61,
+    53, 45, 37, 29, 21, 13, 5, 63, 55, 47, 39, 31, 23, 15, 7,
+];
+
+const DES_FP: [u8; 64] = [
+    40, 8, 48, 16, 56, 24, 64, 32, 39, 7, 47, 15, 55, 23, 63, 31, 38, 6, 46, 14, 54, 22, 62, 30,
+    37, 5, 45, 13, 53, 21, 61, 29, 36, 4, 44, 12, 52, 20, 60, 28, 35, 3, 43, 11, 51, 19, 59, 27,
+    34, 2, 42, 10, 50, 18, 58, 26, 33, 1, 41, 9, 49, 17, 57, 25,
+];
+
+const DES_E: [u8; 48] = [
+    32, 1, 2, 3, 4, 5, 4, 5, 6, 7, 8, 9, 8, 9, 10, 11, 12, 13, 12, 13, 14, 15, 16, 17, 16, 17, 18,
+    19, 20, 21, 20, 21, 22, 23, 24, 25, 24, 25, 26, 27, 28, 29, 28, 29, 30, 31, 32, 1,
+];
+
+const DES_P: [u8; 32] = [
+    16, 7, 20, 21, 29, 12, 28, 17, 1, 15, 23, 26, 5, 18, 31, 10, 2, 8, 24, 14, 32, 27, 3, 9, 19,
+    13, 30, 6, 22, 11, 4, 25,
+];
+
+const DES_SBOXES: [[u8; 64]; 8] = [
+    [
+        14, 4, 13, 1, 2, 15, 11, 8, 3, 10, 6, 12, 5, 9, 0, 7, 0, 15, 7, 4, 14, 2, 13, 1, 10, 6, 12,
+        11, 9, 5, 3, 8, 4, 1, 14, 8, 13, 6, 2, 11, 15, 12, 9, 7, 3, 10, 5, 0, 15, 12, 8, 2, 4, 9,
+        1, 7, 5, 11, 3, 14, 10, 0, 6, 13,
+    ],
+    [
+        15, 1, 8, 14, 6, 11, 3, 4, 9, 7, 2, 13, 12, 0, 5, 10, 3, 13, 4, 7, 15, 2, 8, 14, 12, 0, 1,
+        10, 6, 9, 11, 5, 0, 14, 7, 11, 10, 4, 13, 1, 5, 8, 12, 6, 9, 3, 2, 15, 13, 8, 10, 1, 3, 15,
+        4, 2, 11, 6, 7, 12, 0, 5, 14, 9,
+    ],
+    [
+        10, 0, 9, 14, 6, 3, 15, 5, 1, 13, 12, 7, 11, 4, 2, 8, 13, 7, 0, 9, 3, 4, 6, 10, 2, 8, 5,
+        14, 12, 11, 15, 1, 13, 6, 4, 9, 8, 15, 3, 0, 11, 1, 2, 12, 5, 10, 14, 7, 1, 10, 13, 0, 6,
+        9, 8, 7, 4, 15, 14, 3, 11, 5, 2, 12,
+    ],
+    [
+        7, 13, 14, 3, 0, 6, 9, 10, 1, 2, 8, 5, 11, 12, 4, 15, 13, 8, 11, 5, 6, 15, 0, 3, 4, 7, 2,
+        12, 1, 10, 14, 9, 10, 6, 9, 0, 12, 11, 7, 13, 15, 1, 3, 14, 5, 2, 8, 4, 3, 15, 0, 6, 10, 1,
+        13, 8, 9, 4, 5, 11, 12, 7, 2, 14,
+    ],
+    [
+        2, 12, 4, 1, 7, 10, 11, 6, 8, 5, 3, 15, 13, 0, 14, 9, 14, 11, 2, 12, 4, 7, 13, 1, 5, 0, 15,
+        10, 3, 9, 8, 6, 4, 2, 1, 11, 10, 13, 7, 8, 15, 9, 12, 5, 6, 3, 0, 14, 11, 8, 12, 7, 1, 14,
+        2, 13, 6, 15, 0, 9, 10, 4, 5, 3,
+    ],
+    [
+        12, 1, 10, 15, 9, 2, 6, 8, 0, 13, 3, 4, 14, 7, 5, 11, 10, 15, 4, 2, 7, 12, 9, 5, 6, 1, 13,
+        14, 0, 11, 3, 8, 9, 14, 15, 5, 2, 8, 12, 3, 7, 0, 4, 10, 1, 13, 11, 6, 4, 3, 2, 12, 9, 5,
+        15, 10, 11, 14, 1, 7, 6, 0, 8, 13,
+    ],
+    [
+        4, 11, 2, 14, 15, 0, 8, 13, 3, 12, 9, 7, 5, 10, 6, 1, 13, 0, 11, 7, 4, 9, 1, 10, 14, 3, 5,
+        12, 2, 15, 8, 6, 1, 4, 11, 13, 12, 3, 7, 14, 10, 15, 6, 8, 0, 5, 9, 2, 6, 11, 13, 8, 1, 4,
+        10, 7, 9, 5, 0, 15, 14, 2, 3, 12,
+    ],
+    [
+        13, 2, 8, 4, 6, 15, 11, 1, 10, 9, 3, 14, 5, 0, 12, 7, 1, 15, 13, 8, 10, 3, 7, 4, 12, 5, 6,
+        11, 0, 14, 9, 2, 7, 11, 4, 1, 9, 12, 14, 2, 0, 6, 10, 13, 15, 3, 5, 8, 2, 1, 14, 7, 4, 10,
+        8, 13, 15, 12, 9, 0, 3, 5, 6, 11,
+    ],
+];
+
+/// Apply a DES permutation table, whose entries are 1-based bit positions
+/// counted from the most significant of `width` input bits
+fn permute(input: u64, width: u32, table: &[u8]) -> u64 {
+    table.iter().fold(0, |out, &position| {
+        (out << 1) | ((input >> (width - u32::from(position))) & 1)
+    })
+}
+
+fn des_key_schedule(key: u64) -> [u64; 16] {
+    const MASK: u64 = 0x0fff_ffff;
+    let key = permute(key, 64, &DES_PC1);
+    let (mut c, mut d) = (key >> 28, key & MASK);
+    let mut subkeys = [0; 16];
+    for (subkey, shift) in subkeys.iter_mut().zip(DES_SHIFTS) {
+        c = ((c << shift) | (c >> (28 - shift))) & MASK;
+        d = ((d << shift) | (d >> (28 - shift))) & MASK;
+        *subkey = permute((c << 28) | d, 56, &DES_PC2);
+    }
+    subkeys
+}
+
+fn des_feistel(half: u64, subkey: u64, salt_mask: u64) -> u64 {
+    let mut expanded = permute(half, 32, &DES_E);
+    let swap = ((expanded >> 24) ^ expanded) & salt_mask;
+    expanded ^= swap | (swap << 24);
+
+    let mixed = expanded ^ subkey;
+    let substituted = DES_SBOXES.iter().enumerate().fold(0, |out, (i, sbox)| {
+        let six = (mixed >> (42 - 6 * i)) & 0x3f;
+        let row = ((six >> 4) & 0b10) | (six & 1);
+        let column = (six >> 1) & 0xf;
+        (out << 4) | u64::from(sbox[(row * 16 + column) as usize])
+    });
+    permute(substituted, 32, &DES_P)
+}
+
+fn des_encrypt(block: u64, subkeys: &[u64; 16], salt_mask: u64) -> u64 {
+    let block = permute(block, 64, &DES_IP);
+    let (mut left, mut right) = (block >> 32, block & 0xffff_ffff);
+    for &subkey in subkeys {
+        (left, right) = (right, left ^ des_feistel(right, subkey, salt_mask));
+    }
+    permute((right << 32) | left, 64, &DES_FP)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn md5_crypt_matches_openssl() {
+        assert_eq!(
+            crypt(b"password", "$1$saltsalt").unwrap(),
+            "$1$saltsalt$qjXMvbEw8oaL.CzflDtaK/"
+        );
+        assert_eq!(
+            crypt(b"password", "$apr1$saltsalt").unwrap(),
+            "$apr1$saltsalt$yAAkm4libquA.ZWLHbSBq/"
+        );
+    }
+
+    #[test]
+    fn sha_crypt_matches_glibc() {
+        assert_eq!(
+            crypt(b"Hello world!", "$5$saltstring").unwrap(),
+            "$5$saltstring$5B8vYYiY.CVt1RlTTf8KbXBH3hsxY/GNooZaBBGWEc5"
+        );
+        assert_eq!(
+            crypt(b"Hello world!", "$5$rounds=10000$saltstringsaltstring").unwrap(),
+            "$5$rounds=10000$saltstringsaltst$3xv.VbSHBb41AL9AvLeujZkZRBAwqFMz2.opqey6IcA"
+        );
+        assert_eq!(
+            crypt(b"Hello world!", "$6$saltstring").unwrap(),
+            "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJuesI68u4OTLiBFdcbYEdFCoEOfaS35inz1"
+        );
+        assert_eq!(
+            crypt(b"Hello world!", "$6$rounds=10000$saltstringsaltstring").unwrap(),
+            "$6$rounds=10000$saltstringsaltst$OW1/O6BYHV6BcXZu8QVeXbDWra3Oeqh0sbHbbMCVNSnCM/UrjmM0Dp8vOuZeHBy/YTBmSK6H9qs/y3RnOaw5v."
+        );
+        // Salts are cut to 16 characters
+        assert_eq!(
+            crypt(b"password", "$6$saltsaltsaltsaltsalt").unwrap(),
+            "$6$saltsaltsaltsalt$bcXJ8qxwY5sQ4v8MTl.0B1jeZ0z0JlA9jjmbUoCJZ.1wYXiLTU.q2ILyrDJLm890lyfuF7sWAeli0yjOyFPkf0"
+        );
+    }
+
+    #[test]
+    fn sha_crypt_clamps_rounds() {
+        assert_eq!(
+            crypt(
+                b"the minimum number is still observed",
+                "$5$rounds=10$roundstoolow"
+            )
+            .unwrap(),
+            "$5$rounds=1000$roundstoolow$yfvwcWrQ8l/K0DAWyuPMDNHpIVlTQebY9l/gL972bIC"
+        );
+        assert_eq!(
+            crypt(b"the minimum number is still observed", "$6$rounds=10$roundstoolow").unwrap(),
+            "$6$rounds=1000$roundstoolow$kUMsbe306n21p9R.FRkW3IGn.S9NPN0x50YhH1xhLsPuWGsUSklZt58jaTfF4ZEQpyUNGc0dqbpBYYBaHHrsX."
+        );
+    }
+
+    #[test]
+    fn des_crypt_matches_glibc() {
+        assert_eq!(crypt(b"test", "aa").unwrap(), "aaqPiZY5xR5l.");
+        assert_eq!(crypt(b"password", "aa").unwrap(), "aajfMKNH1hTm2");
+        // Only the first 8 characters count
+        assert_eq!(
+            des_crypt(b"password123", "aa").unwrap(),
+            des_crypt(b"password", "aa").unwrap()
+        );
+    }
+}
@@ -1,94 +1,759 @@
-use blake2::{Blake2b512, Blake2s256};
+use blake2::{Blake2b512, Blake2bMac512, Blake2s256, Blake2sMac256};
 use hmac::{Hmac, Mac};
+use md2::Md2;
+use md4::Md4;
+use md5::Md5;
+use ripemd::{Ripemd160, Ripemd256, Ripemd320};
+use serde::{Deserialize, Serialize};
 use sha1::{Digest, Sha1};
 use sha2::{Sha224, Sha256, Sha384, Sha512};
-use sha3::{Sha3_224, Sha3_256, Sha3_384, Sha3_512};
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::{
+    CShake128, CShake128Core, CShake256, CShake256Core, Keccak224, Keccak256, Keccak384, Keccak512,
+    Sha3_224, Sha3_256, Sha3_384, Sha3_512, Shake128, Shake256,
+};
+use sm3::Sm3;
+use streebog::{Streebog256, Streebog512};
+use subtle::ConstantTimeEq;
+use tiger::Tiger;
+use whirlpool::Whirlpool;
 
-pub fn md5_hash(msg: &str) -> String {
-    hex::encode(md5::compute(msg).to_vec())
+pub fn md2_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Md2>(msg)
 }
 
-pub fn sha1_hash(msg: &str) -> String {
-    let mut hasher = Sha1::new();
-    hasher.update(msg.as_bytes());
-    hex::encode(hasher.finalize().to_vec())
+pub fn md4_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Md4>(msg)
 }
 
-pub fn sha224_hash(msg: &str) -> String {
-    let mut hasher = Sha224::new();
-    hasher.update(msg.as_bytes());
-    hex::encode(hasher.finalize().to_vec())
+pub fn md5_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Md5>(msg)
 }
 
-pub fn sha256_hash(msg: &str) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(msg.as_bytes());
-    hex::encode(hasher.finalize().to_vec())
+pub fn sha1_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Sha1>(msg)
 }
 
-pub fn sha384_hash(msg: &str) -> String {
-    let mut hasher = Sha384::new();
-    hasher.update(msg.as_bytes());
-    hex::encode(hasher.finalize().to_vec())
+pub fn sha224_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Sha224>(msg)
 }
 
-pub fn sha512_hash(msg: &str) -> String {
-    let mut hasher = Sha512::new();
-    hasher.update(msg.as_bytes());
-    hex::encode(hasher.finalize().to_vec())
+pub fn sha256_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Sha256>(msg)
 }
 
-pub fn blake2b512_hash(msg: &str) -> String {
-    let mut hasher = Blake2b512::new();
-    hasher.update(msg.as_bytes());
-    hex::encode(hasher.finalize().to_vec())
+pub fn sha384_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Sha384>(msg)
 }
 
-pub fn blake2s256_hash(msg: &str) -> String {
-    let mut hasher = Blake2s256::new();
-    hasher.update(msg.as_bytes());
-    hex::encode(hasher.finalize().to_vec())
+pub fn sha512_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Sha512>(msg)
 }
 
-pub fn sha3224_hash(msg: &str) -> String {
-    let mut hasher = Sha3_224::new();
-    hasher.update(msg.as_bytes());
-    hex::encode(hasher.finalize().to_vec())
+pub fn blake2b512_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Blake2b512>(msg)
 }
 
-pub fn sha3256_hash(msg: &str) -> String {
-    let mut hasher = Sha3_256::new();
-    hasher.update(msg.as_bytes());
-    hex::encode(hasher.finalize().to_vec())
+pub fn blake2s256_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Blake2s256>(msg)
 }
 
-pub fn sha3384_hash(msg: &str) -> String {
-    let mut hasher = Sha3_384::new();
-    hasher.update(msg.as_bytes());
-    hex::encode(hasher.finalize().to_vec())
+pub fn sha3224_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Sha3_224>(msg)
 }
 
-pub fn sha3512_hash(msg: &str) -> String {
-    let mut hasher = Sha3_512::new();
-    hasher.update(msg.as_bytes());
-    hex::encode(hasher.finalize().to_vec())
+pub fn sha3256_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Sha3_256>(msg)
 }
 
-pub fn hmac_digest_hex(key: &str, msg: &str) -> String {
-    hex::encode(hmac_digest(key, msg))
+pub fn sha3384_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Sha3_384>(msg)
 }
 
-pub fn hmac_digest_b64(key: &str, msg: &str) -> String {
-    base64::encode(hmac_digest(key, msg))
+pub fn sha3512_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Sha3_512>(msg)
 }
 
-fn hmac_digest(key: &str, msg: &str) -> Vec<u8> {
-    type HmacSha256 = Hmac<Sha256>;
+pub fn keccak224_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Keccak224>(msg)
+}
+
+pub fn keccak256_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Keccak256>(msg)
+}
+
+pub fn keccak384_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Keccak384>(msg)
+}
+
+pub fn keccak512_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Keccak512>(msg)
+}
+
+pub fn blake3_hash(msg: &[u8]) -> Vec<u8> {
+    blake3::hash(msg).as_bytes().to_vec()
+}
+
+pub fn ripemd160_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Ripemd160>(msg)
+}
+
+pub fn ripemd256_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Ripemd256>(msg)
+}
+
+pub fn ripemd320_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Ripemd320>(msg)
+}
+
+pub fn whirlpool_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Whirlpool>(msg)
+}
+
+pub fn sm3_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Sm3>(msg)
+}
+
+pub fn streebog256_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Streebog256>(msg)
+}
+
+pub fn streebog512_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Streebog512>(msg)
+}
+
+pub fn tiger_hash(msg: &[u8]) -> Vec<u8> {
+    digest::<Tiger>(msg)
+}
+
+/// BLAKE3 in any of its three modes, with an arbitrary output length
+pub fn blake3_xof(
+    mode: &Blake3Mode,
+    key: &[u8],
+    context: &str,
+    msg: &[u8],
+    length: usize,
+) -> Result<Vec<u8>, String> {
+    let mut hasher = match mode {
+        Blake3Mode::Hash => blake3::Hasher::new(),
+        Blake3Mode::Keyed => {
+            let key: [u8; blake3::KEY_LEN] = key
+                .try_into()
+                .map_err(|_| format!("Key must be {} bytes", blake3::KEY_LEN))?;
+            blake3::Hasher::new_keyed(&key)
+        }
+        Blake3Mode::DeriveKey => blake3::Hasher::new_derive_key(context),
+    };
+    hasher.update(msg);
+
+    let mut output = vec![0; length];
+    hasher.finalize_xof().fill(&mut output);
+    Ok(output)
+}
+
+/// BLAKE2 with its optional parameters, as used by Argon2, libsodium and WireGuard.
+///
+/// Salt and personalization shorter than [`Blake2Variant::salt_size`] are
+/// zero-padded. The parallel variants don't support them.
+pub fn blake2_digest(
+    variant: &Blake2Variant,
+    key: &[u8],
+    salt: &[u8],
+    personal: &[u8],
+    msg: &[u8],
+    length: usize,
+) -> Result<Vec<u8>, String> {
+    if !(1..=variant.output_size()).contains(&length) {
+        return Err(format!(
+            "Output length must be between 1 and {}",
+            variant.output_size()
+        ));
+    }
+    if key.len() > variant.output_size() {
+        return Err(format!(
+            "Key must be at most {} bytes",
+            variant.output_size()
+        ));
+    }
+    let salt_size = variant.salt_size().unwrap_or(0);
+    if salt.len() > salt_size || personal.len() > salt_size {
+        return Err(match salt_size {
+            0 => format!("{} doesn't support salt or personalization", variant.name()),
+            size => format!("Salt and personalization must be at most {} bytes", size),
+        });
+    }
+
+    let hash = match variant {
+        Blake2Variant::Blake2b => blake2b_simd::Params::new()
+            .hash_length(length)
+            .key(key)
+            .salt(salt)
+            .personal(personal)
+            .hash(msg)
+            .as_bytes()
+            .to_vec(),
+        Blake2Variant::Blake2s => blake2s_simd::Params::new()
+            .hash_length(length)
+            .key(key)
+            .salt(salt)
+            .personal(personal)
+            .hash(msg)
+            .as_bytes()
+            .to_vec(),
+        Blake2Variant::Blake2bp => blake2b_simd::blake2bp::Params::new()
+            .hash_length(length)
+            .key(key)
+            .hash(msg)
+            .as_bytes()
+            .to_vec(),
+        Blake2Variant::Blake2sp => blake2s_simd::blake2sp::Params::new()
+            .hash_length(length)
+            .key(key)
+            .hash(msg)
+            .as_bytes()
+            .to_vec(),
+    };
+    Ok(hash)
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum Blake2Variant {
+    #[default]
+    Blake2b,
+    Blake2s,
+    /// 4-way parallel BLAKE2b
+    Blake2bp,
+    /// 8-way parallel BLAKE2s
+    Blake2sp,
+}
+
+impl Blake2Variant {
+    /// Maximum digest length in bytes, which is also the maximum key length
+    pub fn output_size(&self) -> usize {
+        match self {
+            Blake2Variant::Blake2b | Blake2Variant::Blake2bp => 64,
+            Blake2Variant::Blake2s | Blake2Variant::Blake2sp => 32,
+        }
+    }
+
+    /// Size of the salt and personalization fields, if supported
+    pub fn salt_size(&self) -> Option<usize> {
+        match self {
+            Blake2Variant::Blake2b => Some(16),
+            Blake2Variant::Blake2s => Some(8),
+            Blake2Variant::Blake2bp | Blake2Variant::Blake2sp => None,
+        }
+    }
+
+    /// Human readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Blake2Variant::Blake2b => "BLAKE2b",
+            Blake2Variant::Blake2s => "BLAKE2s",
+            Blake2Variant::Blake2bp => "BLAKE2bp",
+            Blake2Variant::Blake2sp => "BLAKE2sp",
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum Blake3Mode {
+    #[default]
+    Hash,
+    /// MAC mode with a 32-byte key
+    Keyed,
+    /// KDF mode, with a context string and the message as key material
+    DeriveKey,
+}
+
+/// SHA-3 derived extendable-output functions (FIPS 202 and SP 800-185)
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum XofAlgorithm {
+    #[default]
+    Shake128,
+    Shake256,
+    CShake128,
+    CShake256,
+    Kmac128,
+    Kmac256,
+}
+
+impl XofAlgorithm {
+    pub const ALL: [XofAlgorithm; 6] = [
+        XofAlgorithm::Shake128,
+        XofAlgorithm::Shake256,
+        XofAlgorithm::CShake128,
+        XofAlgorithm::CShake256,
+        XofAlgorithm::Kmac128,
+        XofAlgorithm::Kmac256,
+    ];
 
-    let mut mac = HmacSha256::new_from_slice(key.as_bytes()).unwrap();
-    mac.update(&msg.as_bytes());
+    /// Stable identifier, used for form values
+    pub fn id(&self) -> &'static str {
+        match self {
+            XofAlgorithm::Shake128 => "shake128",
+            XofAlgorithm::Shake256 => "shake256",
+            XofAlgorithm::CShake128 => "cshake128",
+            XofAlgorithm::CShake256 => "cshake256",
+            XofAlgorithm::Kmac128 => "kmac128",
+            XofAlgorithm::Kmac256 => "kmac256",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|algorithm| algorithm.id() == id)
+    }
+
+    /// Human readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            XofAlgorithm::Shake128 => "SHAKE128",
+            XofAlgorithm::Shake256 => "SHAKE256",
+            XofAlgorithm::CShake128 => "cSHAKE128",
+            XofAlgorithm::CShake256 => "cSHAKE256",
+            XofAlgorithm::Kmac128 => "KMAC128",
+            XofAlgorithm::Kmac256 => "KMAC256",
+        }
+    }
+
+    /// Whether a customization string is mixed into the output
+    pub fn is_customizable(&self) -> bool {
+        !matches!(self, XofAlgorithm::Shake128 | XofAlgorithm::Shake256)
+    }
+
+    pub fn is_keyed(&self) -> bool {
+        matches!(self, XofAlgorithm::Kmac128 | XofAlgorithm::Kmac256)
+    }
+
+    /// Compute `length` bytes of output.
+    ///
+    /// `key` is only used by KMAC and `customization` is ignored by plain SHAKE.
+    pub fn output(&self, key: &[u8], customization: &[u8], msg: &[u8], length: usize) -> Vec<u8> {
+        match self {
+            XofAlgorithm::Shake128 => xof(Shake128::default(), msg, length),
+            XofAlgorithm::Shake256 => xof(Shake256::default(), msg, length),
+            XofAlgorithm::CShake128 => xof(
+                CShake128::from_core(CShake128Core::new(customization)),
+                msg,
+                length,
+            ),
+            XofAlgorithm::CShake256 => xof(
+                CShake256::from_core(CShake256Core::new(customization)),
+                msg,
+                length,
+            ),
+            XofAlgorithm::Kmac128 => xof(
+                CShake128::from_core(CShake128Core::new_with_function_name(
+                    b"KMAC",
+                    customization,
+                )),
+                &kmac_input(168, key, msg, length),
+                length,
+            ),
+            XofAlgorithm::Kmac256 => xof(
+                CShake256::from_core(CShake256Core::new_with_function_name(
+                    b"KMAC",
+                    customization,
+                )),
+                &kmac_input(136, key, msg, length),
+                length,
+            ),
+        }
+    }
+}
+
+/// Build `bytepad(encode_string(K), rate) || X || right_encode(L)` as defined for KMAC
+fn kmac_input(rate: usize, key: &[u8], msg: &[u8], length: usize) -> Vec<u8> {
+    let mut input = left_encode(rate as u64);
+    input.extend(left_encode(key.len() as u64 * 8));
+    input.extend_from_slice(key);
+    input.resize(input.len().div_ceil(rate) * rate, 0);
+    input.extend_from_slice(msg);
+    input.extend(right_encode(length as u64 * 8));
+    input
+}
+
+/// Big-endian bytes of `value` without leading zeros, keeping at least one byte
+fn encode_bytes(value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let skip = (value.leading_zeros() as usize / 8).min(7);
+    bytes[skip..].to_vec()
+}
+
+fn left_encode(value: u64) -> Vec<u8> {
+    let bytes = encode_bytes(value);
+    let mut encoded = vec![bytes.len() as u8];
+    encoded.extend(bytes);
+    encoded
+}
+
+fn right_encode(value: u64) -> Vec<u8> {
+    let mut encoded = encode_bytes(value);
+    encoded.push(encoded.len() as u8);
+    encoded
+}
+
+/// Compute an HMAC of `msg` using the given hash algorithm.
+///
+/// BLAKE2 and BLAKE3 have built-in keyed modes which are used instead of the
+/// HMAC construction, so keys for those are limited to 64 (BLAKE2b) or 32
+/// (BLAKE2s) bytes, or must be exactly 32 bytes (BLAKE3).
+pub fn hmac_digest(algorithm: HashAlgorithm, key: &[u8], msg: &[u8]) -> Result<Vec<u8>, String> {
+    match algorithm {
+        HashAlgorithm::Md2 => mac::<Hmac<Md2>>(key, msg),
+        HashAlgorithm::Md4 => mac::<Hmac<Md4>>(key, msg),
+        HashAlgorithm::Md5 => mac::<Hmac<Md5>>(key, msg),
+        HashAlgorithm::Sha1 => mac::<Hmac<Sha1>>(key, msg),
+        HashAlgorithm::Sha224 => mac::<Hmac<Sha224>>(key, msg),
+        HashAlgorithm::Sha256 => mac::<Hmac<Sha256>>(key, msg),
+        HashAlgorithm::Sha384 => mac::<Hmac<Sha384>>(key, msg),
+        HashAlgorithm::Sha512 => mac::<Hmac<Sha512>>(key, msg),
+        HashAlgorithm::Blake2b512 => mac::<Blake2bMac512>(key, msg),
+        HashAlgorithm::Blake2s256 => mac::<Blake2sMac256>(key, msg),
+        HashAlgorithm::Sha3_224 => mac::<Hmac<Sha3_224>>(key, msg),
+        HashAlgorithm::Sha3_256 => mac::<Hmac<Sha3_256>>(key, msg),
+        HashAlgorithm::Sha3_384 => mac::<Hmac<Sha3_384>>(key, msg),
+        HashAlgorithm::Sha3_512 => mac::<Hmac<Sha3_512>>(key, msg),
+        HashAlgorithm::Keccak224 => mac::<Hmac<Keccak224>>(key, msg),
+        HashAlgorithm::Keccak256 => mac::<Hmac<Keccak256>>(key, msg),
+        HashAlgorithm::Keccak384 => mac::<Hmac<Keccak384>>(key, msg),
+        HashAlgorithm::Keccak512 => mac::<Hmac<Keccak512>>(key, msg),
+        HashAlgorithm::Blake3 => blake3_xof(&Blake3Mode::Keyed, key, "", msg, blake3::OUT_LEN),
+        HashAlgorithm::Ripemd160 => mac::<Hmac<Ripemd160>>(key, msg),
+        HashAlgorithm::Ripemd256 => mac::<Hmac<Ripemd256>>(key, msg),
+        HashAlgorithm::Ripemd320 => mac::<Hmac<Ripemd320>>(key, msg),
+        HashAlgorithm::Whirlpool => mac::<Hmac<Whirlpool>>(key, msg),
+        HashAlgorithm::Sm3 => mac::<Hmac<Sm3>>(key, msg),
+        HashAlgorithm::Streebog256 => mac::<Hmac<Streebog256>>(key, msg),
+        HashAlgorithm::Streebog512 => mac::<Hmac<Streebog512>>(key, msg),
+        HashAlgorithm::Tiger => mac::<Hmac<Tiger>>(key, msg),
+    }
+}
+
+/// PBKDF2 (RFC 8018) with HMAC over the given hash algorithm
+pub fn pbkdf2_digest(
+    algorithm: HashAlgorithm,
+    password: &[u8],
+    salt: &[u8],
+    iterations: u32,
+    length: usize,
+) -> Result<Vec<u8>, String> {
+    if iterations == 0 {
+        return Err("Iterations must be at least 1".to_string());
+    }
+    let derive = match algorithm {
+        HashAlgorithm::Md2 => pbkdf2::pbkdf2::<Hmac<Md2>>,
+        HashAlgorithm::Md4 => pbkdf2::pbkdf2::<Hmac<Md4>>,
+        HashAlgorithm::Md5 => pbkdf2::pbkdf2::<Hmac<Md5>>,
+        HashAlgorithm::Sha1 => pbkdf2::pbkdf2::<Hmac<Sha1>>,
+        HashAlgorithm::Sha224 => pbkdf2::pbkdf2::<Hmac<Sha224>>,
+        HashAlgorithm::Sha256 => pbkdf2::pbkdf2::<Hmac<Sha256>>,
+        HashAlgorithm::Sha384 => pbkdf2::pbkdf2::<Hmac<Sha384>>,
+        HashAlgorithm::Sha512 => pbkdf2::pbkdf2::<Hmac<Sha512>>,
+        HashAlgorithm::Sha3_224 => pbkdf2::pbkdf2::<Hmac<Sha3_224>>,
+        HashAlgorithm::Sha3_256 => pbkdf2::pbkdf2::<Hmac<Sha3_256>>,
+        HashAlgorithm::Sha3_384 => pbkdf2::pbkdf2::<Hmac<Sha3_384>>,
+        HashAlgorithm::Sha3_512 => pbkdf2::pbkdf2::<Hmac<Sha3_512>>,
+        HashAlgorithm::Keccak224 => pbkdf2::pbkdf2::<Hmac<Keccak224>>,
+        HashAlgorithm::Keccak256 => pbkdf2::pbkdf2::<Hmac<Keccak256>>,
+        HashAlgorithm::Keccak384 => pbkdf2::pbkdf2::<Hmac<Keccak384>>,
+        HashAlgorithm::Keccak512 => pbkdf2::pbkdf2::<Hmac<Keccak512>>,
+        HashAlgorithm::Ripemd160 => pbkdf2::pbkdf2::<Hmac<Ripemd160>>,
+        HashAlgorithm::Ripemd256 => pbkdf2::pbkdf2::<Hmac<Ripemd256>>,
+        HashAlgorithm::Ripemd320 => pbkdf2::pbkdf2::<Hmac<Ripemd320>>,
+        HashAlgorithm::Whirlpool => pbkdf2::pbkdf2::<Hmac<Whirlpool>>,
+        HashAlgorithm::Sm3 => pbkdf2::pbkdf2::<Hmac<Sm3>>,
+        HashAlgorithm::Streebog256 => pbkdf2::pbkdf2::<Hmac<Streebog256>>,
+        HashAlgorithm::Streebog512 => pbkdf2::pbkdf2::<Hmac<Streebog512>>,
+        HashAlgorithm::Tiger => pbkdf2::pbkdf2::<Hmac<Tiger>>,
+        HashAlgorithm::Blake2b512 | HashAlgorithm::Blake2s256 | HashAlgorithm::Blake3 => {
+            return Err(hmac_required("PBKDF2", algorithm))
+        }
+    };
+    let mut output = vec![0; length];
+    derive(password, salt, iterations, &mut output)
+        .map_err(|_| "Invalid key length".to_string())?;
+    Ok(output)
+}
+
+/// HKDF-Extract (RFC 5869). An empty salt acts as the all-zero salt the RFC
+/// specifies, since HMAC pads keys with zeros anyway.
+pub fn hkdf_extract(algorithm: HashAlgorithm, salt: &[u8], ikm: &[u8]) -> Result<Vec<u8>, String> {
+    if !algorithm.uses_hmac() {
+        return Err(hmac_required("HKDF", algorithm));
+    }
+    hmac_digest(algorithm, salt, ikm)
+}
+
+/// HKDF-Expand (RFC 5869), up to 255 hash blocks
+pub fn hkdf_expand(
+    algorithm: HashAlgorithm,
+    prk: &[u8],
+    info: &[u8],
+    length: usize,
+) -> Result<Vec<u8>, String> {
+    if !algorithm.uses_hmac() {
+        return Err(hmac_required("HKDF", algorithm));
+    }
+    let max_length = 255 * algorithm.output_size();
+    if length > max_length {
+        return Err(format!(
+            "Output length must be at most {} bytes for {}",
+            max_length,
+            algorithm.name()
+        ));
+    }
+    let mut output = Vec::with_capacity(length);
+    let mut block = Vec::new();
+    let mut counter = 1u8;
+    while output.len() < length {
+        block = hmac_digest(algorithm, prk, &[&block, info, &[counter]].concat())?;
+        output.extend_from_slice(&block);
+        counter = counter.wrapping_add(1);
+    }
+    output.truncate(length);
+    Ok(output)
+}
+
+/// Compare two digests without leaking the position of the first difference
+pub fn digests_match(expected: &[u8], actual: &[u8]) -> bool {
+    expected.ct_eq(actual).into()
+}
+
+/// Supported digest algorithms
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum HashAlgorithm {
+    #[default]
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Blake2b512,
+    Blake2s256,
+    Sha3_224,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    Keccak224,
+    Keccak256,
+    Keccak384,
+    Keccak512,
+    Blake3,
+    Ripemd160,
+    Ripemd256,
+    Ripemd320,
+    Whirlpool,
+    Sm3,
+    Streebog256,
+    Streebog512,
+    Tiger,
+    Md2,
+    Md4,
+}
+
+impl HashAlgorithm {
+    /// Most to least commonly used, which is the order hash identification
+    /// ranks candidates of the same size in
+    pub const ALL: [HashAlgorithm; 27] = [
+        HashAlgorithm::Md5,
+        HashAlgorithm::Sha1,
+        HashAlgorithm::Sha224,
+        HashAlgorithm::Sha256,
+        HashAlgorithm::Sha384,
+        HashAlgorithm::Sha512,
+        HashAlgorithm::Blake2b512,
+        HashAlgorithm::Blake2s256,
+        HashAlgorithm::Sha3_224,
+        HashAlgorithm::Sha3_256,
+        HashAlgorithm::Sha3_384,
+        HashAlgorithm::Sha3_512,
+        HashAlgorithm::Keccak224,
+        HashAlgorithm::Keccak256,
+        HashAlgorithm::Keccak384,
+        HashAlgorithm::Keccak512,
+        HashAlgorithm::Blake3,
+        HashAlgorithm::Ripemd160,
+        HashAlgorithm::Ripemd256,
+        HashAlgorithm::Ripemd320,
+        HashAlgorithm::Whirlpool,
+        HashAlgorithm::Sm3,
+        HashAlgorithm::Streebog256,
+        HashAlgorithm::Streebog512,
+        HashAlgorithm::Tiger,
+        HashAlgorithm::Md2,
+        HashAlgorithm::Md4,
+    ];
+
+    /// Stable identifier, used for form values
+    pub fn id(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Md2 => "md2",
+            HashAlgorithm::Md4 => "md4",
+            HashAlgorithm::Md5 => "md5",
+            HashAlgorithm::Sha1 => "sha1",
+            HashAlgorithm::Sha224 => "sha224",
+            HashAlgorithm::Sha256 => "sha256",
+            HashAlgorithm::Sha384 => "sha384",
+            HashAlgorithm::Sha512 => "sha512",
+            HashAlgorithm::Blake2b512 => "blake2b512",
+            HashAlgorithm::Blake2s256 => "blake2s256",
+            HashAlgorithm::Sha3_224 => "sha3224",
+            HashAlgorithm::Sha3_256 => "sha3256",
+            HashAlgorithm::Sha3_384 => "sha3384",
+            HashAlgorithm::Sha3_512 => "sha3512",
+            HashAlgorithm::Keccak224 => "keccak224",
+            HashAlgorithm::Keccak256 => "keccak256",
+            HashAlgorithm::Keccak384 => "keccak384",
+            HashAlgorithm::Keccak512 => "keccak512",
+            HashAlgorithm::Blake3 => "blake3",
+            HashAlgorithm::Ripemd160 => "ripemd160",
+            HashAlgorithm::Ripemd256 => "ripemd256",
+            HashAlgorithm::Ripemd320 => "ripemd320",
+            HashAlgorithm::Whirlpool => "whirlpool",
+            HashAlgorithm::Sm3 => "sm3",
+            HashAlgorithm::Streebog256 => "streebog256",
+            HashAlgorithm::Streebog512 => "streebog512",
+            HashAlgorithm::Tiger => "tiger",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|algorithm| algorithm.id() == id)
+    }
+
+    /// Digest length in bytes
+    pub fn output_size(&self) -> usize {
+        match self {
+            HashAlgorithm::Md2 | HashAlgorithm::Md4 | HashAlgorithm::Md5 => 16,
+            HashAlgorithm::Sha1 | HashAlgorithm::Ripemd160 => 20,
+            HashAlgorithm::Tiger => 24,
+            HashAlgorithm::Sha224 | HashAlgorithm::Sha3_224 | HashAlgorithm::Keccak224 => 28,
+            HashAlgorithm::Sha256
+            | HashAlgorithm::Sha3_256
+            | HashAlgorithm::Keccak256
+            | HashAlgorithm::Blake2s256
+            | HashAlgorithm::Blake3
+            | HashAlgorithm::Ripemd256
+            | HashAlgorithm::Sm3
+            | HashAlgorithm::Streebog256 => 32,
+            HashAlgorithm::Ripemd320 => 40,
+            HashAlgorithm::Sha384 | HashAlgorithm::Sha3_384 | HashAlgorithm::Keccak384 => 48,
+            HashAlgorithm::Sha512
+            | HashAlgorithm::Sha3_512
+            | HashAlgorithm::Keccak512
+            | HashAlgorithm::Blake2b512
+            | HashAlgorithm::Whirlpool
+            | HashAlgorithm::Streebog512 => 64,
+        }
+    }
+
+    /// All algorithms producing digests of `size` bytes
+    pub fn with_output_size(size: usize) -> Vec<Self> {
+        Self::ALL
+            .into_iter()
+            .filter(|algorithm| algorithm.output_size() == size)
+            .collect()
+    }
+
+    /// Whether [`hmac_digest`] is real HMAC, rather than a keyed mode of the
+    /// hash itself
+    pub fn uses_hmac(&self) -> bool {
+        !matches!(
+            self,
+            HashAlgorithm::Blake2b512 | HashAlgorithm::Blake2s256 | HashAlgorithm::Blake3
+        )
+    }
+
+    /// Compute the digest of `msg`
+    pub fn digest(&self, msg: &[u8]) -> Vec<u8> {
+        match self {
+            HashAlgorithm::Md2 => md2_hash(msg),
+            HashAlgorithm::Md4 => md4_hash(msg),
+            HashAlgorithm::Md5 => md5_hash(msg),
+            HashAlgorithm::Sha1 => sha1_hash(msg),
+            HashAlgorithm::Sha224 => sha224_hash(msg),
+            HashAlgorithm::Sha256 => sha256_hash(msg),
+            HashAlgorithm::Sha384 => sha384_hash(msg),
+            HashAlgorithm::Sha512 => sha512_hash(msg),
+            HashAlgorithm::Blake2b512 => blake2b512_hash(msg),
+            HashAlgorithm::Blake2s256 => blake2s256_hash(msg),
+            HashAlgorithm::Sha3_224 => sha3224_hash(msg),
+            HashAlgorithm::Sha3_256 => sha3256_hash(msg),
+            HashAlgorithm::Sha3_384 => sha3384_hash(msg),
+            HashAlgorithm::Sha3_512 => sha3512_hash(msg),
+            HashAlgorithm::Keccak224 => keccak224_hash(msg),
+            HashAlgorithm::Keccak256 => keccak256_hash(msg),
+            HashAlgorithm::Keccak384 => keccak384_hash(msg),
+            HashAlgorithm::Keccak512 => keccak512_hash(msg),
+            HashAlgorithm::Blake3 => blake3_hash(msg),
+            HashAlgorithm::Ripemd160 => ripemd160_hash(msg),
+            HashAlgorithm::Ripemd256 => ripemd256_hash(msg),
+            HashAlgorithm::Ripemd320 => ripemd320_hash(msg),
+            HashAlgorithm::Whirlpool => whirlpool_hash(msg),
+            HashAlgorithm::Sm3 => sm3_hash(msg),
+            HashAlgorithm::Streebog256 => streebog256_hash(msg),
+            HashAlgorithm::Streebog512 => streebog512_hash(msg),
+            HashAlgorithm::Tiger => tiger_hash(msg),
+        }
+    }
+
+    /// Human readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Md2 => "MD2",
+            HashAlgorithm::Md4 => "MD4",
+            HashAlgorithm::Md5 => "MD5",
+            HashAlgorithm::Sha1 => "SHA-1",
+            HashAlgorithm::Sha224 => "SHA-224",
+            HashAlgorithm::Sha256 => "SHA-256",
+            HashAlgorithm::Sha384 => "SHA-384",
+            HashAlgorithm::Sha512 => "SHA-512",
+            HashAlgorithm::Blake2b512 => "BLAKE2b-512",
+            HashAlgorithm::Blake2s256 => "BLAKE2s-256",
+            HashAlgorithm::Sha3_224 => "SHA3-224",
+            HashAlgorithm::Sha3_256 => "SHA3-256",
+            HashAlgorithm::Sha3_384 => "SHA3-384",
+            HashAlgorithm::Sha3_512 => "SHA3-512",
+            HashAlgorithm::Keccak224 => "Keccak-224",
+            HashAlgorithm::Keccak256 => "Keccak-256",
+            HashAlgorithm::Keccak384 => "Keccak-384",
+            HashAlgorithm::Keccak512 => "Keccak-512",
+            HashAlgorithm::Blake3 => "BLAKE3",
+            HashAlgorithm::Ripemd160 => "RIPEMD-160",
+            HashAlgorithm::Ripemd256 => "RIPEMD-256",
+            HashAlgorithm::Ripemd320 => "RIPEMD-320",
+            HashAlgorithm::Whirlpool => "Whirlpool",
+            HashAlgorithm::Sm3 => "SM3",
+            HashAlgorithm::Streebog256 => "Streebog-256",
+            HashAlgorithm::Streebog512 => "Streebog-512",
+            HashAlgorithm::Tiger => "Tiger",
+        }
+    }
+}
+
+fn digest<D: Digest>(msg: &[u8]) -> Vec<u8> {
+    let mut hasher = D::new();
+    hasher.update(msg);
+    hasher.finalize().to_vec()
+}
+
+fn xof<X: Update + ExtendableOutput>(mut hasher: X, msg: &[u8], length: usize) -> Vec<u8> {
+    hasher.update(msg);
+    let mut output = vec![0; length];
+    hasher.finalize_xof().read(&mut output);
+    output
+}
+
+/// Error for an HMAC-based KDF given a hash [`hmac_digest`] uses in a keyed mode instead
+fn hmac_required(kdf: &str, algorithm: HashAlgorithm) -> String {
+    format!("{} needs an HMAC hash, not {}", kdf, algorithm.name())
+}
 
-    let code_bytes = mac.finalize().into_bytes();
+fn mac<M: Mac + hmac::digest::KeyInit>(key: &[u8], msg: &[u8]) -> Result<Vec<u8>, String> {
+    let mut mac = <M as Mac>::new_from_slice(key).map_err(|_| "Invalid key length".to_string())?;
+    mac.update(msg);
 
-    code_bytes.to_vec()
+    Ok(mac.finalize().into_bytes().to_vec())
 }
@@ -0,0 +1,95 @@
+//! Elliptic curve Diffie-Hellman key agreement
+
+use elliptic_curve::ecdh::diffie_hellman;
+use elliptic_curve::pkcs8::AssociatedOid;
+use elliptic_curve::point::PointCompression;
+use elliptic_curve::sec1::{FromEncodedPoint, ModulusSize, ToEncodedPoint};
+use elliptic_curve::{AffinePoint, CurveArithmetic, FieldBytesSize, JwkParameters};
+
+use super::keys::{EcKey, X25519Key};
+use super::KeyData;
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum EcdhCurve {
+    #[default]
+    X25519,
+    P256,
+    P384,
+    Secp256k1,
+}
+
+impl EcdhCurve {
+    pub const ALL: [EcdhCurve; 4] = [
+        EcdhCurve::X25519,
+        EcdhCurve::P256,
+        EcdhCurve::P384,
+        EcdhCurve::Secp256k1,
+    ];
+
+    /// Stable identifier, used for form values
+    pub fn id(&self) -> &'static str {
+        match self {
+            EcdhCurve::X25519 => "x25519",
+            EcdhCurve::P256 => "p256",
+            EcdhCurve::P384 => "p384",
+            EcdhCurve::Secp256k1 => "secp256k1",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|curve| curve.id() == id)
+    }
+
+    /// Human readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            EcdhCurve::X25519 => "X25519",
+            EcdhCurve::P256 => "P-256",
+            EcdhCurve::P384 => "P-384",
+            EcdhCurve::Secp256k1 => "secp256k1",
+        }
+    }
+
+    /// Raw shared secret between our private key and the peer's public key:
+    /// the u-coordinate for X25519, the x-coordinate for the other curves
+    pub fn shared_secret(&self, private_key: &str, public_key: &str) -> Result<Vec<u8>, String> {
+        let private_key = KeyData::parse(private_key).map_err(|e| format!("Private key: {}", e))?;
+        let public_key = KeyData::parse(public_key).map_err(|e| format!("Peer key: {}", e))?;
+        match self {
+            EcdhCurve::X25519 => {
+                let X25519Key::Private(secret) = X25519Key::parse(&private_key, true)? else {
+                    return Err("Private key is a public key".to_string());
+                };
+                let peer = X25519Key::parse(&public_key, false)?.public_key();
+                let shared = secret.diffie_hellman(&peer);
+                if !shared.was_contributory() {
+                    return Err("Peer key is a low order point".to_string());
+                }
+                Ok(shared.as_bytes().to_vec())
+            }
+            EcdhCurve::P256 => ec_shared_secret::<p256::NistP256>(self, &private_key, &public_key),
+            EcdhCurve::P384 => ec_shared_secret::<p384::NistP384>(self, &private_key, &public_key),
+            EcdhCurve::Secp256k1 => {
+                ec_shared_secret::<k256::Secp256k1>(self, &private_key, &public_key)
+            }
+        }
+    }
+}
+
+fn ec_shared_secret<C>(
+    curve: &EcdhCurve,
+    private_key: &KeyData,
+    public_key: &KeyData,
+) -> Result<Vec<u8>, String>
+where
+    C: CurveArithmetic + AssociatedOid + JwkParameters + PointCompression,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    let EcKey::Private(secret) = EcKey::<C>::parse(private_key, curve.name())? else {
+        return Err("Private key is a public key".to_string());
+    };
+    let peer = EcKey::<C>::parse(public_key, curve.name())?.public_key();
+    let shared = diffie_hellman(secret.to_nonzero_scalar(), peer.as_affine());
+    Ok(shared.raw_secret_bytes().to_vec())
+}
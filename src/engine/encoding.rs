@@ -33,3 +33,199 @@ pub enum Encoding {
     Hex,
     Html,
 }
+
+/// Decode user-entered binary material (keys, salts, etc.) into raw bytes
+pub fn decode_input(text: &str, encoding: &InputEncoding) -> Result<Vec<u8>, String> {
+    match encoding {
+        InputEncoding::Utf8 => Ok(text.as_bytes().to_vec()),
+        InputEncoding::Hex => hex::decode(text.trim()).map_err(|e| e.to_string()),
+        InputEncoding::Base64 => base64::decode(text.trim()).map_err(|e| e.to_string()),
+    }
+}
+
+/// Label, contents and the text following the first PEM block in `text`.
+///
+/// Line breaks are optional, so blocks pasted into a single line input still
+/// decode, and RFC 1421 headers such as `Proc-Type` are skipped.
+pub fn decode_pem(text: &str) -> Result<(&str, Vec<u8>, &str), String> {
+    let rest = match text.find("-----BEGIN ") {
+        Some(start) => &text[start + "-----BEGIN ".len()..],
+        None => return Err("Missing PEM header".to_string()),
+    };
+    let (label, rest) = rest
+        .split_once("-----")
+        .ok_or_else(|| "Invalid PEM header".to_string())?;
+    let end = format!("-----END {}-----", label);
+    let (body, next) = rest
+        .split_once(&end)
+        .ok_or_else(|| format!("Missing {}", end))?;
+    let body: String = body
+        .lines()
+        .filter(|line| !line.contains(':'))
+        .flat_map(|line| line.split_whitespace())
+        .collect();
+    let contents = base64::decode(body).map_err(|e| format!("Invalid PEM: {}", e))?;
+    Ok((label, contents, next))
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum InputEncoding {
+    #[default]
+    Utf8,
+    Hex,
+    Base64,
+}
+
+/// Render digest bytes in the given output format
+pub fn format_digest(bytes: &[u8], format: &DigestFormat) -> String {
+    match format {
+        DigestFormat::Hex => hex::encode(bytes),
+        DigestFormat::HexUpper => hex::encode_upper(bytes),
+        DigestFormat::HexColon => grouped_hex(bytes, ":"),
+        DigestFormat::HexSpace => grouped_hex(bytes, " "),
+        DigestFormat::Base64 => base64::encode(bytes),
+        DigestFormat::Base64Url => base64::encode_config(bytes, base64::URL_SAFE_NO_PAD),
+        DigestFormat::Base32 => base32::encode(base32::Alphabet::RFC4648 { padding: true }, bytes),
+        DigestFormat::Decimal => format!("{:?}", bytes),
+    }
+}
+
+/// Parse a digest written in any of the [`DigestFormat`]s back into bytes.
+///
+/// Whitespace is ignored, as are `:` and `-` separators between hex pairs, and
+/// hex is matched case-insensitively.
+pub fn parse_digest(text: &str) -> Result<Vec<u8>, String> {
+    let text: String = text.split_whitespace().collect();
+    if text.is_empty() {
+        return Err("Digest is empty".to_string());
+    }
+
+    let stripped_hex: String = text.chars().filter(|c| *c != ':' && *c != '-').collect();
+    if let Ok(bytes) = hex::decode(&stripped_hex) {
+        return Ok(bytes);
+    }
+    if let Some(bytes) = parse_decimal_array(&text) {
+        return Ok(bytes);
+    }
+    // Base32's alphabet is a subset of Base64's, so it has to be tried first
+    if is_padded_base32(&text) {
+        if let Some(bytes) = base32::decode(base32::Alphabet::RFC4648 { padding: true }, &text) {
+            return Ok(bytes);
+        }
+    }
+    if let Ok(bytes) = base64::decode(&text) {
+        return Ok(bytes);
+    }
+    if let Ok(bytes) = base64::decode_config(&text, base64::URL_SAFE_NO_PAD) {
+        return Ok(bytes);
+    }
+
+    Err("Unrecognised digest format".to_string())
+}
+
+/// Uppercase RFC 4648 Base32 in whole 8 character blocks, with a valid amount of padding
+fn is_padded_base32(text: &str) -> bool {
+    let data = text.trim_end_matches('=');
+    let padding = text.len() - data.len();
+    text.len().is_multiple_of(8)
+        && [0, 1, 3, 4, 6].contains(&padding)
+        && data
+            .bytes()
+            .all(|c| c.is_ascii_uppercase() || (b'2'..=b'7').contains(&c))
+}
+
+fn parse_decimal_array(text: &str) -> Option<Vec<u8>> {
+    text.strip_prefix('[')?
+        .strip_suffix(']')?
+        .split(',')
+        .map(|byte| byte.parse::<u8>().ok())
+        .collect()
+}
+
+fn grouped_hex(bytes: &[u8], separator: &str) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum DigestFormat {
+    #[default]
+    Hex,
+    HexUpper,
+    HexColon,
+    HexSpace,
+    Base64,
+    Base64Url,
+    Base32,
+    Decimal,
+}
+
+impl DigestFormat {
+    pub const ALL: [DigestFormat; 8] = [
+        DigestFormat::Hex,
+        DigestFormat::HexUpper,
+        DigestFormat::HexColon,
+        DigestFormat::HexSpace,
+        DigestFormat::Base64,
+        DigestFormat::Base64Url,
+        DigestFormat::Base32,
+        DigestFormat::Decimal,
+    ];
+
+    /// Stable identifier, used for form values
+    pub fn id(&self) -> &'static str {
+        match self {
+            DigestFormat::Hex => "hex",
+            DigestFormat::HexUpper => "hex-upper",
+            DigestFormat::HexColon => "hex-colon",
+            DigestFormat::HexSpace => "hex-space",
+            DigestFormat::Base64 => "base64",
+            DigestFormat::Base64Url => "base64url",
+            DigestFormat::Base32 => "base32",
+            DigestFormat::Decimal => "decimal",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| format.id() == id)
+    }
+
+    /// Human readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            DigestFormat::Hex => "Hex",
+            DigestFormat::HexUpper => "Hex (uppercase)",
+            DigestFormat::HexColon => "Hex (colon separated)",
+            DigestFormat::HexSpace => "Hex (space separated)",
+            DigestFormat::Base64 => "Base64",
+            DigestFormat::Base64Url => "Base64url (no padding)",
+            DigestFormat::Base32 => "Base32",
+            DigestFormat::Decimal => "Byte array (decimal)",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::HashAlgorithm;
+
+    #[test]
+    fn digest_formats_round_trip() {
+        for algorithm in HashAlgorithm::ALL {
+            let digest = algorithm.digest(b"abc");
+            for format in DigestFormat::ALL {
+                assert_eq!(
+                    parse_digest(&format_digest(&digest, &format)),
+                    Ok(digest.clone()),
+                    "{} as {}",
+                    algorithm.name(),
+                    format.name()
+                );
+            }
+        }
+    }
+}
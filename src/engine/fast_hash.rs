@@ -0,0 +1,781 @@
+//! Fast non-cryptographic hashes, as used for hash tables, sharding and caches.
+//!
+//! Results are integers, so they are returned as big-endian bytes to match
+//! the number most libraries print. Hashes wider than 64 bits that the
+//! reference implementation returns as several 64-bit words are joined with
+//! the last word most significant, as XXH3-128's canonical form is. Reference
+//! test vectors listed as bytes (SipHash's `vectors.h`, MurmurHash3's
+//! `x64_128` output buffer) are little-endian, so they read reversed here.
+
+use highway::{HighwayHash, HighwayHasher, Key};
+use siphasher::sip::SipHasher24;
+use siphasher::sip128::{Hasher128, SipHasher24 as SipHasher24_128};
+use std::hash::Hasher;
+use xxhash_rust::{xxh3, xxh32, xxh64};
+
+const FNV32_OFFSET: u32 = 0x811c9dc5;
+const FNV32_PRIME: u32 = 0x01000193;
+const FNV64_OFFSET: u64 = 0xcbf29ce484222325;
+const FNV64_PRIME: u64 = 0x00000100000001b3;
+
+pub fn fnv1_32(data: &[u8]) -> u32 {
+    data.iter().fold(FNV32_OFFSET, |hash, &byte| {
+        hash.wrapping_mul(FNV32_PRIME) ^ u32::from(byte)
+    })
+}
+
+pub fn fnv1a_32(data: &[u8]) -> u32 {
+    data.iter().fold(FNV32_OFFSET, |hash, &byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(FNV32_PRIME)
+    })
+}
+
+pub fn fnv1_64(data: &[u8]) -> u64 {
+    data.iter().fold(FNV64_OFFSET, |hash, &byte| {
+        hash.wrapping_mul(FNV64_PRIME) ^ u64::from(byte)
+    })
+}
+
+pub fn fnv1a_64(data: &[u8]) -> u64 {
+    data.iter().fold(FNV64_OFFSET, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(FNV64_PRIME)
+    })
+}
+
+/// What an algorithm accepts to vary its output
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum SeedKind {
+    None,
+    /// 32-bit integer seed
+    U32,
+    /// 64-bit integer seed
+    U64,
+    /// Key of the given number of bytes, entered as hex
+    Key(usize),
+}
+
+/// Supported fast hashes
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum FastHashAlgorithm {
+    Xxh32,
+    #[default]
+    Xxh64,
+    Xxh3_64,
+    Xxh3_128,
+    Murmur3_32,
+    Murmur3_128,
+    Fnv1_32,
+    Fnv1a32,
+    Fnv1_64,
+    Fnv1a64,
+    SipHash24,
+    SipHash24_128,
+    HighwayHash64,
+    HighwayHash128,
+    HighwayHash256,
+    CityHash64,
+    CityHash64V102,
+    CityHash128,
+    CityHash128V102,
+}
+
+impl FastHashAlgorithm {
+    pub const ALL: [FastHashAlgorithm; 19] = [
+        FastHashAlgorithm::Xxh32,
+        FastHashAlgorithm::Xxh64,
+        FastHashAlgorithm::Xxh3_64,
+        FastHashAlgorithm::Xxh3_128,
+        FastHashAlgorithm::Murmur3_32,
+        FastHashAlgorithm::Murmur3_128,
+        FastHashAlgorithm::Fnv1_32,
+        FastHashAlgorithm::Fnv1a32,
+        FastHashAlgorithm::Fnv1_64,
+        FastHashAlgorithm::Fnv1a64,
+        FastHashAlgorithm::SipHash24,
+        FastHashAlgorithm::SipHash24_128,
+        FastHashAlgorithm::HighwayHash64,
+        FastHashAlgorithm::HighwayHash128,
+        FastHashAlgorithm::HighwayHash256,
+        FastHashAlgorithm::CityHash64,
+        FastHashAlgorithm::CityHash64V102,
+        FastHashAlgorithm::CityHash128,
+        FastHashAlgorithm::CityHash128V102,
+    ];
+
+    /// Stable identifier, used for form values
+    pub fn id(&self) -> &'static str {
+        match self {
+            FastHashAlgorithm::Xxh32 => "xxh32",
+            FastHashAlgorithm::Xxh64 => "xxh64",
+            FastHashAlgorithm::Xxh3_64 => "xxh3-64",
+            FastHashAlgorithm::Xxh3_128 => "xxh3-128",
+            FastHashAlgorithm::Murmur3_32 => "murmur3-32",
+            FastHashAlgorithm::Murmur3_128 => "murmur3-128",
+            FastHashAlgorithm::Fnv1_32 => "fnv1-32",
+            FastHashAlgorithm::Fnv1a32 => "fnv1a-32",
+            FastHashAlgorithm::Fnv1_64 => "fnv1-64",
+            FastHashAlgorithm::Fnv1a64 => "fnv1a-64",
+            FastHashAlgorithm::SipHash24 => "siphash24",
+            FastHashAlgorithm::SipHash24_128 => "siphash24-128",
+            FastHashAlgorithm::HighwayHash64 => "highway64",
+            FastHashAlgorithm::HighwayHash128 => "highway128",
+            FastHashAlgorithm::HighwayHash256 => "highway256",
+            FastHashAlgorithm::CityHash64 => "city64",
+            FastHashAlgorithm::CityHash64V102 => "city64-v102",
+            FastHashAlgorithm::CityHash128 => "city128",
+            FastHashAlgorithm::CityHash128V102 => "city128-v102",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|algorithm| algorithm.id() == id)
+    }
+
+    /// Human readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            FastHashAlgorithm::Xxh32 => "xxHash32",
+            FastHashAlgorithm::Xxh64 => "xxHash64",
+            FastHashAlgorithm::Xxh3_64 => "XXH3-64",
+            FastHashAlgorithm::Xxh3_128 => "XXH3-128",
+            FastHashAlgorithm::Murmur3_32 => "MurmurHash3 x86_32",
+            FastHashAlgorithm::Murmur3_128 => "MurmurHash3 x64_128",
+            FastHashAlgorithm::Fnv1_32 => "FNV-1 32",
+            FastHashAlgorithm::Fnv1a32 => "FNV-1a 32",
+            FastHashAlgorithm::Fnv1_64 => "FNV-1 64",
+            FastHashAlgorithm::Fnv1a64 => "FNV-1a 64",
+            FastHashAlgorithm::SipHash24 => "SipHash-2-4",
+            FastHashAlgorithm::SipHash24_128 => "SipHash-2-4 (128-bit output)",
+            FastHashAlgorithm::HighwayHash64 => "HighwayHash-64",
+            FastHashAlgorithm::HighwayHash128 => "HighwayHash-128",
+            FastHashAlgorithm::HighwayHash256 => "HighwayHash-256",
+            FastHashAlgorithm::CityHash64 => "CityHash64 (v1.1)",
+            FastHashAlgorithm::CityHash64V102 => "CityHash64 (v1.0.2, ClickHouse)",
+            FastHashAlgorithm::CityHash128 => "CityHash128 (v1.1)",
+            FastHashAlgorithm::CityHash128V102 => "CityHash128 (v1.0.2, ClickHouse)",
+        }
+    }
+
+    pub fn seed_kind(&self) -> SeedKind {
+        match self {
+            FastHashAlgorithm::Xxh32
+            | FastHashAlgorithm::Murmur3_32
+            | FastHashAlgorithm::Murmur3_128 => SeedKind::U32,
+            FastHashAlgorithm::Xxh64 | FastHashAlgorithm::Xxh3_64 | FastHashAlgorithm::Xxh3_128 => {
+                SeedKind::U64
+            }
+            FastHashAlgorithm::SipHash24 | FastHashAlgorithm::SipHash24_128 => SeedKind::Key(16),
+            FastHashAlgorithm::HighwayHash64
+            | FastHashAlgorithm::HighwayHash128
+            | FastHashAlgorithm::HighwayHash256 => SeedKind::Key(32),
+            FastHashAlgorithm::Fnv1_32
+            | FastHashAlgorithm::Fnv1a32
+            | FastHashAlgorithm::Fnv1_64
+            | FastHashAlgorithm::Fnv1a64
+            | FastHashAlgorithm::CityHash64
+            | FastHashAlgorithm::CityHash64V102
+            | FastHashAlgorithm::CityHash128
+            | FastHashAlgorithm::CityHash128V102 => SeedKind::None,
+        }
+    }
+
+    /// Hash `data`, using `seed` as described by [`Self::seed_kind`].
+    ///
+    /// An empty seed means zero.
+    pub fn hash(&self, data: &[u8], seed: &str) -> Result<Vec<u8>, String> {
+        let hash = match self {
+            FastHashAlgorithm::Xxh32 => xxh32::xxh32(data, parse_seed(seed, 32)? as u32)
+                .to_be_bytes()
+                .to_vec(),
+            FastHashAlgorithm::Xxh64 => xxh64::xxh64(data, parse_seed(seed, 64)?)
+                .to_be_bytes()
+                .to_vec(),
+            FastHashAlgorithm::Xxh3_64 => xxh3::xxh3_64_with_seed(data, parse_seed(seed, 64)?)
+                .to_be_bytes()
+                .to_vec(),
+            FastHashAlgorithm::Xxh3_128 => xxh3::xxh3_128_with_seed(data, parse_seed(seed, 64)?)
+                .to_be_bytes()
+                .to_vec(),
+            FastHashAlgorithm::Murmur3_32 => {
+                murmur3::murmur3_32(&mut &data[..], parse_seed(seed, 32)? as u32)
+                    .map_err(|e| e.to_string())?
+                    .to_be_bytes()
+                    .to_vec()
+            }
+            FastHashAlgorithm::Murmur3_128 => {
+                murmur3::murmur3_x64_128(&mut &data[..], parse_seed(seed, 32)? as u32)
+                    .map_err(|e| e.to_string())?
+                    .to_be_bytes()
+                    .to_vec()
+            }
+            FastHashAlgorithm::Fnv1_32 => fnv1_32(data).to_be_bytes().to_vec(),
+            FastHashAlgorithm::Fnv1a32 => fnv1a_32(data).to_be_bytes().to_vec(),
+            FastHashAlgorithm::Fnv1_64 => fnv1_64(data).to_be_bytes().to_vec(),
+            FastHashAlgorithm::Fnv1a64 => fnv1a_64(data).to_be_bytes().to_vec(),
+            FastHashAlgorithm::SipHash24 => {
+                let mut hasher = SipHasher24::new_with_key(&parse_key::<16>(seed)?);
+                hasher.write(data);
+                hasher.finish().to_be_bytes().to_vec()
+            }
+            FastHashAlgorithm::SipHash24_128 => {
+                let mut hasher = SipHasher24_128::new_with_key(&parse_key::<16>(seed)?);
+                hasher.write(data);
+                hasher.finish128().as_u128().to_be_bytes().to_vec()
+            }
+            FastHashAlgorithm::HighwayHash64 => highway_hasher(seed, data)?
+                .finalize64()
+                .to_be_bytes()
+                .to_vec(),
+            FastHashAlgorithm::HighwayHash128 => highway_hasher(seed, data)?
+                .finalize128()
+                .iter()
+                .rev()
+                .flat_map(|word| word.to_be_bytes())
+                .collect(),
+            FastHashAlgorithm::HighwayHash256 => highway_hasher(seed, data)?
+                .finalize256()
+                .iter()
+                .rev()
+                .flat_map(|word| word.to_be_bytes())
+                .collect(),
+            FastHashAlgorithm::CityHash64 => cityhash64(data).to_be_bytes().to_vec(),
+            FastHashAlgorithm::CityHash64V102 => cityhash64_v102(data).to_be_bytes().to_vec(),
+            // `cityhash_rs` packs the reference's `Low64` into the high half
+            FastHashAlgorithm::CityHash128 => cityhash_rs::cityhash_110_128(data)
+                .rotate_left(64)
+                .to_be_bytes()
+                .to_vec(),
+            FastHashAlgorithm::CityHash128V102 => cityhash_rs::cityhash_102_128(data)
+                .rotate_left(64)
+                .to_be_bytes()
+                .to_vec(),
+        };
+
+        Ok(hash)
+    }
+}
+
+const CITY_K0: u64 = 0xc3a5c85c97cb3127;
+const CITY_K1: u64 = 0xb492b66fbe98f273;
+const CITY_K2: u64 = 0x9ae16a3b2f90404f;
+const CITY_K3: u64 = 0xc949d7c7509e6557;
+const CITY_KMUL: u64 = 0x9ddfea08eb382d69;
+
+/// CityHash64 from CityHash v1.1
+pub fn cityhash64(data: &[u8]) -> u64 {
+    match data.len() {
+        0..=16 => city_len_0_to_16(data),
+        17..=32 => city_len_17_to_32(data),
+        33..=64 => city_len_33_to_64(data),
+        _ => city_long(data),
+    }
+}
+
+/// CityHash64 from CityHash v1.0.2, as ClickHouse's `cityHash64` uses
+pub fn cityhash64_v102(data: &[u8]) -> u64 {
+    match data.len() {
+        0..=16 => city_v102_len_0_to_16(data),
+        17..=32 => city_v102_len_17_to_32(data),
+        33..=64 => city_v102_len_33_to_64(data),
+        _ => city_v102_long(data),
+    }
+}
+
+fn city_fetch64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+fn city_fetch32(data: &[u8], offset: usize) -> u64 {
+    u64::from(u32::from_le_bytes(
+        data[offset..offset + 4].try_into().unwrap(),
+    ))
+}
+
+fn city_shift_mix(value: u64) -> u64 {
+    value ^ (value >> 47)
+}
+
+fn city_len16(u: u64, v: u64, mul: u64) -> u64 {
+    let a = city_shift_mix((u ^ v).wrapping_mul(mul));
+    let b = city_shift_mix((v ^ a).wrapping_mul(mul));
+    b.wrapping_mul(mul)
+}
+
+/// The 1 to 3 byte case, which only differs between versions in one constant
+fn city_len_1_to_3(data: &[u8], k: u64) -> u64 {
+    let len = data.len();
+    let y = u32::from(data[0]) + (u32::from(data[len >> 1]) << 8);
+    let z = len as u32 + (u32::from(data[len - 1]) << 2);
+    city_shift_mix(u64::from(y).wrapping_mul(CITY_K2) ^ u64::from(z).wrapping_mul(k))
+        .wrapping_mul(CITY_K2)
+}
+
+fn city_len_0_to_16(data: &[u8]) -> u64 {
+    let len = data.len();
+    let mul = CITY_K2.wrapping_add(len as u64 * 2);
+    if len >= 8 {
+        let a = city_fetch64(data, 0).wrapping_add(CITY_K2);
+        let b = city_fetch64(data, len - 8);
+        let c = b.rotate_right(37).wrapping_mul(mul).wrapping_add(a);
+        let d = a.rotate_right(25).wrapping_add(b).wrapping_mul(mul);
+        city_len16(c, d, mul)
+    } else if len >= 4 {
+        let a = city_fetch32(data, 0);
+        city_len16(len as u64 + (a << 3), city_fetch32(data, len - 4), mul)
+    } else if len > 0 {
+        city_len_1_to_3(data, CITY_K0)
+    } else {
+        CITY_K2
+    }
+}
+
+fn city_len_17_to_32(data: &[u8]) -> u64 {
+    let len = data.len();
+    let mul = CITY_K2.wrapping_add(len as u64 * 2);
+    let a = city_fetch64(data, 0).wrapping_mul(CITY_K1);
+    let b = city_fetch64(data, 8);
+    let c = city_fetch64(data, len - 8).wrapping_mul(mul);
+    let d = city_fetch64(data, len - 16).wrapping_mul(CITY_K2);
+    city_len16(
+        a.wrapping_add(b)
+            .rotate_right(43)
+            .wrapping_add(c.rotate_right(30))
+            .wrapping_add(d),
+        a.wrapping_add(b.wrapping_add(CITY_K2).rotate_right(18))
+            .wrapping_add(c),
+        mul,
+    )
+}
+
+fn city_len_33_to_64(data: &[u8]) -> u64 {
+    let len = data.len();
+    let mul = CITY_K2.wrapping_add(len as u64 * 2);
+    let a = city_fetch64(data, 0).wrapping_mul(CITY_K2);
+    let b = city_fetch64(data, 8);
+    let c = city_fetch64(data, len - 24);
+    let d = city_fetch64(data, len - 32);
+    let e = city_fetch64(data, 16).wrapping_mul(CITY_K2);
+    let f = city_fetch64(data, 24).wrapping_mul(9);
+    let g = city_fetch64(data, len - 8);
+    let h = city_fetch64(data, len - 16).wrapping_mul(mul);
+    let u = a
+        .wrapping_add(g)
+        .rotate_right(43)
+        .wrapping_add(b.rotate_right(30).wrapping_add(c).wrapping_mul(9));
+    let v = (a.wrapping_add(g) ^ d).wrapping_add(f).wrapping_add(1);
+    let w = u
+        .wrapping_add(v)
+        .wrapping_mul(mul)
+        .swap_bytes()
+        .wrapping_add(h);
+    let x = e.wrapping_add(f).rotate_right(42).wrapping_add(c);
+    let y = v
+        .wrapping_add(w)
+        .wrapping_mul(mul)
+        .swap_bytes()
+        .wrapping_add(g)
+        .wrapping_mul(mul);
+    let z = e.wrapping_add(f).wrapping_add(c);
+    let a = x
+        .wrapping_add(z)
+        .wrapping_mul(mul)
+        .wrapping_add(y)
+        .swap_bytes()
+        .wrapping_add(b);
+    let b = city_shift_mix(
+        z.wrapping_add(a)
+            .wrapping_mul(mul)
+            .wrapping_add(d)
+            .wrapping_add(h),
+    )
+    .wrapping_mul(mul);
+    b.wrapping_add(x)
+}
+
+fn city_v102_len_0_to_16(data: &[u8]) -> u64 {
+    let len = data.len();
+    if len > 8 {
+        let a = city_fetch64(data, 0);
+        let b = city_fetch64(data, len - 8);
+        city_len16(
+            a,
+            b.wrapping_add(len as u64).rotate_right(len as u32),
+            CITY_KMUL,
+        ) ^ b
+    } else if len >= 4 {
+        let a = city_fetch32(data, 0);
+        city_len16(
+            len as u64 + (a << 3),
+            city_fetch32(data, len - 4),
+            CITY_KMUL,
+        )
+    } else if len > 0 {
+        city_len_1_to_3(data, CITY_K3)
+    } else {
+        CITY_K2
+    }
+}
+
+fn city_v102_len_17_to_32(data: &[u8]) -> u64 {
+    let len = data.len();
+    let a = city_fetch64(data, 0).wrapping_mul(CITY_K1);
+    let b = city_fetch64(data, 8);
+    let c = city_fetch64(data, len - 8).wrapping_mul(CITY_K2);
+    let d = city_fetch64(data, len - 16).wrapping_mul(CITY_K0);
+    city_len16(
+        a.wrapping_sub(b)
+            .rotate_right(43)
+            .wrapping_add(c.rotate_right(30))
+            .wrapping_add(d),
+        a.wrapping_add((b ^ CITY_K3).rotate_right(20))
+            .wrapping_sub(c)
+            .wrapping_add(len as u64),
+        CITY_KMUL,
+    )
+}
+
+fn city_v102_len_33_to_64(data: &[u8]) -> u64 {
+    let len = data.len();
+    let mut z = city_fetch64(data, 24);
+    let mut a = city_fetch64(data, 0).wrapping_add(
+        (len as u64)
+            .wrapping_add(city_fetch64(data, len - 16))
+            .wrapping_mul(CITY_K0),
+    );
+    let mut b = a.wrapping_add(z).rotate_right(52);
+    let mut c = a.rotate_right(37);
+    a = a.wrapping_add(city_fetch64(data, 8));
+    c = c.wrapping_add(a.rotate_right(7));
+    a = a.wrapping_add(city_fetch64(data, 16));
+    let vf = a.wrapping_add(z);
+    let vs = b.wrapping_add(a.rotate_right(31)).wrapping_add(c);
+    a = city_fetch64(data, 16).wrapping_add(city_fetch64(data, len - 32));
+    z = city_fetch64(data, len - 8);
+    b = a.wrapping_add(z).rotate_right(52);
+    c = a.rotate_right(37);
+    a = a.wrapping_add(city_fetch64(data, len - 24));
+    c = c.wrapping_add(a.rotate_right(7));
+    a = a.wrapping_add(city_fetch64(data, len - 16));
+    let wf = a.wrapping_add(z);
+    let ws = b.wrapping_add(a.rotate_right(31)).wrapping_add(c);
+    let r = city_shift_mix(
+        vf.wrapping_add(ws)
+            .wrapping_mul(CITY_K2)
+            .wrapping_add(wf.wrapping_add(vs).wrapping_mul(CITY_K0)),
+    );
+    city_shift_mix(r.wrapping_mul(CITY_K0).wrapping_add(vs)).wrapping_mul(CITY_K2)
+}
+
+fn city_weak_len32(data: &[u8], offset: usize, a: u64, b: u64) -> (u64, u64) {
+    let w = city_fetch64(data, offset);
+    let x = city_fetch64(data, offset + 8);
+    let y = city_fetch64(data, offset + 16);
+    let z = city_fetch64(data, offset + 24);
+    let a = a.wrapping_add(w);
+    let b = b.wrapping_add(a).wrapping_add(z).rotate_right(21);
+    let c = a;
+    let a = a.wrapping_add(x).wrapping_add(y);
+    let b = b.wrapping_add(a.rotate_right(44));
+    (a.wrapping_add(z), b.wrapping_add(c))
+}
+
+fn city_long(data: &[u8]) -> u64 {
+    let len = data.len();
+    let mut x = city_fetch64(data, len - 40);
+    let mut y = city_fetch64(data, len - 16).wrapping_add(city_fetch64(data, len - 56));
+    let mut z = city_len16(
+        city_fetch64(data, len - 48).wrapping_add(len as u64),
+        city_fetch64(data, len - 24),
+        CITY_KMUL,
+    );
+    let mut v = city_weak_len32(data, len - 64, len as u64, z);
+    let mut w = city_weak_len32(data, len - 32, y.wrapping_add(CITY_K1), x);
+    x = x.wrapping_mul(CITY_K1).wrapping_add(city_fetch64(data, 0));
+
+    // Whole 64 byte chunks, the last of which may overlap the tail hashed above
+    for offset in (0..(len - 1) & !63).step_by(64) {
+        x = x
+            .wrapping_add(y)
+            .wrapping_add(v.0)
+            .wrapping_add(city_fetch64(data, offset + 8))
+            .rotate_right(37)
+            .wrapping_mul(CITY_K1);
+        y = y
+            .wrapping_add(v.1)
+            .wrapping_add(city_fetch64(data, offset + 48))
+            .rotate_right(42)
+            .wrapping_mul(CITY_K1);
+        x ^= w.1;
+        y = y
+            .wrapping_add(v.0)
+            .wrapping_add(city_fetch64(data, offset + 40));
+        z = z.wrapping_add(w.0).rotate_right(33).wrapping_mul(CITY_K1);
+        v = city_weak_len32(data, offset, v.1.wrapping_mul(CITY_K1), x.wrapping_add(w.0));
+        w = city_weak_len32(
+            data,
+            offset + 32,
+            z.wrapping_add(w.1),
+            y.wrapping_add(city_fetch64(data, offset + 16)),
+        );
+        std::mem::swap(&mut z, &mut x);
+    }
+    city_len16(
+        city_len16(v.0, w.0, CITY_KMUL)
+            .wrapping_add(city_shift_mix(y).wrapping_mul(CITY_K1))
+            .wrapping_add(z),
+        city_len16(v.1, w.1, CITY_KMUL).wrapping_add(x),
+        CITY_KMUL,
+    )
+}
+
+fn city_v102_long(data: &[u8]) -> u64 {
+    let len = data.len();
+    let mut x = city_fetch64(data, 0);
+    let mut y = city_fetch64(data, len - 16) ^ CITY_K1;
+    let mut z = city_fetch64(data, len - 56) ^ CITY_K0;
+    let mut v = city_weak_len32(data, len - 64, len as u64, y);
+    let mut w = city_weak_len32(data, len - 32, (len as u64).wrapping_mul(CITY_K1), CITY_K0);
+    z = z.wrapping_add(city_shift_mix(v.1).wrapping_mul(CITY_K1));
+    x = z.wrapping_add(x).rotate_right(39).wrapping_mul(CITY_K1);
+    y = y.rotate_right(33).wrapping_mul(CITY_K1);
+
+    for offset in (0..(len - 1) & !63).step_by(64) {
+        x = x
+            .wrapping_add(y)
+            .wrapping_add(v.0)
+            .wrapping_add(city_fetch64(data, offset + 16))
+            .rotate_right(37)
+            .wrapping_mul(CITY_K1);
+        y = y
+            .wrapping_add(v.1)
+            .wrapping_add(city_fetch64(data, offset + 48))
+            .rotate_right(42)
+            .wrapping_mul(CITY_K1);
+        x ^= w.1;
+        y ^= v.0;
+        z = (z ^ w.0).rotate_right(33);
+        v = city_weak_len32(data, offset, v.1.wrapping_mul(CITY_K1), x.wrapping_add(w.0));
+        w = city_weak_len32(data, offset + 32, z.wrapping_add(w.1), y);
+        std::mem::swap(&mut z, &mut x);
+    }
+    city_len16(
+        city_len16(v.0, w.0, CITY_KMUL)
+            .wrapping_add(city_shift_mix(y).wrapping_mul(CITY_K1))
+            .wrapping_add(z),
+        city_len16(v.1, w.1, CITY_KMUL).wrapping_add(x),
+        CITY_KMUL,
+    )
+}
+
+/// Parse a decimal or `0x`-prefixed hex seed of at most `bits` bits
+fn parse_seed(seed: &str, bits: u32) -> Result<u64, String> {
+    let seed = seed.trim();
+    let parsed = match seed.strip_prefix("0x").or_else(|| seed.strip_prefix("0X")) {
+        Some(digits) => u64::from_str_radix(digits, 16),
+        None if seed.is_empty() => Ok(0),
+        None => seed.parse::<u64>(),
+    }
+    .map_err(|e| format!("Seed: {}", e))?;
+
+    if bits < 64 && parsed >> bits != 0 {
+        return Err(format!("Seed must fit in {} bits", bits));
+    }
+    Ok(parsed)
+}
+
+/// Parse a hex key of exactly `N` bytes
+fn parse_key<const N: usize>(key: &str) -> Result<[u8; N], String> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Ok([0; N]);
+    }
+    hex::decode(key)
+        .map_err(|e| format!("Key: {}", e))?
+        .try_into()
+        .map_err(|_| format!("Key must be {} bytes ({} hex digits)", N, N * 2))
+}
+
+fn highway_hasher(key: &str, data: &[u8]) -> Result<HighwayHasher, String> {
+    let key = parse_key::<32>(key)?;
+    let mut words = [0u64; 4];
+    for (word, chunk) in words.iter_mut().zip(key.chunks(8)) {
+        *word = u64::from_le_bytes(chunk.try_into().unwrap());
+    }
+
+    let mut hasher = HighwayHasher::new(Key(words));
+    hasher.append(data);
+    Ok(hasher)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FOX: &[u8] = b"The quick brown fox jumps over the lazy dog";
+
+    fn hash_hex(algorithm: FastHashAlgorithm, data: &[u8], seed: &str) -> String {
+        hex::encode(algorithm.hash(data, seed).unwrap())
+    }
+
+    /// Test input from CityHash's `city-test.cc` in v1.1
+    fn city_data() -> Vec<u8> {
+        let (mut a, mut b) = (9u64, 777u64);
+        (0..1 << 16)
+            .map(|i| {
+                a = a.wrapping_add(b);
+                b = b.wrapping_add(a);
+                a = (a ^ (a >> 41)).wrapping_mul(CITY_K0);
+                b = (b ^ (b >> 41)).wrapping_mul(CITY_K0).wrapping_add(i);
+                (b >> 37) as u8
+            })
+            .collect()
+    }
+
+    /// Test input from CityHash's `city-test.cc` in v1.0.2
+    fn city_v102_data() -> Vec<u8> {
+        let (mut a, mut b) = (9u64, 777u64);
+        (0..1 << 16)
+            .map(|i| {
+                a = (a ^ (a >> 41)).wrapping_mul(CITY_K0).wrapping_add(b);
+                b = (b ^ (b >> 41)).wrapping_mul(CITY_K0).wrapping_add(i);
+                (b >> 37) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn xxhash() {
+        assert_eq!(hash_hex(FastHashAlgorithm::Xxh32, b"", ""), "02cc5d05");
+        assert_eq!(
+            hash_hex(FastHashAlgorithm::Xxh64, b"", ""),
+            "ef46db3751d8e999"
+        );
+        assert_eq!(
+            hash_hex(FastHashAlgorithm::Xxh3_64, b"", ""),
+            "2d06800538d394c2"
+        );
+        assert_eq!(
+            hash_hex(FastHashAlgorithm::Xxh3_128, b"", ""),
+            "99aa06d3014798d86001c324468d497f"
+        );
+    }
+
+    #[test]
+    fn murmur3() {
+        assert_eq!(hash_hex(FastHashAlgorithm::Murmur3_32, b"", ""), "00000000");
+        assert_eq!(
+            hash_hex(FastHashAlgorithm::Murmur3_32, b"", "1"),
+            "514e28b7"
+        );
+        assert_eq!(
+            hash_hex(FastHashAlgorithm::Murmur3_32, FOX, "0x9747b28c"),
+            "2fa826cd"
+        );
+        // MurmurHash3_x64_128 writes h1 then h2, each little-endian:
+        // 6c1b07bc7bbc4be347939ac4a93c437a
+        assert_eq!(
+            hash_hex(FastHashAlgorithm::Murmur3_128, FOX, ""),
+            "7a433ca9c49a9347e34bbc7bbc071b6c"
+        );
+    }
+
+    #[test]
+    fn fnv() {
+        assert_eq!(hash_hex(FastHashAlgorithm::Fnv1_32, b"a", ""), "050c5d7e");
+        assert_eq!(hash_hex(FastHashAlgorithm::Fnv1a32, b"a", ""), "e40c292c");
+        assert_eq!(
+            hash_hex(FastHashAlgorithm::Fnv1_64, b"a", ""),
+            "af63bd4c8601b7be"
+        );
+        assert_eq!(
+            hash_hex(FastHashAlgorithm::Fnv1a64, b"a", ""),
+            "af63dc4c8601ec8c"
+        );
+    }
+
+    #[test]
+    fn siphash() {
+        let key = hex::encode((0..16).collect::<Vec<u8>>());
+        let data = (0..15).collect::<Vec<u8>>();
+
+        // vectors.h lists vectors_sip64[15] as e5 45 be 49 61 ca 29 a1
+        assert_eq!(
+            hash_hex(FastHashAlgorithm::SipHash24, &data, &key),
+            "a129ca6149be45e5"
+        );
+        // and vectors_sip128[0] as a3 81 7f 04 ba 25 a8 e6 6d f6 72 14 c7 55 02 93
+        assert_eq!(
+            hash_hex(FastHashAlgorithm::SipHash24_128, b"", &key),
+            "930255c71472f66de6a825ba047f81a3"
+        );
+    }
+
+    #[test]
+    fn highwayhash() {
+        // The reference tests use key bytes 0 to 31 and list results as words
+        let key = hex::encode((0..32).collect::<Vec<u8>>());
+
+        assert_eq!(
+            hash_hex(FastHashAlgorithm::HighwayHash64, b"", &key),
+            "907a56de22c26e53"
+        );
+        assert_eq!(
+            hash_hex(FastHashAlgorithm::HighwayHash128, b"", &key),
+            "33565e767f093e6f0fed268f9d8ffec7"
+        );
+        assert_eq!(
+            hash_hex(FastHashAlgorithm::HighwayHash256, b"", &key),
+            "41da233145751df4b3aebeccb98714ffd946017313c7351fdd44482ac2c874f5"
+        );
+    }
+
+    #[test]
+    fn cityhash64() {
+        let data = city_data();
+        let input = |i: usize| &data[i * i..i * i + i];
+
+        assert_eq!(super::cityhash64(input(0)), 0x9ae16a3b2f90404f);
+        assert_eq!(super::cityhash64(input(3)), 0xef923a7a1af78eab);
+        assert_eq!(super::cityhash64(input(10)), 0x236827beae282a46);
+        assert_eq!(super::cityhash64(input(20)), 0x4182832b52d63735);
+        assert_eq!(super::cityhash64(input(40)), 0x4ec0b54cf1566aff);
+        assert_eq!(super::cityhash64(input(100)), 0x6369163565814de6);
+        assert_eq!(super::cityhash64(input(250)), 0xcd80dea24321eea4);
+        assert_eq!(
+            hash_hex(FastHashAlgorithm::CityHash64, input(3), ""),
+            "ef923a7a1af78eab"
+        );
+    }
+
+    #[test]
+    fn cityhash64_v102() {
+        let data = city_v102_data();
+        let input = |i: usize| &data[i * i..i * i + i];
+
+        assert_eq!(super::cityhash64_v102(input(3)), 0x69cfe9fca1cc683a);
+        assert_eq!(super::cityhash64_v102(input(10)), 0x172c17ff21dbf88d);
+        assert_eq!(super::cityhash64_v102(input(20)), 0x4dabcb5c1d382e5c);
+        assert_eq!(super::cityhash64_v102(input(40)), 0x05cc268bac4bd55f);
+        assert_eq!(super::cityhash64_v102(input(100)), 0x1e0ee26b7044741b);
+        assert_eq!(super::cityhash64_v102(input(250)), 0x6d56acb61a9abe8e);
+        assert_eq!(
+            hash_hex(FastHashAlgorithm::CityHash64V102, input(3), ""),
+            "69cfe9fca1cc683a"
+        );
+    }
+
+    #[test]
+    fn cityhash128_puts_high64_first() {
+        let data = city_data();
+        assert_eq!(
+            hash_hex(FastHashAlgorithm::CityHash128, &data[9..12], ""),
+            "8b6a8ff06cda83022193fb7620cbf23b"
+        );
+
+        let data = city_v102_data();
+        assert_eq!(
+            hash_hex(FastHashAlgorithm::CityHash128V102, &data[9..12], ""),
+            "4ebe60df371ec1298f52532fc6f005b7"
+        );
+    }
+}
@@ -0,0 +1,305 @@
+use super::{digests_match, lm_hash, nt_hash, HashAlgorithm};
+
+/// A possible origin for a hash string
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct HashCandidate {
+    /// Algorithm or scheme name
+    pub name: String,
+    /// How the value is encoded or structured
+    pub format: &'static str,
+    /// Matching entry in the digest registry, if it can be recomputed
+    pub algorithm: Option<HashAlgorithm>,
+    /// Password hash outside the registry that can be recomputed
+    pub password_digest: Option<PasswordDigest>,
+    /// The raw digest extracted from the string, if it is unsalted
+    pub digest: Option<Vec<u8>>,
+}
+
+impl HashCandidate {
+    fn new(name: &str, format: &'static str) -> Self {
+        HashCandidate {
+            name: name.to_string(),
+            format,
+            algorithm: None,
+            password_digest: None,
+            digest: None,
+        }
+    }
+
+    fn digest(algorithm: HashAlgorithm, format: &'static str, digest: &[u8]) -> Self {
+        HashCandidate {
+            name: algorithm.name().to_string(),
+            format,
+            algorithm: Some(algorithm),
+            password_digest: None,
+            digest: Some(digest.to_vec()),
+        }
+    }
+
+    /// Whether this candidate reproduces the hash from `plaintext`.
+    ///
+    /// Returns `None` for salted or otherwise unsupported schemes.
+    pub fn test(&self, plaintext: &str) -> Option<bool> {
+        let digest = self.digest.as_ref()?;
+        let computed = match (self.algorithm, self.password_digest) {
+            (Some(algorithm), _) => algorithm.digest(plaintext.as_bytes()),
+            (None, Some(password_digest)) => password_digest.digest(plaintext),
+            (None, None) => return None,
+        };
+        Some(digests_match(digest, &computed))
+    }
+}
+
+/// Unsalted password hashes that aren't in the digest registry
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum PasswordDigest {
+    Nt,
+    Lm,
+}
+
+impl PasswordDigest {
+    /// Empty when the password can't be hashed, such as LM for over 14 characters
+    fn digest(&self, password: &str) -> Vec<u8> {
+        match self {
+            PasswordDigest::Nt => nt_hash(password),
+            PasswordDigest::Lm => lm_hash(password).unwrap_or_default(),
+        }
+    }
+}
+
+/// Crypt-style `$id$` prefixes
+const CRYPT_SCHEMES: &[(&str, &str)] = &[
+    ("$1$", "MD5-crypt"),
+    ("$2$", "bcrypt"),
+    ("$2a$", "bcrypt"),
+    ("$2b$", "bcrypt"),
+    ("$2x$", "bcrypt"),
+    ("$2y$", "bcrypt"),
+    ("$3$", "NT hash (crypt)"),
+    ("$5$", "SHA-256-crypt"),
+    ("$6$", "SHA-512-crypt"),
+    ("$7$", "scrypt (crypt)"),
+    ("$y$", "yescrypt"),
+    ("$gy$", "gost-yescrypt"),
+    ("$sha1$", "SHA-1-crypt (NetBSD)"),
+    ("$md5$", "Sun MD5-crypt"),
+    ("$apr1$", "Apache MD5 (apr1)"),
+    ("$argon2id$", "Argon2id"),
+    ("$argon2i$", "Argon2i"),
+    ("$argon2d$", "Argon2d"),
+    ("$scrypt$", "scrypt (PHC)"),
+    ("$pbkdf2$", "PBKDF2-SHA1 (passlib)"),
+    ("$pbkdf2-sha256$", "PBKDF2-SHA256 (passlib)"),
+    ("$pbkdf2-sha512$", "PBKDF2-SHA512 (passlib)"),
+    ("$P$", "phpass (WordPress)"),
+    ("$H$", "phpass (phpBB)"),
+];
+
+/// Django `algorithm$...` prefixes
+const DJANGO_SCHEMES: &[(&str, &str)] = &[
+    ("pbkdf2_sha256$", "PBKDF2-SHA256"),
+    ("pbkdf2_sha1$", "PBKDF2-SHA1"),
+    ("argon2$", "Argon2"),
+    ("bcrypt_sha256$", "bcrypt with SHA-256"),
+    ("bcrypt$", "bcrypt"),
+    ("scrypt$", "scrypt"),
+    ("sha1$", "Salted SHA-1"),
+    ("md5$", "Salted MD5"),
+    ("unsalted_md5$", "MD5"),
+    ("unsalted_sha1$", "SHA-1"),
+];
+
+/// Werkzeug `method:params$salt$hash` prefixes
+const WERKZEUG_SCHEMES: &[(&str, &str)] = &[
+    ("pbkdf2:sha256", "PBKDF2-SHA256"),
+    ("pbkdf2:sha512", "PBKDF2-SHA512"),
+    ("pbkdf2:sha1", "PBKDF2-SHA1"),
+    ("scrypt:", "scrypt"),
+];
+
+/// LDAP `{SCHEME}` prefixes, with the registry algorithm of unsalted schemes
+const LDAP_SCHEMES: &[(&str, &str, Option<HashAlgorithm>)] = &[
+    ("{SHA}", "SHA-1", Some(HashAlgorithm::Sha1)),
+    ("{SHA256}", "SHA-256", Some(HashAlgorithm::Sha256)),
+    ("{SHA384}", "SHA-384", Some(HashAlgorithm::Sha384)),
+    ("{SHA512}", "SHA-512", Some(HashAlgorithm::Sha512)),
+    ("{MD5}", "MD5", Some(HashAlgorithm::Md5)),
+    ("{SSHA}", "Salted SHA-1", None),
+    ("{SSHA256}", "Salted SHA-256", None),
+    ("{SSHA384}", "Salted SHA-384", None),
+    ("{SSHA512}", "Salted SHA-512", None),
+    ("{SMD5}", "Salted MD5", None),
+    ("{CRYPT}", "crypt(3)", None),
+    ("{PBKDF2}", "PBKDF2-SHA1", None),
+    ("{PBKDF2-SHA256}", "PBKDF2-SHA256", None),
+    ("{PBKDF2-SHA512}", "PBKDF2-SHA512", None),
+    ("{ARGON2}", "Argon2", None),
+];
+
+/// Subresource Integrity `algo-` prefixes
+const SRI_SCHEMES: &[(&str, HashAlgorithm)] = &[
+    ("sha256-", HashAlgorithm::Sha256),
+    ("sha384-", HashAlgorithm::Sha384),
+    ("sha512-", HashAlgorithm::Sha512),
+];
+
+/// Hex digests that aren't in the registry, keyed by length in bytes, with
+/// the password hash that recomputes them
+const OTHER_HEX_DIGESTS: &[(usize, &str, Option<PasswordDigest>)] = &[
+    (4, "CRC-32", None),
+    (4, "Adler-32", None),
+    (8, "CRC-64", None),
+    (8, "MySQL OLD_PASSWORD()", None),
+    (16, "NTLM", Some(PasswordDigest::Nt)),
+    (16, "LM", Some(PasswordDigest::Lm)),
+    (16, "RIPEMD-128", None),
+    (20, "MySQL PASSWORD() without '*'", None),
+];
+
+/// Guess which algorithms and formats could have produced `hash`.
+///
+/// Candidates are ordered roughly from most to least likely.
+pub fn identify_hash(hash: &str) -> Vec<HashCandidate> {
+    let hash = hash.trim();
+    let mut candidates = Vec::new();
+    if hash.is_empty() {
+        return candidates;
+    }
+
+    if let Some((_, name)) = CRYPT_SCHEMES
+        .iter()
+        .filter(|(prefix, _)| hash.starts_with(prefix))
+        .max_by_key(|(prefix, _)| prefix.len())
+    {
+        candidates.push(HashCandidate::new(name, "crypt(3) / PHC string"));
+    }
+
+    for (prefix, name) in DJANGO_SCHEMES {
+        if hash.starts_with(prefix) {
+            candidates.push(HashCandidate::new(name, "Django"));
+            break;
+        }
+    }
+
+    for (prefix, name) in WERKZEUG_SCHEMES {
+        if hash.starts_with(prefix) && hash.contains('$') {
+            candidates.push(HashCandidate::new(name, "Werkzeug"));
+            break;
+        }
+    }
+
+    let upper = hash.to_ascii_uppercase();
+    if let Some((prefix, name, algorithm)) = LDAP_SCHEMES
+        .iter()
+        .find(|(prefix, _, _)| upper.starts_with(prefix))
+    {
+        let mut candidate = HashCandidate::new(name, "LDAP");
+        if let Some(algorithm) = algorithm {
+            if let Ok(digest) = base64::decode(&hash[prefix.len()..]) {
+                candidate = HashCandidate::digest(*algorithm, "LDAP", &digest);
+            }
+        }
+        candidates.push(candidate);
+    }
+
+    for (prefix, algorithm) in SRI_SCHEMES {
+        if let Some(digest) = hash
+            .strip_prefix(prefix)
+            .and_then(|rest| base64::decode(rest).ok())
+        {
+            candidates.push(HashCandidate::digest(
+                *algorithm,
+                "Subresource Integrity",
+                &digest,
+            ));
+        }
+    }
+
+    if let Some(rest) = hash.strip_prefix("SCRAM-SHA-256$") {
+        if rest.contains(':') {
+            candidates.push(HashCandidate::new("SCRAM-SHA-256", "PostgreSQL"));
+        }
+    }
+    if let Some(rest) = hash.strip_prefix("md5") {
+        if is_hex(rest, 32) {
+            candidates.push(HashCandidate::new(
+                "MD5 (password + username)",
+                "PostgreSQL",
+            ));
+        }
+    }
+    if let Some(rest) = hash.strip_prefix('*') {
+        if is_hex(rest, 40) {
+            candidates.push(HashCandidate::new("MySQL 4.1+ PASSWORD()", "MySQL"));
+        }
+    }
+
+    if let Ok(digest) = hex::decode(hash) {
+        candidates.extend(candidates_for_digest(&digest, "Hex"));
+    } else if hash.len() == 13 && hash.chars().all(is_crypt_char) {
+        candidates.push(HashCandidate::new("DES crypt", "crypt(3)"));
+    } else if candidates.is_empty() {
+        if let Ok(digest) = base64::decode(hash) {
+            candidates.extend(candidates_for_digest(&digest, "Base64"));
+        } else if let Ok(digest) = base64::decode_config(hash, base64::URL_SAFE_NO_PAD) {
+            candidates.extend(candidates_for_digest(&digest, "Base64url"));
+        }
+    }
+
+    candidates
+}
+
+fn candidates_for_digest(digest: &[u8], format: &'static str) -> Vec<HashCandidate> {
+    let mut candidates: Vec<HashCandidate> = HashAlgorithm::with_output_size(digest.len())
+        .into_iter()
+        .map(|algorithm| HashCandidate::digest(algorithm, format, digest))
+        .collect();
+    candidates.extend(
+        OTHER_HEX_DIGESTS
+            .iter()
+            .filter(|(size, _, _)| *size == digest.len())
+            .map(|(_, name, password_digest)| HashCandidate {
+                password_digest: *password_digest,
+                digest: password_digest.map(|_| digest.to_vec()),
+                ..HashCandidate::new(name, format)
+            }),
+    );
+    candidates
+}
+
+fn is_hex(text: &str, len: usize) -> bool {
+    text.len() == len && text.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn is_crypt_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '.' || c == '/'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_candidate(hash: &str, name: &str, plaintext: &str) -> Option<bool> {
+        identify_hash(hash)
+            .into_iter()
+            .find(|candidate| candidate.name == name)?
+            .test(plaintext)
+    }
+
+    #[test]
+    fn md5_ranks_first_for_32_hex_chars() {
+        let candidates = identify_hash("900150983cd24fb0d6963f7d28e17f72");
+        assert_eq!(candidates[0].name, "MD5");
+    }
+
+    #[test]
+    fn windows_hashes_are_testable() {
+        let nt = "8846f7eaee8fb117ad06bdd830b7586c";
+        assert_eq!(test_candidate(nt, "NTLM", "password"), Some(true));
+        assert_eq!(test_candidate(nt, "NTLM", "Password"), Some(false));
+        let lm = "e52cac67419a9a224a3b108f3fa6cb6d";
+        assert_eq!(test_candidate(lm, "LM", "PassWord"), Some(true));
+        assert_eq!(test_candidate(lm, "LM", "a password over 14"), Some(false));
+        assert_eq!(test_candidate(lm, "RIPEMD-128", "password"), None);
+    }
+}
@@ -0,0 +1,165 @@
+//! Key derivation functions producing raw key bytes
+
+use argon2::Argon2;
+use serde::{Deserialize, Serialize};
+
+use super::{hkdf_expand, hkdf_extract, pbkdf2_digest, Argon2Variant, HashAlgorithm};
+
+/// Largest key that can be derived, in bytes
+pub const MAX_KDF_LENGTH: usize = 4096;
+
+/// A key derivation function together with its parameters
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+pub enum KdfAlgorithm {
+    Hkdf {
+        hash: HashAlgorithm,
+        info: Vec<u8>,
+    },
+    Pbkdf2 {
+        hash: HashAlgorithm,
+        iterations: u32,
+    },
+    Scrypt {
+        log_n: u8,
+        r: u32,
+        p: u32,
+    },
+    Argon2 {
+        variant: Argon2Variant,
+        /// Memory in KiB
+        memory: u32,
+        iterations: u32,
+        parallelism: u32,
+    },
+}
+
+impl KdfAlgorithm {
+    /// Derive `length` bytes from `secret` and `salt`.
+    ///
+    /// scrypt and Argon2 are deliberately slow, so should be run off the UI
+    /// thread.
+    pub fn derive(&self, secret: &[u8], salt: &[u8], length: usize) -> Result<Vec<u8>, String> {
+        if !(1..=MAX_KDF_LENGTH).contains(&length) {
+            return Err(format!(
+                "Output length must be between 1 and {}",
+                MAX_KDF_LENGTH
+            ));
+        }
+        match self {
+            KdfAlgorithm::Hkdf { hash, info } => {
+                let prk = hkdf_extract(*hash, salt, secret)?;
+                hkdf_expand(*hash, &prk, info, length)
+            }
+            KdfAlgorithm::Pbkdf2 { hash, iterations } => {
+                pbkdf2_digest(*hash, secret, salt, *iterations, length)
+            }
+            KdfAlgorithm::Scrypt { log_n, r, p } => {
+                // The length here only matters for PHC strings
+                let params = scrypt::Params::new(*log_n, *r, *p, scrypt::Params::RECOMMENDED_LEN)
+                    .map_err(|_| "Invalid scrypt parameters".to_string())?;
+                let mut output = vec![0; length];
+                scrypt::scrypt(secret, salt, &params, &mut output).map_err(|e| e.to_string())?;
+                Ok(output)
+            }
+            KdfAlgorithm::Argon2 {
+                variant,
+                memory,
+                iterations,
+                parallelism,
+            } => {
+                let algorithm = match variant {
+                    Argon2Variant::Argon2d => argon2::Algorithm::Argon2d,
+                    Argon2Variant::Argon2i => argon2::Algorithm::Argon2i,
+                    Argon2Variant::Argon2id => argon2::Algorithm::Argon2id,
+                };
+                let params = argon2::Params::new(*memory, *iterations, *parallelism, Some(length))
+                    .map_err(|e| e.to_string())?;
+                let mut output = vec![0; length];
+                Argon2::new(algorithm, argon2::Version::V0x13, params)
+                    .hash_password_into(secret, salt, &mut output)
+                    .map_err(|e| e.to_string())?;
+                Ok(output)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hkdf(hash: HashAlgorithm, ikm: &[u8], salt: &[u8], info: &[u8], length: usize) -> String {
+        let kdf = KdfAlgorithm::Hkdf {
+            hash,
+            info: info.to_vec(),
+        };
+        hex::encode(kdf.derive(ikm, salt, length).unwrap())
+    }
+
+    fn pbkdf2(password: &[u8], salt: &[u8], iterations: u32, length: usize) -> String {
+        let kdf = KdfAlgorithm::Pbkdf2 {
+            hash: HashAlgorithm::Sha1,
+            iterations,
+        };
+        hex::encode(kdf.derive(password, salt, length).unwrap())
+    }
+
+    #[test]
+    fn hkdf_rfc5869() {
+        let ikm = [0x0b; 22];
+        let salt: Vec<u8> = (0x00..=0x0c).collect();
+        let info: Vec<u8> = (0xf0..=0xf9).collect();
+        // Test case 1
+        assert_eq!(
+            hkdf(HashAlgorithm::Sha256, &ikm, &salt, &info, 42),
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+        );
+        // Test case 3, with no salt or info
+        assert_eq!(
+            hkdf(HashAlgorithm::Sha256, &ikm, &[], &[], 42),
+            "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8"
+        );
+    }
+
+    #[test]
+    fn pbkdf2_rfc6070() {
+        assert_eq!(
+            pbkdf2(b"password", b"salt", 1, 20),
+            "0c60c80f961f0e71f3a9b524af6012062fe037a6"
+        );
+        assert_eq!(
+            pbkdf2(b"password", b"salt", 2, 20),
+            "ea6c014dc72d6f8ccd1ed92ace1d41f0d8de8957"
+        );
+        assert_eq!(
+            pbkdf2(b"password", b"salt", 4096, 20),
+            "4b007901b765489abead49d926f721d065a429c1"
+        );
+        assert_eq!(
+            pbkdf2(
+                b"passwordPASSWORDpassword",
+                b"saltSALTsaltSALTsaltSALTsaltSALTsalt",
+                4096,
+                25
+            ),
+            "3d2eec4fe41c849b80c8d83662c0e44a8b291a964cf2f07038"
+        );
+        assert_eq!(
+            pbkdf2(b"pass\0word", b"sa\0lt", 4096, 16),
+            "56fa6aa75548099dcc37d7f03425e0c3"
+        );
+    }
+
+    #[test]
+    fn keyed_hashes_are_rejected() {
+        for hash in [HashAlgorithm::Blake2b512, HashAlgorithm::Blake3] {
+            let hkdf = KdfAlgorithm::Hkdf { hash, info: vec![] };
+            assert!(hkdf.derive(b"secret", b"salt", 32).is_err());
+            let pbkdf2 = KdfAlgorithm::Pbkdf2 {
+                hash,
+                iterations: 1,
+            };
+            assert!(pbkdf2.derive(b"secret", b"salt", 32).is_err());
+        }
+    }
+}
@@ -0,0 +1,454 @@
+//! Key pair generation, exported as PKCS#8/SPKI, JWK and OpenSSH
+
+use elliptic_curve::pkcs8::{AssociatedOid, EncodePrivateKey, EncodePublicKey};
+use elliptic_curve::sec1::{FromEncodedPoint, ModulusSize, ToEncodedPoint};
+use elliptic_curve::{AffinePoint, CurveArithmetic, FieldBytesSize, JwkParameters, SecretKey};
+use password_hash::rand_core::OsRng;
+use rsa::traits::{PrivateKeyParts, PublicKeyParts};
+use rsa::{BigUint, RsaPrivateKey};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use ssh_key::private::{EcdsaKeypair, Ed25519Keypair, KeypairData, RsaKeypair};
+use ssh_key::{LineEnding, PrivateKey};
+
+use super::ssh::{md5_fingerprint, sha256_fingerprint};
+use super::{openssl_base64, sha256_hash};
+
+/// PKCS#8, SPKI, private JWK and the key for OpenSSH export
+type KeyParts = (Vec<u8>, Vec<u8>, Value, Option<KeypairData>);
+
+/// Last byte of the Ed25519 and X25519 object identifiers (RFC 8410)
+const ED25519_OID_END: u8 = 0x70;
+const X25519_OID_END: u8 = 0x6e;
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum KeyType {
+    #[default]
+    Ed25519,
+    X25519,
+    P256,
+    P384,
+    Secp256k1,
+    Rsa2048,
+    Rsa3072,
+    Rsa4096,
+}
+
+impl KeyType {
+    pub const ALL: [KeyType; 8] = [
+        KeyType::Ed25519,
+        KeyType::X25519,
+        KeyType::P256,
+        KeyType::P384,
+        KeyType::Secp256k1,
+        KeyType::Rsa2048,
+        KeyType::Rsa3072,
+        KeyType::Rsa4096,
+    ];
+
+    /// Stable identifier, used for form values
+    pub fn id(&self) -> &'static str {
+        match self {
+            KeyType::Ed25519 => "ed25519",
+            KeyType::X25519 => "x25519",
+            KeyType::P256 => "p256",
+            KeyType::P384 => "p384",
+            KeyType::Secp256k1 => "secp256k1",
+            KeyType::Rsa2048 => "rsa2048",
+            KeyType::Rsa3072 => "rsa3072",
+            KeyType::Rsa4096 => "rsa4096",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|key_type| key_type.id() == id)
+    }
+
+    /// Human readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyType::Ed25519 => "Ed25519",
+            KeyType::X25519 => "X25519",
+            KeyType::P256 => "P-256",
+            KeyType::P384 => "P-384",
+            KeyType::Secp256k1 => "secp256k1",
+            KeyType::Rsa2048 => "RSA 2048",
+            KeyType::Rsa3072 => "RSA 3072",
+            KeyType::Rsa4096 => "RSA 4096",
+        }
+    }
+
+    fn rsa_bits(&self) -> Option<usize> {
+        match self {
+            KeyType::Rsa2048 => Some(2048),
+            KeyType::Rsa3072 => Some(3072),
+            KeyType::Rsa4096 => Some(4096),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default)]
+pub enum KeyFormat {
+    #[default]
+    Pem,
+    DerHex,
+    DerBase64,
+    Jwk,
+    OpenSsh,
+}
+
+impl KeyFormat {
+    pub const ALL: [KeyFormat; 5] = [
+        KeyFormat::Pem,
+        KeyFormat::DerHex,
+        KeyFormat::DerBase64,
+        KeyFormat::Jwk,
+        KeyFormat::OpenSsh,
+    ];
+
+    /// Stable identifier, used for form values
+    pub fn id(&self) -> &'static str {
+        match self {
+            KeyFormat::Pem => "pem",
+            KeyFormat::DerHex => "der-hex",
+            KeyFormat::DerBase64 => "der-base64",
+            KeyFormat::Jwk => "jwk",
+            KeyFormat::OpenSsh => "openssh",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|format| format.id() == id)
+    }
+
+    /// Human readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyFormat::Pem => "PEM (PKCS#8/SPKI)",
+            KeyFormat::DerHex => "DER (hex)",
+            KeyFormat::DerBase64 => "DER (Base64)",
+            KeyFormat::Jwk => "JWK",
+            KeyFormat::OpenSsh => "OpenSSH",
+        }
+    }
+}
+
+/// A generated key pair in every export format
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+pub struct KeyPair {
+    pub key_type: KeyType,
+    /// PKCS#8
+    pub private_der: Vec<u8>,
+    /// SubjectPublicKeyInfo
+    pub public_der: Vec<u8>,
+    pub private_jwk: String,
+    pub public_jwk: String,
+    /// `None` for key types OpenSSH doesn't support
+    pub private_openssh: Option<String>,
+    pub public_openssh: Option<String>,
+    pub fingerprints: KeyFingerprints,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+pub struct KeyFingerprints {
+    /// As `ssh-keygen -l` prints them
+    pub ssh_sha256: Option<String>,
+    pub ssh_md5: Option<String>,
+    /// Base64 SHA-256 of the SubjectPublicKeyInfo, as used for certificate
+    /// pinning
+    pub spki_sha256: String,
+    /// RFC 7638 JWK thumbprint, Base64url SHA-256
+    pub jwk_thumbprint: String,
+}
+
+impl KeyPair {
+    pub fn private_pem(&self) -> String {
+        to_pem("PRIVATE KEY", &self.private_der)
+    }
+
+    pub fn public_pem(&self) -> String {
+        to_pem("PUBLIC KEY", &self.public_der)
+    }
+
+    /// Private and public key in `format`
+    pub fn export(&self, format: KeyFormat) -> Result<(String, String), String> {
+        match format {
+            KeyFormat::Pem => Ok((self.private_pem(), self.public_pem())),
+            KeyFormat::DerHex => Ok((
+                hex::encode(&self.private_der),
+                hex::encode(&self.public_der),
+            )),
+            KeyFormat::DerBase64 => Ok((
+                base64::encode(&self.private_der),
+                base64::encode(&self.public_der),
+            )),
+            KeyFormat::Jwk => Ok((self.private_jwk.clone(), self.public_jwk.clone())),
+            KeyFormat::OpenSsh => match (&self.private_openssh, &self.public_openssh) {
+                (Some(private), Some(public)) => Ok((private.clone(), public.clone())),
+                _ => Err(format!(
+                    "OpenSSH doesn't support {} keys",
+                    self.key_type.name()
+                )),
+            },
+        }
+    }
+}
+
+/// A new key pair from the system's secure random number generator
+pub fn generate_key_pair(key_type: KeyType) -> Result<KeyPair, String> {
+    let (private_der, public_der, private_jwk, ssh_key) = match key_type {
+        KeyType::Ed25519 => {
+            let key = ed25519_dalek::SigningKey::generate(&mut OsRng);
+            let public = key.verifying_key();
+            let jwk = json!({
+                "kty": "OKP",
+                "crv": "Ed25519",
+                "x": base64url(public.as_bytes()),
+                "d": base64url(key.as_bytes()),
+            });
+            (
+                okp_pkcs8(ED25519_OID_END, key.as_bytes()),
+                okp_spki(ED25519_OID_END, public.as_bytes()),
+                jwk,
+                Some(KeypairData::from(Ed25519Keypair::from(&key))),
+            )
+        }
+        KeyType::X25519 => {
+            let secret = x25519_dalek::StaticSecret::random_from_rng(OsRng);
+            let public = x25519_dalek::PublicKey::from(&secret);
+            let jwk = json!({
+                "kty": "OKP",
+                "crv": "X25519",
+                "x": base64url(public.as_bytes()),
+                "d": base64url(secret.as_bytes()),
+            });
+            (
+                okp_pkcs8(X25519_OID_END, secret.as_bytes()),
+                okp_spki(X25519_OID_END, public.as_bytes()),
+                jwk,
+                None,
+            )
+        }
+        KeyType::P256 => {
+            let key = p256::SecretKey::random(&mut OsRng);
+            let ssh_key = EcdsaKeypair::NistP256 {
+                public: key.public_key().into(),
+                private: key.clone().into(),
+            };
+            ec_key_pair(&key, Some(KeypairData::from(ssh_key)))?
+        }
+        KeyType::P384 => {
+            let key = p384::SecretKey::random(&mut OsRng);
+            let ssh_key = EcdsaKeypair::NistP384 {
+                public: key.public_key().into(),
+                private: key.clone().into(),
+            };
+            ec_key_pair(&key, Some(KeypairData::from(ssh_key)))?
+        }
+        KeyType::Secp256k1 => ec_key_pair(&k256::SecretKey::random(&mut OsRng), None)?,
+        KeyType::Rsa2048 | KeyType::Rsa3072 | KeyType::Rsa4096 => {
+            let bits = key_type.rsa_bits().unwrap_or(2048);
+            let key = RsaPrivateKey::new(&mut OsRng, bits).map_err(|e| e.to_string())?;
+            let ssh_key = RsaKeypair::try_from(&key).map_err(|e| e.to_string())?;
+            (
+                key.to_pkcs8_der()
+                    .map_err(|e| e.to_string())?
+                    .as_bytes()
+                    .to_vec(),
+                key.to_public_key()
+                    .to_public_key_der()
+                    .map_err(|e| e.to_string())?
+                    .into_vec(),
+                rsa_jwk(&key)?,
+                Some(KeypairData::from(ssh_key)),
+            )
+        }
+    };
+
+    let public_jwk = public_jwk(&private_jwk);
+    let ssh_key = ssh_key
+        .map(|key_data| PrivateKey::new(key_data, "").map_err(|e| e.to_string()))
+        .transpose()?;
+    let (private_openssh, public_openssh, ssh_sha256, ssh_md5) = match ssh_key {
+        Some(key) => {
+            let public = key.public_key();
+            let blob = public.to_bytes().map_err(|e| e.to_string())?;
+            (
+                Some(
+                    key.to_openssh(LineEnding::LF)
+                        .map_err(|e| e.to_string())?
+                        .to_string(),
+                ),
+                Some(public.to_openssh().map_err(|e| e.to_string())?),
+                Some(sha256_fingerprint(&blob)),
+                Some(md5_fingerprint(&blob)),
+            )
+        }
+        None => (None, None, None, None),
+    };
+
+    Ok(KeyPair {
+        key_type,
+        fingerprints: KeyFingerprints {
+            ssh_sha256,
+            ssh_md5,
+            spki_sha256: base64::encode(sha256_hash(&public_der)),
+            jwk_thumbprint: jwk_thumbprint(&public_jwk),
+        },
+        private_der,
+        public_der,
+        private_jwk: pretty_json(&private_jwk),
+        public_jwk: pretty_json(&public_jwk),
+        private_openssh,
+        public_openssh,
+    })
+}
+
+/// Exports of an elliptic curve key
+fn ec_key_pair<C>(key: &SecretKey<C>, ssh_key: Option<KeypairData>) -> Result<KeyParts, String>
+where
+    C: CurveArithmetic + AssociatedOid + JwkParameters,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    let public = key.public_key();
+    let point = public.to_encoded_point(false);
+    let (Some(x), Some(y)) = (point.x(), point.y()) else {
+        return Err("Invalid public key".to_string());
+    };
+    let jwk = json!({
+        "kty": "EC",
+        "crv": C::CRV,
+        "x": base64url(x),
+        "y": base64url(y),
+        "d": base64url(&key.to_bytes()),
+    });
+    Ok((
+        key.to_pkcs8_der()
+            .map_err(|e| e.to_string())?
+            .as_bytes()
+            .to_vec(),
+        public
+            .to_public_key_der()
+            .map_err(|e| e.to_string())?
+            .into_vec(),
+        jwk,
+        ssh_key,
+    ))
+}
+
+/// Version 1 PKCS#8 without the optional public key, as `openssl genpkey`
+/// writes it. X25519 isn't in the `pkcs8` crates, and Ed25519's version 2
+/// output is rejected by some readers.
+fn okp_pkcs8(oid_end: u8, private_key: &[u8; 32]) -> Vec<u8> {
+    let prefix = [
+        0x30, 0x2e, 0x02, 0x01, 0x00, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, oid_end, 0x04, 0x22,
+        0x04, 0x20,
+    ];
+    [&prefix[..], private_key].concat()
+}
+
+fn okp_spki(oid_end: u8, public_key: &[u8; 32]) -> Vec<u8> {
+    let prefix = [
+        0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, oid_end, 0x03, 0x21, 0x00,
+    ];
+    [&prefix[..], public_key].concat()
+}
+
+fn rsa_jwk(key: &RsaPrivateKey) -> Result<Value, String> {
+    let uint = |value: &BigUint| base64url(&value.to_bytes_be());
+    let (Some(dp), Some(dq), Some(qi)) = (key.dp(), key.dq(), key.crt_coefficient()) else {
+        return Err("RSA key has no CRT values".to_string());
+    };
+    let primes = key.primes();
+    Ok(json!({
+        "kty": "RSA",
+        "n": uint(key.n()),
+        "e": uint(key.e()),
+        "d": uint(key.d()),
+        "p": uint(&primes[0]),
+        "q": uint(&primes[1]),
+        "dp": uint(dp),
+        "dq": uint(dq),
+        "qi": uint(&qi),
+    }))
+}
+
+/// The JWK without its private members
+fn public_jwk(private_jwk: &Value) -> Value {
+    let mut jwk = private_jwk.clone();
+    if let Some(members) = jwk.as_object_mut() {
+        for member in ["d", "p", "q", "dp", "dq", "qi"] {
+            members.remove(member);
+        }
+    }
+    jwk
+}
+
+/// SHA-256 of the required members in lexicographic order, without
+/// whitespace (RFC 7638)
+fn jwk_thumbprint(jwk: &Value) -> String {
+    let required: &[&str] = match jwk["kty"].as_str() {
+        Some("RSA") => &["e", "kty", "n"],
+        Some("EC") => &["crv", "kty", "x", "y"],
+        _ => &["crv", "kty", "x"],
+    };
+    let members: Map<String, Value> = required
+        .iter()
+        .map(|member| (member.to_string(), jwk[*member].clone()))
+        .collect();
+    let json = Value::Object(members).to_string();
+    base64url(&sha256_hash(json.as_bytes()))
+}
+
+fn to_pem(label: &str, der: &[u8]) -> String {
+    format!(
+        "-----BEGIN {label}-----\n{}\n-----END {label}-----\n",
+        openssl_base64(der)
+    )
+}
+
+fn base64url(bytes: &[u8]) -> String {
+    base64::encode_config(bytes, base64::URL_SAFE_NO_PAD)
+}
+
+fn pretty_json(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn rfc7638_thumbprint() {
+        // RFC 7638 §3.1, with members the thumbprint leaves out
+        let jwk = json!({
+            "kty": "RSA",
+            "n": "0vx7agoebGcQSuuPiLJXZptN9nndrQmbXEps2aiAFbWhM78LhWx4cbbfAAtVT86zwu1RK7aPFFxuhDR1L6tSoc_BJECPebWKRXjBZCiFV4n3oknjhMstn64tZ_2W-5JsGY4Hc5n9yBXArwl93lqt7_RN5w6Cf0h4QyQ5v-65YGjQR0_FDW2QvzqY368QQMicAtaSqzs8KJZgnYb9c7d0zgdAZHzu6qMQvRL5hajrn1n91CbOpbISD08qNLyrdkt-bFTWhAI4vMQFh6WeZu0fM4lFd2NcRwr3XPksINHaQ-G_xBniIqbw0Ls1jF44-csFCur-kEgU8awapJzKnqDKgw",
+            "e": "AQAB",
+            "alg": "RS256",
+            "kid": "2011-04-29"
+        });
+        assert_eq!(
+            jwk_thumbprint(&jwk),
+            "NzbLsXh8uDCcd-6MNwXF4W_7noWXFZAfHkxZsRGC9Xs"
+        );
+    }
+
+    #[test]
+    fn rfc8037_thumbprint() {
+        // RFC 8037 appendix A.3
+        let jwk = json!({
+            "kty": "OKP",
+            "crv": "Ed25519",
+            "x": "11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"
+        });
+        assert_eq!(
+            jwk_thumbprint(&jwk),
+            "kPrK_qmxVWaYVA9wwBF6Iuo3vVzz7TxHCTwXBygrS4k"
+        );
+    }
+}
@@ -0,0 +1,312 @@
+//! Public and private keys in PEM, DER, JWK or raw form
+
+use elliptic_curve::generic_array::typenum::Unsigned;
+use elliptic_curve::pkcs8::{
+    AssociatedOid, DecodePrivateKey, DecodePublicKey, ObjectIdentifier, PrivateKeyInfo,
+    SubjectPublicKeyInfoRef,
+};
+use elliptic_curve::point::PointCompression;
+use elliptic_curve::sec1::{FromEncodedPoint, ModulusSize, ToEncodedPoint};
+use elliptic_curve::{
+    AffinePoint, CurveArithmetic, FieldBytesSize, JwkParameters, PublicKey, SecretKey,
+};
+use rsa::pkcs1::{DecodeRsaPrivateKey, DecodeRsaPublicKey};
+use rsa::{BigUint, RsaPrivateKey, RsaPublicKey};
+use serde_json::{Map, Value};
+
+use super::{decode_pem, parse_digest};
+
+/// Key text decoded as far as its encoding, before the key type is known
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum KeyData {
+    Pem {
+        label: String,
+        der: Vec<u8>,
+    },
+    Jwk(String, Map<String, Value>),
+    /// DER or a raw key, from hex or Base64
+    Bytes(Vec<u8>),
+}
+
+impl KeyData {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err("Key is required".to_string());
+        }
+        if text.starts_with("-----BEGIN ") {
+            let (label, der) = parse_pem(text)?;
+            return Ok(KeyData::Pem { label, der });
+        }
+        if text.starts_with('{') {
+            let jwk = serde_json::from_str(text).map_err(|e| format!("Invalid JWK: {}", e))?;
+            return Ok(KeyData::Jwk(text.to_string(), jwk));
+        }
+        parse_digest(text)
+            .map(KeyData::Bytes)
+            .map_err(|_| "Key is not PEM, JWK, hex or Base64".to_string())
+    }
+
+    fn der(&self) -> Option<&[u8]> {
+        match self {
+            KeyData::Pem { der, .. } | KeyData::Bytes(der) => Some(der),
+            KeyData::Jwk(..) => None,
+        }
+    }
+
+    fn jwk_field(&self, name: &str) -> Result<Option<Vec<u8>>, String> {
+        let KeyData::Jwk(_, jwk) = self else {
+            return Ok(None);
+        };
+        match jwk.get(name) {
+            Some(Value::String(value)) => {
+                base64::decode_config(value.trim_end_matches('='), base64::URL_SAFE_NO_PAD)
+                    .map(Some)
+                    .map_err(|_| format!("JWK \"{}\" is not Base64url", name))
+            }
+            Some(_) => Err(format!("JWK \"{}\" must be a string", name)),
+            None => Ok(None),
+        }
+    }
+}
+
+pub(super) enum Ed25519Key {
+    Private(ed25519_dalek::SigningKey),
+    Public(ed25519_dalek::VerifyingKey),
+}
+
+impl Ed25519Key {
+    /// 32 raw bytes are a private seed if `raw_private` is set, otherwise a
+    /// public key
+    pub(super) fn parse(data: &KeyData, raw_private: bool) -> Result<Self, String> {
+        if let KeyData::Jwk(_, jwk) = data {
+            if jwk.get("crv").and_then(Value::as_str) != Some("Ed25519") {
+                return Err("JWK is not an Ed25519 key".to_string());
+            }
+            return match (data.jwk_field("d")?, data.jwk_field("x")?) {
+                (Some(d), _) => ed25519_seed(&d).map(Ed25519Key::Private),
+                (None, Some(x)) => ed25519_public(&x).map(Ed25519Key::Public),
+                (None, None) => Err("JWK has no \"x\" or \"d\"".to_string()),
+            };
+        }
+
+        let der = data.der().unwrap_or_default();
+        if let Ok(key) = ed25519_dalek::SigningKey::from_pkcs8_der(der) {
+            return Ok(Ed25519Key::Private(key));
+        }
+        if let Ok(key) = ed25519_dalek::VerifyingKey::from_public_key_der(der) {
+            return Ok(Ed25519Key::Public(key));
+        }
+        match (data, der.len()) {
+            (KeyData::Bytes(raw), 32) if raw_private => ed25519_seed(raw).map(Ed25519Key::Private),
+            (KeyData::Bytes(raw), 32) => ed25519_public(raw).map(Ed25519Key::Public),
+            // Seed followed by the public key, as NaCl and libsodium store it
+            (KeyData::Bytes(raw), 64) => ed25519_seed(&raw[..32]).map(Ed25519Key::Private),
+            _ => Err("Not an Ed25519 key".to_string()),
+        }
+    }
+
+    pub(super) fn public_key(&self) -> ed25519_dalek::VerifyingKey {
+        match self {
+            Ed25519Key::Private(key) => key.verifying_key(),
+            Ed25519Key::Public(key) => *key,
+        }
+    }
+}
+
+fn ed25519_seed(seed: &[u8]) -> Result<ed25519_dalek::SigningKey, String> {
+    let seed = seed
+        .try_into()
+        .map_err(|_| "Ed25519 private key must be 32 bytes".to_string())?;
+    Ok(ed25519_dalek::SigningKey::from_bytes(seed))
+}
+
+fn ed25519_public(bytes: &[u8]) -> Result<ed25519_dalek::VerifyingKey, String> {
+    let bytes = bytes
+        .try_into()
+        .map_err(|_| "Ed25519 public key must be 32 bytes".to_string())?;
+    ed25519_dalek::VerifyingKey::from_bytes(bytes)
+        .map_err(|_| "Invalid Ed25519 public key".to_string())
+}
+
+const X25519_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.110");
+
+pub(super) enum X25519Key {
+    Private(x25519_dalek::StaticSecret),
+    Public(x25519_dalek::PublicKey),
+}
+
+impl X25519Key {
+    /// 32 raw bytes are a private key if `raw_private` is set, otherwise a
+    /// public key
+    pub(super) fn parse(data: &KeyData, raw_private: bool) -> Result<Self, String> {
+        if let KeyData::Jwk(_, jwk) = data {
+            if jwk.get("crv").and_then(Value::as_str) != Some("X25519") {
+                return Err("JWK is not an X25519 key".to_string());
+            }
+            return match (data.jwk_field("d")?, data.jwk_field("x")?) {
+                (Some(d), _) => x25519_bytes(&d).map(|d| X25519Key::Private(d.into())),
+                (None, Some(x)) => x25519_bytes(&x).map(|x| X25519Key::Public(x.into())),
+                (None, None) => Err("JWK has no \"x\" or \"d\"".to_string()),
+            };
+        }
+
+        let der = data.der().unwrap_or_default();
+        if let Ok(info) = PrivateKeyInfo::try_from(der) {
+            if info.algorithm.oid != X25519_OID {
+                return Err("Not an X25519 key".to_string());
+            }
+            // The key is an OCTET STRING inside the PKCS#8 OCTET STRING
+            let key = info
+                .private_key
+                .strip_prefix(&[0x04, 0x20])
+                .unwrap_or_default();
+            return x25519_bytes(key).map(|key| X25519Key::Private(key.into()));
+        }
+        if let Ok(info) = SubjectPublicKeyInfoRef::try_from(der) {
+            if info.algorithm.oid != X25519_OID {
+                return Err("Not an X25519 key".to_string());
+            }
+            let key = info.subject_public_key.raw_bytes();
+            return x25519_bytes(key).map(|key| X25519Key::Public(key.into()));
+        }
+        match data {
+            KeyData::Bytes(raw) if raw_private => {
+                x25519_bytes(raw).map(|key| X25519Key::Private(key.into()))
+            }
+            KeyData::Bytes(raw) => x25519_bytes(raw).map(|key| X25519Key::Public(key.into())),
+            _ => Err("Not an X25519 key".to_string()),
+        }
+    }
+
+    pub(super) fn public_key(&self) -> x25519_dalek::PublicKey {
+        match self {
+            X25519Key::Private(key) => key.into(),
+            X25519Key::Public(key) => *key,
+        }
+    }
+}
+
+fn x25519_bytes(bytes: &[u8]) -> Result<[u8; 32], String> {
+    bytes
+        .try_into()
+        .map_err(|_| "X25519 key must be 32 bytes".to_string())
+}
+
+/// A key on one of the NIST or SECG curves
+pub(super) enum EcKey<C: CurveArithmetic> {
+    Private(SecretKey<C>),
+    Public(PublicKey<C>),
+}
+
+impl<C> EcKey<C>
+where
+    C: CurveArithmetic + AssociatedOid + JwkParameters + PointCompression,
+    AffinePoint<C>: FromEncodedPoint<C> + ToEncodedPoint<C>,
+    FieldBytesSize<C>: ModulusSize,
+{
+    /// Raw keys are told apart by length: a private scalar is as long as a
+    /// field element, a public point is SEC1 encoded
+    pub(super) fn parse(data: &KeyData, curve: &str) -> Result<Self, String> {
+        let not_curve = || format!("Not a {} key", curve);
+        if let KeyData::Jwk(text, jwk) = data {
+            return if jwk.contains_key("d") {
+                SecretKey::from_jwk_str(text).map(EcKey::Private)
+            } else {
+                PublicKey::from_jwk_str(text).map(EcKey::Public)
+            }
+            .map_err(|_| format!("JWK is not a {} key", curve));
+        }
+
+        let der = data.der().unwrap_or_default();
+        if let Ok(key) = SecretKey::from_pkcs8_der(der).or_else(|_| SecretKey::from_sec1_der(der)) {
+            return Ok(EcKey::Private(key));
+        }
+        if let Ok(key) = PublicKey::from_public_key_der(der) {
+            return Ok(EcKey::Public(key));
+        }
+        match data {
+            KeyData::Bytes(raw) if raw.len() == FieldBytesSize::<C>::USIZE => {
+                SecretKey::from_slice(raw)
+                    .map(EcKey::Private)
+                    .map_err(|_| not_curve())
+            }
+            KeyData::Bytes(raw) => PublicKey::from_sec1_bytes(raw)
+                .map(EcKey::Public)
+                .map_err(|_| not_curve()),
+            _ => Err(not_curve()),
+        }
+    }
+
+    pub(super) fn public_key(&self) -> PublicKey<C> {
+        match self {
+            EcKey::Private(key) => key.public_key(),
+            EcKey::Public(key) => *key,
+        }
+    }
+}
+
+pub(super) enum RsaKey {
+    Private(Box<RsaPrivateKey>),
+    Public(RsaPublicKey),
+}
+
+impl RsaKey {
+    pub(super) fn parse(data: &KeyData) -> Result<Self, String> {
+        if let KeyData::Jwk(_, jwk) = data {
+            if jwk.get("kty").and_then(Value::as_str) != Some("RSA") {
+                return Err("JWK is not an RSA key".to_string());
+            }
+            let field = |name| {
+                data.jwk_field(name)?
+                    .map(|bytes| BigUint::from_bytes_be(&bytes))
+                    .ok_or_else(|| format!("JWK has no \"{}\"", name))
+            };
+            let (n, e) = (field("n")?, field("e")?);
+            if !jwk.contains_key("d") {
+                return RsaPublicKey::new(n, e)
+                    .map(RsaKey::Public)
+                    .map_err(|e| e.to_string());
+            }
+            // Primes are recovered from `d` when the JWK leaves them out
+            let primes = match (field("p"), field("q")) {
+                (Ok(p), Ok(q)) => vec![p, q],
+                _ => Vec::new(),
+            };
+            return RsaPrivateKey::from_components(n, e, field("d")?, primes)
+                .map(|key| RsaKey::Private(Box::new(key)))
+                .map_err(|e| e.to_string());
+        }
+
+        let der = data.der().unwrap_or_default();
+        if let Ok(key) =
+            RsaPrivateKey::from_pkcs8_der(der).or_else(|_| RsaPrivateKey::from_pkcs1_der(der))
+        {
+            return Ok(RsaKey::Private(Box::new(key)));
+        }
+        RsaPublicKey::from_public_key_der(der)
+            .or_else(|_| RsaPublicKey::from_pkcs1_der(der))
+            .map(RsaKey::Public)
+            .map_err(|_| "Not an RSA key".to_string())
+    }
+
+    pub(super) fn public_key(&self) -> RsaPublicKey {
+        match self {
+            RsaKey::Private(key) => key.to_public_key(),
+            RsaKey::Public(key) => key.clone(),
+        }
+    }
+}
+
+/// Label and contents of a PEM block, skipping the `EC PARAMETERS` that
+/// `openssl ecparam -genkey` writes first
+fn parse_pem(text: &str) -> Result<(String, Vec<u8>), String> {
+    let (label, der, next) = decode_pem(text)?;
+    if label == "EC PARAMETERS" && next.contains("-----BEGIN ") {
+        return parse_pem(next);
+    }
+    if label.contains("ENCRYPTED") || text.contains("Proc-Type:") {
+        return Err("Encrypted private keys are not supported".to_string());
+    }
+    Ok((label.to_string(), der))
+}
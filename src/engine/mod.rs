@@ -1,5 +1,45 @@
+mod age;
+mod app_password;
+mod asn1;
+mod checksum;
+mod cipher;
+mod crypt;
 mod crypto;
+mod date;
+mod ecdh;
 mod encoding;
+mod fast_hash;
+mod fernet;
+mod identify;
+mod kdf;
+mod keygen;
+mod keys;
+mod openssl;
+mod password;
+mod pgp;
+mod signature;
+mod ssh;
+mod x509;
 
+pub use age::*;
+pub use app_password::*;
+pub use asn1::*;
+pub use checksum::*;
+pub use cipher::*;
+pub use crypt::*;
 pub use crypto::*;
+pub use date::*;
+pub use ecdh::*;
 pub use encoding::*;
+pub use fast_hash::*;
+pub use fernet::*;
+pub use identify::*;
+pub use kdf::*;
+pub use keygen::*;
+pub use keys::*;
+pub use openssl::*;
+pub use password::*;
+pub use pgp::*;
+pub use signature::*;
+pub use ssh::*;
+pub use x509::*;
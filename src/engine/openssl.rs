@@ -0,0 +1,219 @@
+//! Password-based encryption compatible with `openssl enc`
+
+use password_hash::rand_core::{OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use super::{pbkdf2_digest, CipherAlgorithm, CipherDirection, HashAlgorithm};
+
+/// Magic that starts salted `openssl enc` output, followed by the 8 byte salt
+pub const OPENSSL_MAGIC: &[u8] = b"Salted__";
+
+pub const OPENSSL_SALT_SIZE: usize = 8;
+
+/// Digests accepted by `openssl enc -md`
+pub const OPENSSL_DIGESTS: [HashAlgorithm; 5] = [
+    HashAlgorithm::Md5,
+    HashAlgorithm::Sha1,
+    HashAlgorithm::Sha256,
+    HashAlgorithm::Sha384,
+    HashAlgorithm::Sha512,
+];
+
+/// How `openssl enc` turns the password into a key and IV
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum KeyDerivation {
+    /// `-pbkdf2`, with `-iter` iterations
+    #[default]
+    Pbkdf2,
+    /// The legacy default, one round of `EVP_BytesToKey`
+    EvpBytesToKey,
+}
+
+impl KeyDerivation {
+    pub const ALL: [KeyDerivation; 2] = [KeyDerivation::Pbkdf2, KeyDerivation::EvpBytesToKey];
+
+    /// Stable identifier, used for form values
+    pub fn id(&self) -> &'static str {
+        match self {
+            KeyDerivation::Pbkdf2 => "pbkdf2",
+            KeyDerivation::EvpBytesToKey => "evp-bytes-to-key",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|kdf| kdf.id() == id)
+    }
+
+    /// Human readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            KeyDerivation::Pbkdf2 => "PBKDF2",
+            KeyDerivation::EvpBytesToKey => "EVP_BytesToKey (legacy)",
+        }
+    }
+}
+
+/// The `openssl enc` options that affect the output
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Serialize, Deserialize)]
+pub struct OpensslParams {
+    /// AES-CBC, AES-CTR or AES-ECB
+    pub cipher: CipherAlgorithm,
+    /// AES key size in bytes
+    pub key_size: usize,
+    pub kdf: KeyDerivation,
+    pub digest: HashAlgorithm,
+    /// PBKDF2 iterations, ignored by `EVP_BytesToKey`
+    pub iterations: u32,
+}
+
+impl OpensslParams {
+    /// Equivalent command line, without the input and output options
+    pub fn command(&self) -> String {
+        let mut command = format!(
+            "openssl enc -aes-{}-{}",
+            self.key_size * 8,
+            self.cipher.id().trim_start_matches("aes-")
+        );
+        if self.kdf == KeyDerivation::Pbkdf2 {
+            command.push_str(&format!(" -pbkdf2 -iter {}", self.iterations));
+        }
+        command.push_str(&format!(" -md {}", self.digest.id()));
+        command
+    }
+
+    /// Key followed by IV, as the KDF produces them
+    fn derive(&self, password: &[u8], salt: &[u8]) -> Result<(Vec<u8>, Vec<u8>), String> {
+        if self.cipher.is_aead() {
+            return Err(format!(
+                "openssl enc does not support {}",
+                self.cipher.name()
+            ));
+        }
+        if !self.cipher.key_sizes().contains(&self.key_size) {
+            return Err("AES key must be 16, 24 or 32 bytes".to_string());
+        }
+        let length = self.key_size + self.cipher.nonce_size().unwrap_or(0);
+        let mut key = match self.kdf {
+            KeyDerivation::Pbkdf2 => {
+                pbkdf2_digest(self.digest, password, salt, self.iterations, length)?
+            }
+            KeyDerivation::EvpBytesToKey => evp_bytes_to_key(self.digest, password, salt, length),
+        };
+        let iv = key.split_off(self.key_size);
+        Ok((key, iv))
+    }
+
+    /// Encrypt into `Salted__`, the salt and the ciphertext, or just the
+    /// ciphertext when the salt is empty (`-nosalt`)
+    pub fn encrypt(
+        &self,
+        password: &str,
+        salt: &[u8],
+        plaintext: &[u8],
+    ) -> Result<Vec<u8>, String> {
+        if !salt.is_empty() && salt.len() != OPENSSL_SALT_SIZE {
+            return Err(format!("Salt must be {} bytes", OPENSSL_SALT_SIZE));
+        }
+        let (key, iv) = self.derive(password.as_bytes(), salt)?;
+        let ciphertext = self
+            .cipher
+            .apply(CipherDirection::Encrypt, &key, &iv, &[], plaintext)?;
+        if salt.is_empty() {
+            return Ok(ciphertext);
+        }
+        Ok([OPENSSL_MAGIC, salt, &ciphertext].concat())
+    }
+
+    /// Decrypt salted output, or unsalted output when there is no header
+    pub fn decrypt(&self, password: &str, data: &[u8]) -> Result<Vec<u8>, String> {
+        let (salt, ciphertext) = match data.strip_prefix(OPENSSL_MAGIC) {
+            Some(rest) if rest.len() >= OPENSSL_SALT_SIZE => rest.split_at(OPENSSL_SALT_SIZE),
+            Some(_) => return Err("Salted__ header is truncated".to_string()),
+            None => (&[][..], data),
+        };
+        let (key, iv) = self.derive(password.as_bytes(), salt)?;
+        self.cipher
+            .apply(CipherDirection::Decrypt, &key, &iv, &[], ciphertext)
+            .map_err(|_| "Wrong password or options".to_string())
+    }
+}
+
+/// OpenSSL's `EVP_BytesToKey` with a count of 1
+fn evp_bytes_to_key(digest: HashAlgorithm, password: &[u8], salt: &[u8], length: usize) -> Vec<u8> {
+    let mut output = Vec::with_capacity(length);
+    let mut block = Vec::new();
+    while output.len() < length {
+        block = digest.digest(&[&block, password, salt].concat());
+        output.extend_from_slice(&block);
+    }
+    output.truncate(length);
+    output
+}
+
+/// Base64 in 64 character lines, as `openssl enc -a` writes it
+pub fn openssl_base64(data: &[u8]) -> String {
+    base64::encode(data)
+        .as_bytes()
+        .chunks(64)
+        .map(|line| String::from_utf8_lossy(line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn random_openssl_salt() -> [u8; OPENSSL_SALT_SIZE] {
+    let mut salt = [0; OPENSSL_SALT_SIZE];
+    OsRng.fill_bytes(&mut salt);
+    salt
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decrypt(kdf: KeyDerivation, digest: HashAlgorithm, base64: &str) -> Vec<u8> {
+        let params = OpensslParams {
+            cipher: CipherAlgorithm::AesCbc,
+            key_size: 32,
+            kdf,
+            digest,
+            iterations: 10000,
+        };
+        params
+            .decrypt("password", &base64::decode(base64).unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn decrypts_openssl_pbkdf2() {
+        // echo 'Hello, OpenSSL!' | openssl enc -aes-256-cbc -pbkdf2 -pass pass:password -base64
+        let data = "U2FsdGVkX19kqHV+xxMNe5dweKspgZfg+axWLGR9S8WYADdCDXE2PtDcKRQHH8n7";
+        assert_eq!(
+            decrypt(KeyDerivation::Pbkdf2, HashAlgorithm::Sha256, data),
+            b"Hello, OpenSSL!\n"
+        );
+    }
+
+    #[test]
+    fn decrypts_openssl_evp_bytes_to_key() {
+        // echo 'Hello, OpenSSL!' | openssl enc -aes-256-cbc -md md5 -pass pass:password -base64
+        let data = "U2FsdGVkX1+BqNWws/16weoznmpw9EjhPA1RooOA1Axxlhuj0c/9phTpLUhYyljs";
+        assert_eq!(
+            decrypt(KeyDerivation::EvpBytesToKey, HashAlgorithm::Md5, data),
+            b"Hello, OpenSSL!\n"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let params = OpensslParams {
+            cipher: CipherAlgorithm::AesCtr,
+            key_size: 16,
+            kdf: KeyDerivation::Pbkdf2,
+            digest: HashAlgorithm::Sha512,
+            iterations: 1000,
+        };
+        let encrypted = params.encrypt("secret", &[7; 8], b"message").unwrap();
+        assert!(encrypted.starts_with(OPENSSL_MAGIC));
+        assert_eq!(params.decrypt("secret", &encrypted).unwrap(), b"message");
+    }
+}
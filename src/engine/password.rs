@@ -0,0 +1,657 @@
+//! Password hashing schemes, producing and reading their usual string formats
+
+use argon2::Argon2;
+use password_hash::rand_core::{OsRng, RngCore};
+use password_hash::{Ident, PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use pbkdf2::Pbkdf2;
+use scrypt::Scrypt;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    crypt, digests_match, django_pbkdf2_sha256, ldap_salted_hash, lm_hash, mysql_native_password,
+    mysql_old_password, nt_hash, postgres_md5, scram_sha256, sha1_hash, LdapScheme, CRYPT_ALPHABET,
+    EMPTY_LM_HASH,
+};
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum Argon2Variant {
+    Argon2d,
+    Argon2i,
+    #[default]
+    Argon2id,
+}
+
+impl Argon2Variant {
+    pub const ALL: [Argon2Variant; 3] = [
+        Argon2Variant::Argon2d,
+        Argon2Variant::Argon2i,
+        Argon2Variant::Argon2id,
+    ];
+
+    /// Stable identifier, which is also the PHC algorithm name
+    pub fn id(&self) -> &'static str {
+        match self {
+            Argon2Variant::Argon2d => "argon2d",
+            Argon2Variant::Argon2i => "argon2i",
+            Argon2Variant::Argon2id => "argon2id",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|variant| variant.id() == id)
+    }
+
+    /// Human readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Argon2Variant::Argon2d => "Argon2d",
+            Argon2Variant::Argon2i => "Argon2i",
+            Argon2Variant::Argon2id => "Argon2id",
+        }
+    }
+}
+
+/// Hash used inside PBKDF2's HMAC
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Default, Serialize, Deserialize)]
+pub enum Pbkdf2Hash {
+    Sha1,
+    #[default]
+    Sha256,
+    Sha512,
+}
+
+impl Pbkdf2Hash {
+    pub const ALL: [Pbkdf2Hash; 3] = [Pbkdf2Hash::Sha1, Pbkdf2Hash::Sha256, Pbkdf2Hash::Sha512];
+
+    /// Stable identifier, which is also the PHC algorithm name
+    pub fn id(&self) -> &'static str {
+        match self {
+            Pbkdf2Hash::Sha1 => "pbkdf2",
+            Pbkdf2Hash::Sha256 => "pbkdf2-sha256",
+            Pbkdf2Hash::Sha512 => "pbkdf2-sha512",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|hash| hash.id() == id)
+    }
+
+    /// Human readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Pbkdf2Hash::Sha1 => "HMAC-SHA1",
+            Pbkdf2Hash::Sha256 => "HMAC-SHA256",
+            Pbkdf2Hash::Sha512 => "HMAC-SHA512",
+        }
+    }
+}
+
+/// A password hashing scheme together with its cost parameters
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+pub enum PasswordAlgorithm {
+    Bcrypt {
+        cost: u32,
+    },
+    Scrypt {
+        log_n: u8,
+        r: u32,
+        p: u32,
+    },
+    Argon2 {
+        variant: Argon2Variant,
+        /// Memory in KiB
+        memory: u32,
+        iterations: u32,
+        parallelism: u32,
+    },
+    Pbkdf2 {
+        hash: Pbkdf2Hash,
+        iterations: u32,
+    },
+    Md5Crypt,
+    /// Apache's MD5-crypt variant, as written by `htpasswd -m`
+    Apr1,
+    /// `None` uses the default of 5000 rounds, which is left out of the string
+    Sha256Crypt {
+        rounds: Option<u32>,
+    },
+    Sha512Crypt {
+        rounds: Option<u32>,
+    },
+    DesCrypt,
+    /// Unsalted Base64 SHA-1, as written by `htpasswd -s`
+    LdapSha,
+    Nt,
+    Lm,
+    MysqlNative,
+    MysqlOld,
+    PostgresMd5 {
+        user: String,
+    },
+    PostgresScram {
+        iterations: u32,
+    },
+    Django {
+        iterations: u32,
+    },
+    LdapSalted {
+        scheme: LdapScheme,
+    },
+}
+
+impl PasswordAlgorithm {
+    /// Hash `password` with a fresh random salt.
+    ///
+    /// This is deliberately slow, so should be run off the UI thread.
+    pub fn hash(&self, password: &str) -> Result<String, String> {
+        match self {
+            PasswordAlgorithm::Bcrypt { cost } => {
+                let mut salt = [0; 16];
+                OsRng.fill_bytes(&mut salt);
+                bcrypt::hash_with_salt(password.as_bytes(), *cost, salt)
+                    .map(|parts| parts.format_for_version(bcrypt::Version::TwoB))
+                    .map_err(|e| e.to_string())
+            }
+            PasswordAlgorithm::Scrypt { log_n, r, p } => {
+                let params = scrypt::Params::new(*log_n, *r, *p, scrypt::Params::RECOMMENDED_LEN)
+                    .map_err(|e| e.to_string())?;
+                phc_hash(&Scrypt, password.as_bytes(), None, params)
+            }
+            PasswordAlgorithm::Argon2 {
+                variant,
+                memory,
+                iterations,
+                parallelism,
+            } => {
+                let params = argon2::Params::new(*memory, *iterations, *parallelism, None)
+                    .map_err(|e| e.to_string())?;
+                phc_hash(
+                    &Argon2::default(),
+                    password.as_bytes(),
+                    Some(Ident::new_unwrap(variant.id())),
+                    params,
+                )
+            }
+            PasswordAlgorithm::Pbkdf2 { hash, iterations } => {
+                let params = pbkdf2::Params {
+                    rounds: *iterations,
+                    output_length: match hash {
+                        Pbkdf2Hash::Sha1 => 20,
+                        Pbkdf2Hash::Sha256 => 32,
+                        Pbkdf2Hash::Sha512 => 64,
+                    },
+                };
+                phc_hash(
+                    &Pbkdf2,
+                    password.as_bytes(),
+                    Some(Ident::new_unwrap(hash.id())),
+                    params,
+                )
+            }
+            PasswordAlgorithm::Md5Crypt => {
+                crypt(password.as_bytes(), &format!("$1${}", crypt_salt(8)))
+            }
+            PasswordAlgorithm::Apr1 => {
+                crypt(password.as_bytes(), &format!("$apr1${}", crypt_salt(8)))
+            }
+            PasswordAlgorithm::Sha256Crypt { rounds } => {
+                crypt(password.as_bytes(), &sha_crypt_setting("$5$", *rounds))
+            }
+            PasswordAlgorithm::Sha512Crypt { rounds } => {
+                crypt(password.as_bytes(), &sha_crypt_setting("$6$", *rounds))
+            }
+            PasswordAlgorithm::DesCrypt => crypt(password.as_bytes(), &crypt_salt(2)),
+            PasswordAlgorithm::LdapSha => Ok(format!(
+                "{{SHA}}{}",
+                base64::encode(sha1_hash(password.as_bytes()))
+            )),
+            PasswordAlgorithm::Nt => Ok(hex::encode(nt_hash(password))),
+            PasswordAlgorithm::Lm => lm_hash(password).map(hex::encode),
+            PasswordAlgorithm::MysqlNative => Ok(mysql_native_password(password)),
+            PasswordAlgorithm::MysqlOld => Ok(mysql_old_password(password)),
+            PasswordAlgorithm::PostgresMd5 { user } => Ok(postgres_md5(password, user)),
+            PasswordAlgorithm::PostgresScram { iterations } => {
+                scram_sha256(password, &random_bytes(16), *iterations)
+            }
+            PasswordAlgorithm::Django { iterations } => Ok(django_pbkdf2_sha256(
+                password,
+                &alphanumeric_salt(22),
+                *iterations,
+            )),
+            PasswordAlgorithm::LdapSalted { scheme } => {
+                Ok(ldap_salted_hash(*scheme, password, &random_bytes(8)))
+            }
+        }
+    }
+}
+
+/// Check `password` against a bcrypt, crypt(3), LDAP, database, Windows,
+/// Django or PHC string (scrypt, Argon2, PBKDF2), optionally in a
+/// `user:hash` line.
+pub fn verify_password(password: &str, hash: &str) -> Result<bool, String> {
+    let (user, hash) = split_credential_line(hash);
+    if is_bcrypt(hash) {
+        return bcrypt::verify(password, hash).map_err(|e| e.to_string());
+    }
+    if let Some(digest) = hash.strip_prefix("{SHA}") {
+        let digest = base64::decode(digest).map_err(|e| e.to_string())?;
+        return Ok(digests_match(&digest, &sha1_hash(password.as_bytes())));
+    }
+    if let Some((scheme, value)) = LdapScheme::from_hash(hash) {
+        let (digest, salt) = split_ldap_value(scheme, value)?;
+        let mut salted = password.as_bytes().to_vec();
+        salted.extend_from_slice(&salt);
+        return Ok(digests_match(&digest, &scheme.digest(&salted)));
+    }
+    if hash.starts_with("SCRAM-SHA-256$") {
+        let (iterations, salt, _, _) = parse_scram(hash)?;
+        let salt = base64::decode(salt).map_err(|e| e.to_string())?;
+        let computed = scram_sha256(password, &salt, iterations)?;
+        return Ok(digests_match(computed.as_bytes(), hash.as_bytes()));
+    }
+    if hash.starts_with("pbkdf2_sha256$") {
+        let (iterations, salt, _) = parse_django(hash)?;
+        let computed = django_pbkdf2_sha256(password, salt, iterations);
+        return Ok(digests_match(computed.as_bytes(), hash.as_bytes()));
+    }
+    if is_postgres_md5(hash) {
+        let user = user.ok_or(
+            "PostgreSQL md5 hashes are salted with the role name, so need a user:hash line",
+        )?;
+        let computed = postgres_md5(password, user);
+        return Ok(digests_match(
+            computed.as_bytes(),
+            hash.to_ascii_lowercase().as_bytes(),
+        ));
+    }
+    if is_mysql_native(hash) {
+        let computed = mysql_native_password(password);
+        return Ok(digests_match(
+            computed.as_bytes(),
+            hash.to_ascii_uppercase().as_bytes(),
+        ));
+    }
+    if is_hex(hash, 16) {
+        let computed = mysql_old_password(password);
+        return Ok(digests_match(
+            computed.as_bytes(),
+            hash.to_ascii_lowercase().as_bytes(),
+        ));
+    }
+    if is_hex(hash, 32) {
+        // NT and LM hashes look alike, so either may match
+        let hash = hex::decode(hash).map_err(|e| e.to_string())?;
+        let lm_matches = lm_hash(password).is_ok_and(|lm| digests_match(&lm, &hash));
+        return Ok(lm_matches || digests_match(&nt_hash(password), &hash));
+    }
+    if let Some((lm, nt)) = split_lm_nt_pair(hash) {
+        let nt = hex::decode(nt).map_err(|e| e.to_string())?;
+        let lm_matches = lm.eq_ignore_ascii_case(EMPTY_LM_HASH) || {
+            let lm = hex::decode(lm).map_err(|e| e.to_string())?;
+            lm_hash(password).is_ok_and(|computed| digests_match(&computed, &lm))
+        };
+        return Ok(lm_matches && digests_match(&nt_hash(password), &nt));
+    }
+    if is_crypt(hash) {
+        let computed = crypt(password.as_bytes(), hash)?;
+        return Ok(digests_match(computed.as_bytes(), hash.as_bytes()));
+    }
+
+    let parsed = PasswordHash::new(hash).map_err(|e| e.to_string())?;
+    let verifier: &dyn PasswordVerifier = match parsed.algorithm.as_str() {
+        "scrypt" => &Scrypt,
+        "argon2d" | "argon2i" | "argon2id" => &Argon2::default(),
+        "pbkdf2" | "pbkdf2-sha256" | "pbkdf2-sha512" => &Pbkdf2,
+        algorithm => return Err(format!("Unsupported algorithm: {}", algorithm)),
+    };
+    match verifier.verify_password(password.as_bytes(), &parsed) {
+        Ok(()) => Ok(true),
+        Err(password_hash::Error::Password) => Ok(false),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// The fields of a password hash string
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PasswordHashInfo {
+    pub scheme: String,
+    /// Field name and value, in the order they appear
+    pub fields: Vec<(String, String)>,
+}
+
+/// Split a password hash string into its parameters, salt and hash.
+///
+/// `user:hash` lines from htpasswd or `/etc/shadow` are accepted, and the
+/// user is listed as the first field.
+pub fn parse_password_hash(line: &str) -> Result<PasswordHashInfo, String> {
+    let (user, hash) = split_credential_line(line);
+    let mut info = parse_hash_fields(hash)?;
+    if let Some(user) = user {
+        info.fields
+            .insert(0, ("User".to_string(), user.to_string()));
+    }
+    Ok(info)
+}
+
+fn parse_hash_fields(hash: &str) -> Result<PasswordHashInfo, String> {
+    if hash.is_empty() || hash.starts_with('!') || hash == "*" {
+        return Err("Account is locked or has no password".to_string());
+    }
+    if let Some(digest) = hash.strip_prefix("{SHA}") {
+        return Ok(PasswordHashInfo {
+            scheme: "{SHA} (unsalted SHA-1)".to_string(),
+            fields: vec![("Hash".to_string(), digest.to_string())],
+        });
+    }
+    if let Some((scheme, value)) = LdapScheme::from_hash(hash) {
+        let (digest, salt) = split_ldap_value(scheme, value)?;
+        return Ok(PasswordHashInfo {
+            scheme: match scheme {
+                LdapScheme::Ssha => "{SSHA} (salted SHA-1)",
+                LdapScheme::Ssha512 => "{SSHA512} (salted SHA-512)",
+            }
+            .to_string(),
+            fields: vec![
+                ("Salt".to_string(), hex::encode(salt)),
+                ("Hash".to_string(), hex::encode(digest)),
+            ],
+        });
+    }
+    if hash.starts_with("SCRAM-SHA-256$") {
+        let (iterations, salt, stored_key, server_key) = parse_scram(hash)?;
+        return Ok(PasswordHashInfo {
+            scheme: "PostgreSQL SCRAM-SHA-256".to_string(),
+            fields: vec![
+                ("Iterations".to_string(), iterations.to_string()),
+                ("Salt".to_string(), salt.to_string()),
+                ("StoredKey".to_string(), stored_key.to_string()),
+                ("ServerKey".to_string(), server_key.to_string()),
+            ],
+        });
+    }
+    if hash.starts_with("pbkdf2_sha256$") {
+        let (iterations, salt, checksum) = parse_django(hash)?;
+        return Ok(PasswordHashInfo {
+            scheme: "Django PBKDF2-SHA256".to_string(),
+            fields: vec![
+                ("Iterations".to_string(), iterations.to_string()),
+                ("Salt".to_string(), salt.to_string()),
+                ("Hash".to_string(), checksum.to_string()),
+            ],
+        });
+    }
+    let hex_scheme = if is_postgres_md5(hash) {
+        Some("PostgreSQL md5 (salted with the role name)")
+    } else if is_mysql_native(hash) {
+        Some("MySQL 4.1+ PASSWORD()")
+    } else if is_hex(hash, 16) {
+        Some("MySQL OLD_PASSWORD()")
+    } else if is_hex(hash, 32) {
+        Some("NT or LM hash")
+    } else {
+        None
+    };
+    if let Some(scheme) = hex_scheme {
+        return Ok(PasswordHashInfo {
+            scheme: scheme.to_string(),
+            fields: vec![("Hash".to_string(), hash.to_string())],
+        });
+    }
+    if let Some((lm, nt)) = split_lm_nt_pair(hash) {
+        return Ok(PasswordHashInfo {
+            scheme: "LM:NT (pwdump)".to_string(),
+            fields: vec![
+                ("LM hash".to_string(), lm.to_string()),
+                ("NT hash".to_string(), nt.to_string()),
+            ],
+        });
+    }
+    if is_crypt(hash) {
+        return Ok(parse_crypt(hash));
+    }
+    if is_bcrypt(hash) {
+        // $2b$12$ then a 22 character salt and a 31 character hash
+        let version = &hash[1..3];
+        let cost = &hash[4..6];
+        let (salt, checksum) = hash[7..].split_at(22);
+        return Ok(PasswordHashInfo {
+            scheme: "bcrypt".to_string(),
+            fields: vec![
+                ("Version".to_string(), version.to_string()),
+                ("Cost".to_string(), cost.to_string()),
+                ("Salt".to_string(), salt.to_string()),
+                ("Hash".to_string(), checksum.to_string()),
+            ],
+        });
+    }
+
+    let parsed = PasswordHash::new(hash).map_err(|e| e.to_string())?;
+    let mut fields = Vec::new();
+    if let Some(version) = parsed.version {
+        fields.push(("Version".to_string(), version.to_string()));
+    }
+    for (name, value) in parsed.params.iter() {
+        fields.push((name.to_string(), value.to_string()));
+    }
+    if let Some(salt) = parsed.salt {
+        fields.push(("Salt".to_string(), salt.to_string()));
+    }
+    if let Some(checksum) = parsed.hash {
+        fields.push(("Hash".to_string(), checksum.to_string()));
+    }
+    Ok(PasswordHashInfo {
+        scheme: parsed.algorithm.to_string(),
+        fields,
+    })
+}
+
+/// Split `user:hash` (htpasswd), `user:hash:...` (shadow) or
+/// `user:rid:lm:nt:::` (pwdump) into its parts.
+///
+/// A colon only separates a user when the part before it has no `$`, so
+/// hashes that contain colons themselves are left intact.
+fn split_credential_line(line: &str) -> (Option<&str>, &str) {
+    let line = line.trim();
+    if split_lm_nt_pair(line).is_some() {
+        return (None, line);
+    }
+    match line.split_once(':') {
+        Some((user, rest)) if !user.contains('$') && !user.contains('{') => {
+            (Some(user), credential_field(rest))
+        }
+        _ => (None, line),
+    }
+}
+
+/// The hash at the start of the fields after the user
+fn credential_field(fields: &str) -> &str {
+    if fields.starts_with("SCRAM-SHA-256$") {
+        return fields;
+    }
+    if let Some((rid, rest)) = fields.split_once(':') {
+        let pair = rest.get(..65).unwrap_or(rest);
+        if rid.bytes().all(|c| c.is_ascii_digit()) && split_lm_nt_pair(pair).is_some() {
+            return pair;
+        }
+    }
+    fields.split(':').next().unwrap_or_default()
+}
+
+fn is_hex(hash: &str, length: usize) -> bool {
+    hash.len() == length && hash.bytes().all(|c| c.is_ascii_hexdigit())
+}
+
+fn is_mysql_native(hash: &str) -> bool {
+    hash.strip_prefix('*')
+        .is_some_and(|digest| is_hex(digest, 40))
+}
+
+fn is_postgres_md5(hash: &str) -> bool {
+    hash.strip_prefix("md5")
+        .is_some_and(|digest| is_hex(digest, 32))
+}
+
+/// The LM and NT hashes of a pwdump `lm:nt` pair
+fn split_lm_nt_pair(hash: &str) -> Option<(&str, &str)> {
+    hash.split_once(':')
+        .filter(|(lm, nt)| is_hex(lm, 32) && is_hex(nt, 32))
+}
+
+/// Digest and salt of a salted LDAP value
+fn split_ldap_value(scheme: LdapScheme, value: &str) -> Result<(Vec<u8>, Vec<u8>), String> {
+    let mut digest = base64::decode(value).map_err(|e| e.to_string())?;
+    if digest.len() <= scheme.digest_size() {
+        return Err(format!("{} hash has no salt", scheme.prefix()));
+    }
+    let salt = digest.split_off(scheme.digest_size());
+    Ok((digest, salt))
+}
+
+/// Iterations, salt, stored key and server key of
+/// `SCRAM-SHA-256$iterations:salt$StoredKey:ServerKey`
+fn parse_scram(hash: &str) -> Result<(u32, &str, &str, &str), String> {
+    let invalid = || "Invalid SCRAM-SHA-256 verifier".to_string();
+    let (params, keys) = hash
+        .strip_prefix("SCRAM-SHA-256$")
+        .and_then(|rest| rest.split_once('$'))
+        .ok_or_else(invalid)?;
+    let (iterations, salt) = params.split_once(':').ok_or_else(invalid)?;
+    let (stored_key, server_key) = keys.split_once(':').ok_or_else(invalid)?;
+    let iterations = iterations.parse().map_err(|_| invalid())?;
+    Ok((iterations, salt, stored_key, server_key))
+}
+
+/// Iterations, salt and hash of `pbkdf2_sha256$iterations$salt$hash`
+fn parse_django(hash: &str) -> Result<(u32, &str, &str), String> {
+    let invalid = || "Invalid Django PBKDF2 hash".to_string();
+    let mut parts = hash.split('$').skip(1);
+    let (iterations, salt, checksum) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(iterations), Some(salt), Some(checksum)) => (iterations, salt, checksum),
+        _ => return Err(invalid()),
+    };
+    let iterations = iterations.parse().map_err(|_| invalid())?;
+    Ok((iterations, salt, checksum))
+}
+
+fn is_crypt(hash: &str) -> bool {
+    ["$1$", "$apr1$", "$5$", "$6$"]
+        .iter()
+        .any(|prefix| hash.starts_with(prefix))
+        || (hash.len() == 13 && hash.bytes().all(|c| CRYPT_ALPHABET.contains(&c)))
+}
+
+/// Fields of a crypt(3) string: `$id$[rounds=N$]salt$hash`, or DES `sshhhhhhhhhhh`
+fn parse_crypt(hash: &str) -> PasswordHashInfo {
+    let mut parts = hash.split('$').skip(1);
+    let scheme = match parts.next() {
+        Some("1") => "MD5-crypt",
+        Some("apr1") => "Apache MD5 (apr1)",
+        Some("5") => "SHA-256-crypt",
+        Some("6") => "SHA-512-crypt",
+        _ => {
+            return PasswordHashInfo {
+                scheme: "DES crypt".to_string(),
+                fields: vec![
+                    ("Salt".to_string(), hash[..2].to_string()),
+                    ("Hash".to_string(), hash[2..].to_string()),
+                ],
+            }
+        }
+    };
+
+    let mut fields = Vec::new();
+    for part in parts {
+        match part.strip_prefix("rounds=") {
+            Some(rounds) => fields.push(("Rounds".to_string(), rounds.to_string())),
+            None if !fields.iter().any(|(name, _)| name == "Salt") => {
+                fields.push(("Salt".to_string(), part.to_string()))
+            }
+            None => fields.push(("Hash".to_string(), part.to_string())),
+        }
+    }
+    PasswordHashInfo {
+        scheme: scheme.to_string(),
+        fields,
+    }
+}
+
+fn random_bytes(length: usize) -> Vec<u8> {
+    let mut bytes = vec![0; length];
+    OsRng.fill_bytes(&mut bytes);
+    bytes
+}
+
+/// Random salt of letters and digits, as Django generates
+fn alphanumeric_salt(length: usize) -> String {
+    const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+    random_bytes(length)
+        .iter()
+        .map(|byte| ALPHANUMERIC[usize::from(*byte) % ALPHANUMERIC.len()] as char)
+        .collect()
+}
+
+/// Random salt from the crypt alphabet
+fn crypt_salt(length: usize) -> String {
+    random_bytes(length)
+        .iter()
+        .map(|byte| CRYPT_ALPHABET[usize::from(byte & 0x3f)] as char)
+        .collect()
+}
+
+fn sha_crypt_setting(magic: &str, rounds: Option<u32>) -> String {
+    match rounds {
+        Some(rounds) => format!("{}rounds={}${}", magic, rounds, crypt_salt(16)),
+        None => format!("{}{}", magic, crypt_salt(16)),
+    }
+}
+
+fn is_bcrypt(hash: &str) -> bool {
+    hash.len() == 60
+        && hash.is_ascii()
+        && ["$2a$", "$2b$", "$2x$", "$2y$"]
+            .iter()
+            .any(|prefix| hash.starts_with(prefix))
+}
+
+/// Hash with a PHC string format hasher and a random salt
+fn phc_hash<H: PasswordHasher>(
+    hasher: &H,
+    password: &[u8],
+    algorithm: Option<Ident<'_>>,
+    params: H::Params,
+) -> Result<String, String> {
+    let salt = SaltString::generate(&mut OsRng);
+    hasher
+        .hash_password_customized(password, algorithm, None, params, &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// From the OpenBSD/Openwall bcrypt test vectors, password `U*U`
+    const BCRYPT: &str = "$2a$05$CCCCCCCCCCCCCCCCCCCCC.E5YPO9kmyuRGyh0XouQYb4YMJKvyOeW";
+
+    #[test]
+    fn bcrypt_fields() {
+        let info = parse_password_hash(BCRYPT).unwrap();
+        assert_eq!(info.scheme, "bcrypt");
+        assert_eq!(info.fields[1], ("Cost".to_string(), "05".to_string()));
+        assert_eq!(
+            info.fields[2],
+            ("Salt".to_string(), "CCCCCCCCCCCCCCCCCCCCC.".to_string())
+        );
+        assert!(verify_password("U*U", BCRYPT).unwrap());
+    }
+
+    #[test]
+    fn non_ascii_bcrypt_lookalike_is_an_error() {
+        // 60 bytes with a `$2b$` prefix, but the cost straddles a multi-byte character
+        let hash = format!("$2b$1\u{e9}{}", "a".repeat(53));
+        assert_eq!(hash.len(), 60);
+        assert!(parse_password_hash(&hash).is_err());
+        assert!(verify_password("password", &hash).is_err());
+    }
+}
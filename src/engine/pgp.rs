@@ -0,0 +1,753 @@
+//! OpenPGP ASCII armor and packets, as in RFC 9580
+
+use super::{
+    crc, decode_input, format_unix_time, parse_digest, sha1_hash, sha256_hash, CrcParams,
+    InputEncoding,
+};
+
+/// An ASCII armor block: its label, headers and CRC-24 checksum
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PgpArmor {
+    /// What follows `BEGIN PGP`, such as `PUBLIC KEY BLOCK`
+    pub label: String,
+    pub headers: Vec<(String, String)>,
+    /// Checksum from the armor, which RFC 9580 makes optional
+    pub checksum: Option<u32>,
+    /// Checksum of the decoded data
+    pub computed_checksum: u32,
+}
+
+impl PgpArmor {
+    pub fn checksum_matches(&self) -> Option<bool> {
+        self.checksum
+            .map(|checksum| checksum == self.computed_checksum)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PgpPacket {
+    pub offset: usize,
+    pub tag: u8,
+    /// Length of the body, which may have been sent in partial chunks
+    pub length: usize,
+    pub partial: bool,
+    /// Fields decoded from the body, such as key IDs and creation times
+    pub fields: Vec<(&'static str, String)>,
+}
+
+impl PgpPacket {
+    pub fn name(&self) -> &'static str {
+        match self.tag {
+            1 => "Public-Key Encrypted Session Key",
+            2 => "Signature",
+            3 => "Symmetric-Key Encrypted Session Key",
+            4 => "One-Pass Signature",
+            5 => "Secret-Key",
+            6 => "Public-Key",
+            7 => "Secret-Subkey",
+            8 => "Compressed Data",
+            9 => "Symmetrically Encrypted Data",
+            10 => "Marker",
+            11 => "Literal Data",
+            12 => "Trust",
+            13 => "User ID",
+            14 => "Public-Subkey",
+            17 => "User Attribute",
+            18 => "Symmetrically Encrypted and Integrity Protected Data",
+            19 => "Modification Detection Code",
+            20 => "AEAD Encrypted Data",
+            21 => "Padding",
+            _ => "Unknown",
+        }
+    }
+}
+
+/// Binary OpenPGP data from ASCII armor, which is checked but not required,
+/// or from hex or Base64. For cleartext signed messages this is the signature.
+pub fn decode_pgp(text: &str) -> Result<(Option<PgpArmor>, Vec<u8>), String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("Input is empty".to_string());
+    }
+    if !text.contains("-----BEGIN PGP ") {
+        let data = parse_digest(text).map_err(|_| "Input is not armored, hex or Base64")?;
+        return Ok((None, data));
+    }
+
+    let mut rest = text;
+    let (label, body) = loop {
+        let start = rest
+            .find("-----BEGIN PGP ")
+            .ok_or_else(|| "Missing armor header".to_string())?;
+        let (label, after) = rest[start + "-----BEGIN PGP ".len()..]
+            .split_once("-----")
+            .ok_or_else(|| "Invalid armor header".to_string())?;
+        // The signed text of a cleartext signature isn't armored, so skip to
+        // the signature after it
+        if label == "SIGNED MESSAGE" {
+            rest = after;
+            continue;
+        }
+        let end = format!("-----END PGP {}-----", label);
+        let (body, _) = after
+            .split_once(&end)
+            .ok_or_else(|| format!("Missing {}", end))?;
+        break (label.to_string(), body);
+    };
+
+    let mut headers = Vec::new();
+    let mut base64 = String::new();
+    let mut checksum = None;
+    let mut lines = body
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| line.is_empty());
+    for line in lines.by_ref() {
+        match line.split_once(": ") {
+            Some((key, value)) => headers.push((key.to_string(), value.to_string())),
+            None => {
+                base64.push_str(line);
+                break;
+            }
+        }
+    }
+    for line in lines {
+        if let Some(encoded) = line.strip_prefix('=') {
+            let bytes = decode_input(encoded, &InputEncoding::Base64)
+                .map_err(|e| format!("Invalid armor checksum: {}", e))?;
+            let bytes: [u8; 3] = bytes
+                .try_into()
+                .map_err(|_| "Armor checksum must be 3 bytes".to_string())?;
+            checksum = Some(u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]));
+        } else {
+            base64.push_str(line);
+        }
+    }
+    let data = decode_input(&base64, &InputEncoding::Base64)
+        .map_err(|e| format!("Invalid armor: {}", e))?;
+
+    let crc24 = CrcParams::from_name("CRC-24/OPENPGP")
+        .ok_or_else(|| "CRC-24/OPENPGP is not catalogued".to_string())?;
+    let armor = PgpArmor {
+        label,
+        headers,
+        checksum,
+        computed_checksum: crc(&crc24, &data) as u32,
+    };
+    Ok((Some(armor), data))
+}
+
+/// Split OpenPGP data into packets and decode what's readable of each
+pub fn parse_pgp_packets(data: &[u8]) -> Result<Vec<PgpPacket>, String> {
+    if data.is_empty() {
+        return Err("Input is empty".to_string());
+    }
+    let mut packets = Vec::new();
+    let mut reader = Reader::new(data);
+    while !reader.is_empty() {
+        let offset = reader.position;
+        let header = reader.u8()?;
+        if header & 0x80 == 0 {
+            return Err(format!("Invalid packet header at offset {}", offset));
+        }
+        let (tag, body, partial) = if header & 0x40 != 0 {
+            let tag = header & 0x3f;
+            let (body, partial) = new_format_body(&mut reader)?;
+            (tag, body, partial)
+        } else {
+            let tag = (header >> 2) & 0x0f;
+            let length = match header & 0x03 {
+                0 => reader.u8()? as usize,
+                1 => reader.u16()? as usize,
+                2 => reader.u32()? as usize,
+                // Indeterminate length, to the end of the data
+                _ => reader.remaining(),
+            };
+            (tag, reader.bytes(length)?.to_vec(), false)
+        };
+        let fields = match packet_fields(tag, &body) {
+            Ok(fields) => fields,
+            Err(e) => vec![("Error", e)],
+        };
+        packets.push(PgpPacket {
+            offset,
+            tag,
+            length: body.len(),
+            partial,
+            fields,
+        });
+    }
+    Ok(packets)
+}
+
+/// Render packets in the style of `gpg --list-packets`
+pub fn format_pgp_packets(packets: &[PgpPacket]) -> String {
+    let mut output = String::new();
+    for packet in packets {
+        output.push_str(&format!(
+            "offset {}: {} packet (tag {}), {} bytes{}\n",
+            packet.offset,
+            packet.name(),
+            packet.tag,
+            packet.length,
+            if packet.partial { ", partial" } else { "" }
+        ));
+        for (name, value) in &packet.fields {
+            output.push_str(&format!("    {}: {}\n", name, value));
+        }
+    }
+    output
+}
+
+/// Body of a new format packet, joining any partial body chunks
+fn new_format_body(reader: &mut Reader) -> Result<(Vec<u8>, bool), String> {
+    let mut body = Vec::new();
+    let mut partial = false;
+    loop {
+        let first = reader.u8()? as usize;
+        let length = match first {
+            0..=191 => first,
+            192..=223 => ((first - 192) << 8) + reader.u8()? as usize + 192,
+            224..=254 => {
+                partial = true;
+                body.extend_from_slice(reader.bytes(1 << (first & 0x1f))?);
+                continue;
+            }
+            _ => reader.u32()? as usize,
+        };
+        body.extend_from_slice(reader.bytes(length)?);
+        return Ok((body, partial));
+    }
+}
+
+fn packet_fields(tag: u8, body: &[u8]) -> Result<Vec<(&'static str, String)>, String> {
+    let mut reader = Reader::new(body);
+    let mut fields = Vec::new();
+    match tag {
+        1 => {
+            let version = reader.u8()?;
+            fields.push(("Version", version.to_string()));
+            if version == 3 {
+                fields.push(("Key ID", hex::encode_upper(reader.bytes(8)?)));
+                fields.push(("Algorithm", public_key_algorithm(reader.u8()?)));
+            }
+        }
+        2 => signature_fields(&mut reader, &mut fields)?,
+        3 => {
+            fields.push(("Version", reader.u8()?.to_string()));
+            fields.push(("Cipher", symmetric_algorithm(reader.u8()?)));
+        }
+        4 => {
+            let version = reader.u8()?;
+            fields.push(("Version", version.to_string()));
+            fields.push(("Type", signature_type(reader.u8()?)));
+            fields.push(("Hash", hash_algorithm(reader.u8()?)));
+            fields.push(("Algorithm", public_key_algorithm(reader.u8()?)));
+            if version == 3 {
+                fields.push(("Key ID", hex::encode_upper(reader.bytes(8)?)));
+            } else {
+                let salt_length = reader.u8()? as usize;
+                reader.bytes(salt_length)?;
+                fields.push(("Fingerprint", hex::encode_upper(reader.bytes(32)?)));
+            }
+        }
+        5 | 6 | 7 | 14 => {
+            public_key_fields(&mut reader, &mut fields)?;
+            if tag == 5 || tag == 7 {
+                let protection = match reader.u8()? {
+                    0 => "None".to_string(),
+                    usage => format!("Encrypted (S2K usage {})", usage),
+                };
+                fields.push(("Secret key protection", protection));
+            }
+        }
+        8 => {
+            let algorithm = match reader.u8()? {
+                0 => "Uncompressed".to_string(),
+                1 => "ZIP".to_string(),
+                2 => "ZLIB".to_string(),
+                3 => "BZip2".to_string(),
+                id => format!("Unknown ({})", id),
+            };
+            fields.push(("Algorithm", algorithm));
+        }
+        11 => {
+            let format = reader.u8()?;
+            fields.push(("Format", (format as char).to_string()));
+            let name_length = reader.u8()? as usize;
+            let name = String::from_utf8_lossy(reader.bytes(name_length)?).into_owned();
+            if !name.is_empty() {
+                fields.push(("File name", name));
+            }
+            fields.push(("Date", time_field(reader.u32()?)));
+            fields.push(("Data", format!("{} bytes", reader.remaining())));
+        }
+        13 => fields.push(("User ID", String::from_utf8_lossy(body).into_owned())),
+        18 => {
+            let version = reader.u8()?;
+            fields.push(("Version", version.to_string()));
+            if version == 2 {
+                fields.push(("Cipher", symmetric_algorithm(reader.u8()?)));
+                let aead = match reader.u8()? {
+                    1 => "EAX".to_string(),
+                    2 => "OCB".to_string(),
+                    3 => "GCM".to_string(),
+                    id => format!("Unknown ({})", id),
+                };
+                fields.push(("AEAD", aead));
+            }
+        }
+        _ => {}
+    }
+    Ok(fields)
+}
+
+/// Version, creation time, algorithm, size and fingerprint of a key, leaving
+/// the reader after the public part
+fn public_key_fields(
+    reader: &mut Reader,
+    fields: &mut Vec<(&'static str, String)>,
+) -> Result<(), String> {
+    let version = reader.u8()?;
+    fields.push(("Version", version.to_string()));
+    if !(4..=6).contains(&version) {
+        return Ok(());
+    }
+    fields.push(("Created", time_field(reader.u32()?)));
+    let algorithm = reader.u8()?;
+    fields.push(("Algorithm", public_key_algorithm(algorithm)));
+    if version == 4 {
+        key_material_fields(algorithm, reader, fields)?;
+    } else {
+        let length = reader.u32()? as usize;
+        let mut material = Reader::new(reader.bytes(length)?);
+        key_material_fields(algorithm, &mut material, fields)?;
+    }
+
+    let public = &reader.data[..reader.position];
+    let (fingerprint, key_id) = if version == 4 {
+        let mut hashed = vec![0x99];
+        hashed.extend_from_slice(&(public.len() as u16).to_be_bytes());
+        hashed.extend_from_slice(public);
+        let fingerprint = sha1_hash(&hashed);
+        let key_id = fingerprint[12..].to_vec();
+        (fingerprint, key_id)
+    } else {
+        let mut hashed = vec![if version == 5 { 0x9a } else { 0x9b }];
+        hashed.extend_from_slice(&(public.len() as u32).to_be_bytes());
+        hashed.extend_from_slice(public);
+        let fingerprint = sha256_hash(&hashed);
+        let key_id = fingerprint[..8].to_vec();
+        (fingerprint, key_id)
+    };
+    fields.push(("Fingerprint", hex::encode_upper(fingerprint)));
+    fields.push(("Key ID", hex::encode_upper(key_id)));
+    Ok(())
+}
+
+fn key_material_fields(
+    algorithm: u8,
+    reader: &mut Reader,
+    fields: &mut Vec<(&'static str, String)>,
+) -> Result<(), String> {
+    let size = match algorithm {
+        1..=3 => {
+            let (bits, _) = reader.mpi()?;
+            reader.mpi()?;
+            format!("{} bits", bits)
+        }
+        16 | 20 => {
+            let (bits, _) = reader.mpi()?;
+            reader.mpi()?;
+            reader.mpi()?;
+            format!("{} bits", bits)
+        }
+        17 => {
+            let (bits, _) = reader.mpi()?;
+            for _ in 0..3 {
+                reader.mpi()?;
+            }
+            format!("{} bits", bits)
+        }
+        18 | 19 | 22 => {
+            let oid_length = reader.u8()? as usize;
+            let curve = curve_name(reader.bytes(oid_length)?);
+            reader.mpi()?;
+            if algorithm == 18 {
+                let kdf_length = reader.u8()? as usize;
+                reader.bytes(kdf_length)?;
+            }
+            curve
+        }
+        25 => {
+            reader.bytes(32)?;
+            "Curve25519".to_string()
+        }
+        26 => {
+            reader.bytes(56)?;
+            "Curve448".to_string()
+        }
+        27 => {
+            reader.bytes(32)?;
+            "Ed25519".to_string()
+        }
+        28 => {
+            reader.bytes(57)?;
+            "Ed448".to_string()
+        }
+        _ => return Err("Unsupported public key algorithm".to_string()),
+    };
+    fields.push(("Key size", size));
+    Ok(())
+}
+
+fn signature_fields(
+    reader: &mut Reader,
+    fields: &mut Vec<(&'static str, String)>,
+) -> Result<(), String> {
+    let version = reader.u8()?;
+    fields.push(("Version", version.to_string()));
+    if version == 3 {
+        reader.u8()?;
+        fields.push(("Type", signature_type(reader.u8()?)));
+        fields.push(("Created", time_field(reader.u32()?)));
+        fields.push(("Issuer key ID", hex::encode_upper(reader.bytes(8)?)));
+        fields.push(("Algorithm", public_key_algorithm(reader.u8()?)));
+        fields.push(("Hash", hash_algorithm(reader.u8()?)));
+        return Ok(());
+    }
+    if !(4..=6).contains(&version) {
+        return Ok(());
+    }
+    fields.push(("Type", signature_type(reader.u8()?)));
+    fields.push(("Algorithm", public_key_algorithm(reader.u8()?)));
+    fields.push(("Hash", hash_algorithm(reader.u8()?)));
+    for _ in ["hashed", "unhashed"] {
+        let length = if version == 4 {
+            reader.u16()? as usize
+        } else {
+            reader.u32()? as usize
+        };
+        subpacket_fields(reader.bytes(length)?, fields)?;
+    }
+    fields.push(("Hash prefix", hex::encode(reader.bytes(2)?)));
+    Ok(())
+}
+
+/// The signature subpackets worth showing: times, issuer and key flags
+fn subpacket_fields(data: &[u8], fields: &mut Vec<(&'static str, String)>) -> Result<(), String> {
+    let mut reader = Reader::new(data);
+    while !reader.is_empty() {
+        let first = reader.u8()? as usize;
+        let length = match first {
+            0..=191 => first,
+            192..=254 => ((first - 192) << 8) + reader.u8()? as usize + 192,
+            _ => reader.u32()? as usize,
+        };
+        let mut subpacket = Reader::new(reader.bytes(length)?);
+        match subpacket.u8()? & 0x7f {
+            2 => fields.push(("Created", time_field(subpacket.u32()?))),
+            3 => fields.push(("Signature expires", duration_field(subpacket.u32()?))),
+            9 => fields.push(("Key expires", duration_field(subpacket.u32()?))),
+            16 => fields.push(("Issuer key ID", hex::encode_upper(subpacket.bytes(8)?))),
+            27 => {
+                const FLAGS: [&str; 6] = [
+                    "certify",
+                    "sign",
+                    "encrypt communications",
+                    "encrypt storage",
+                    "split key",
+                    "authenticate",
+                ];
+                let flags = subpacket.u8()?;
+                let flags = FLAGS
+                    .iter()
+                    .enumerate()
+                    .filter(|(bit, _)| flags & (1 << bit) != 0)
+                    .map(|(_, flag)| *flag)
+                    .collect::<Vec<_>>();
+                fields.push(("Key flags", flags.join(", ")));
+            }
+            28 => fields.push((
+                "Signer's user ID",
+                String::from_utf8_lossy(subpacket.bytes(subpacket.remaining())?).into_owned(),
+            )),
+            33 => {
+                subpacket.u8()?;
+                let fingerprint = subpacket.bytes(subpacket.remaining())?;
+                fields.push(("Issuer fingerprint", hex::encode_upper(fingerprint)));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn time_field(seconds: u32) -> String {
+    format!("{} ({})", format_unix_time(seconds as u64), seconds)
+}
+
+fn duration_field(seconds: u32) -> String {
+    match seconds {
+        0 => "Never".to_string(),
+        seconds => format!("{} days after creation", seconds / 86400),
+    }
+}
+
+fn public_key_algorithm(id: u8) -> String {
+    let name = match id {
+        1 => "RSA",
+        2 => "RSA (encrypt only)",
+        3 => "RSA (sign only)",
+        16 => "Elgamal",
+        17 => "DSA",
+        18 => "ECDH",
+        19 => "ECDSA",
+        20 => "Elgamal (sign or encrypt)",
+        22 => "EdDSA (legacy)",
+        25 => "X25519",
+        26 => "X448",
+        27 => "Ed25519",
+        28 => "Ed448",
+        _ => return format!("Unknown ({})", id),
+    };
+    name.to_string()
+}
+
+fn hash_algorithm(id: u8) -> String {
+    let name = match id {
+        1 => "MD5",
+        2 => "SHA-1",
+        3 => "RIPEMD-160",
+        8 => "SHA-256",
+        9 => "SHA-384",
+        10 => "SHA-512",
+        11 => "SHA-224",
+        12 => "SHA3-256",
+        14 => "SHA3-512",
+        _ => return format!("Unknown ({})", id),
+    };
+    name.to_string()
+}
+
+fn symmetric_algorithm(id: u8) -> String {
+    let name = match id {
+        0 => "Plaintext",
+        1 => "IDEA",
+        2 => "TripleDES",
+        3 => "CAST5",
+        4 => "Blowfish",
+        7 => "AES-128",
+        8 => "AES-192",
+        9 => "AES-256",
+        10 => "Twofish",
+        11 => "Camellia-128",
+        12 => "Camellia-192",
+        13 => "Camellia-256",
+        _ => return format!("Unknown ({})", id),
+    };
+    name.to_string()
+}
+
+fn signature_type(id: u8) -> String {
+    let name = match id {
+        0x00 => "Binary document",
+        0x01 => "Text document",
+        0x02 => "Standalone",
+        0x10 => "Generic certification",
+        0x11 => "Persona certification",
+        0x12 => "Casual certification",
+        0x13 => "Positive certification",
+        0x18 => "Subkey binding",
+        0x19 => "Primary key binding",
+        0x1f => "Direct key",
+        0x20 => "Key revocation",
+        0x28 => "Subkey revocation",
+        0x30 => "Certification revocation",
+        0x40 => "Timestamp",
+        0x50 => "Third-party confirmation",
+        _ => return format!("Unknown (0x{:02x})", id),
+    };
+    format!("{} (0x{:02x})", name, id)
+}
+
+fn curve_name(oid: &[u8]) -> String {
+    let name = match hex::encode(oid).as_str() {
+        "2a8648ce3d030107" => "NIST P-256",
+        "2b81040022" => "NIST P-384",
+        "2b81040023" => "NIST P-521",
+        "2b8104000a" => "secp256k1",
+        "2b2403030208010107" => "brainpoolP256r1",
+        "2b240303020801010b" => "brainpoolP384r1",
+        "2b240303020801010d" => "brainpoolP512r1",
+        "2b06010401da470f01" => "Ed25519",
+        "2b060104019755010501" => "Curve25519",
+        oid => return format!("Unknown curve ({})", oid),
+    };
+    name.to_string()
+}
+
+/// Bounds-checked reading of big-endian fields
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    fn remaining(&self) -> usize {
+        self.data.len() - self.position
+    }
+
+    fn bytes(&mut self, length: usize) -> Result<&'a [u8], String> {
+        if length > self.remaining() {
+            return Err(format!("Truncated at offset {}", self.position));
+        }
+        let bytes = &self.data[self.position..self.position + length];
+        self.position += length;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.bytes(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        let bytes = self.bytes(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let bytes = self.bytes(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// A multiprecision integer, returning its size in bits and its bytes
+    fn mpi(&mut self) -> Result<(u16, &'a [u8]), String> {
+        let bits = self.u16()?;
+        Ok((bits, self.bytes((bits as usize).div_ceil(8))?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// gpg --quick-gen-key "Test Key <test@example.com>" ed25519, created 2024-01-01
+    const PUBLIC_KEY: &str = "\
+-----BEGIN PGP PUBLIC KEY BLOCK-----
+
+mDMEZZIAgBYJKwYBBAHaRw8BAQdATVuvlX7tW3buhDLynHw6Pu/gcI5L5iLWGeLf
+Cw6lR/y0G1Rlc3QgS2V5IDx0ZXN0QGV4YW1wbGUuY29tPoiQBBMWCAA4FiEEpCse
+3gHduwIGYeIqY8DpIDBOqCEFAmWSAIACGwMFCwkIBwIGFQoJCAsCBBYCAwECHgEC
+F4AACgkQY8DpIDBOqCH2rwD/ZvKUtgRZ00HgJaJN21MfZb9WPkt1MFb7dsoG9+ao
+TakA/iq+rzT81PbRWmoD4jPMgykTTci1H1dnYqBXDhLRCzEE
+=mrTb
+-----END PGP PUBLIC KEY BLOCK-----
+";
+
+    fn field<'a>(packet: &'a PgpPacket, name: &str) -> &'a str {
+        packet
+            .fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value.as_str())
+            .unwrap()
+    }
+
+    #[test]
+    fn public_key_matches_gpg() {
+        let (armor, data) = decode_pgp(PUBLIC_KEY).unwrap();
+        let armor = armor.unwrap();
+        assert_eq!(armor.label, "PUBLIC KEY BLOCK");
+        assert_eq!(armor.checksum_matches(), Some(true));
+
+        // gpg --with-fingerprint --show-keys, which writes old format headers
+        let packets = parse_pgp_packets(&data).unwrap();
+        let summary: Vec<_> = packets
+            .iter()
+            .map(|packet| (packet.offset, packet.tag, packet.length))
+            .collect();
+        assert_eq!(summary, [(0, 6, 51), (53, 13, 27), (82, 2, 144)]);
+        let key = &packets[0];
+        assert_eq!(
+            field(key, "Fingerprint"),
+            "A42B1EDE01DDBB020661E22A63C0E920304EA821"
+        );
+        assert_eq!(field(key, "Key ID"), "63C0E920304EA821");
+        assert_eq!(
+            field(key, "Created"),
+            "2024-01-01 00:00:00 UTC (1704067200)"
+        );
+        assert_eq!(field(&packets[1], "User ID"), "Test Key <test@example.com>");
+        assert_eq!(field(&packets[2], "Issuer key ID"), "63C0E920304EA821");
+    }
+
+    #[test]
+    fn armor_checksum_mismatch() {
+        let tampered = PUBLIC_KEY.replace("=mrTb", "=mrTc");
+        let (armor, _) = decode_pgp(&tampered).unwrap();
+        assert_eq!(armor.unwrap().checksum_matches(), Some(false));
+
+        // RFC 9580 makes the checksum optional
+        let without = PUBLIC_KEY.replace("=mrTb\n", "");
+        let (armor, _) = decode_pgp(&without).unwrap();
+        assert_eq!(armor.unwrap().checksum_matches(), None);
+    }
+
+    #[test]
+    fn new_format_lengths() {
+        let user_id = |length: usize| vec![b'a'; length];
+        // One, two and five octet lengths
+        let mut data = vec![0xcd, 100];
+        data.extend(user_id(100));
+        data.extend([0xcd, 0xc0, 0x08]);
+        data.extend(user_id(200));
+        data.extend([0xcd, 0xff, 0x00, 0x00, 0x20, 0x00]);
+        data.extend(user_id(8192));
+        let packets = parse_pgp_packets(&data).unwrap();
+        let lengths: Vec<_> = packets.iter().map(|packet| packet.length).collect();
+        assert_eq!(lengths, [100, 200, 8192]);
+        assert_eq!(packets[2].offset, 2 + 100 + 3 + 200);
+        assert!(packets.iter().all(|packet| !packet.partial));
+    }
+
+    #[test]
+    fn partial_body_lengths() {
+        // Literal data `b`, no file name, time 0 and `hi`, sent as chunks of
+        // 1, 2 and 4 octets, then a final 1 octet length
+        let data = [
+            0xcb, 0xe0, b'b', 0xe1, 0, 0, 0xe2, 0, 0, 0, b'h', 0x01, b'i',
+        ];
+        let packets = parse_pgp_packets(&data).unwrap();
+        let literal = &packets[0];
+        assert_eq!(
+            (literal.tag, literal.length, literal.partial),
+            (11, 8, true)
+        );
+        assert_eq!(field(literal, "Format"), "b");
+        assert_eq!(field(literal, "Data"), "2 bytes");
+    }
+
+    #[test]
+    fn old_format_lengths() {
+        // Two octet length, then an indeterminate length to the end
+        let mut data = vec![0xb5, 0x01, 0x00];
+        data.extend([b'a'; 256]);
+        data.extend([0xb7, b'x', b'y', b'z']);
+        let packets = parse_pgp_packets(&data).unwrap();
+        let summary: Vec<_> = packets
+            .iter()
+            .map(|packet| (packet.offset, packet.tag, packet.length))
+            .collect();
+        assert_eq!(summary, [(0, 13, 256), (259, 13, 3)]);
+        assert!(parse_pgp_packets(&[0xb4, 10, b'a']).is_err());
+    }
+}
@@ -0,0 +1,252 @@
+//! OpenSSH public keys, as found in `.pub`, `authorized_keys` and `known_hosts` files
+
+use ssh_key::public::KeyData;
+use ssh_key::{EcdsaCurve, Fingerprint, PublicKey};
+
+use super::{md5_hash, sha256_hash};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct SshPublicKey {
+    /// Line of the input the key was on, from 1
+    pub line: usize,
+    /// Algorithm name, such as `ssh-ed25519`
+    pub algorithm: String,
+    /// Key type as `ssh-keygen -l` shows it, such as `ED25519`
+    pub key_type: &'static str,
+    pub bits: Option<usize>,
+    pub comment: String,
+    /// Anything before the key: `authorized_keys` options, or `known_hosts`
+    /// markers and host patterns
+    pub options: String,
+    pub sha256_fingerprint: String,
+    pub md5_fingerprint: String,
+    pub randomart: String,
+}
+
+/// Every key in `text`, one per line, skipping blank lines and `#` comments
+pub fn parse_ssh_public_keys(text: &str) -> Result<Vec<SshPublicKey>, String> {
+    let keys = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+        .map(|(index, line)| {
+            parse_line(index + 1, line).map_err(|e| format!("Line {}: {}", index + 1, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if keys.is_empty() {
+        return Err("No SSH public keys found".to_string());
+    }
+    Ok(keys)
+}
+
+fn parse_line(line_number: usize, line: &str) -> Result<SshPublicKey, String> {
+    let tokens = tokenize(line);
+    // The key is the first token followed by a blob naming the same algorithm,
+    // so options and host patterns before it can be anything
+    let (index, blob) = tokens
+        .windows(2)
+        .enumerate()
+        .find_map(|(index, pair)| {
+            let blob = base64::decode(pair[1].1).ok()?;
+            let name = blob.get(4..4 + pair[0].1.len())?;
+            (blob.get(..4)? == (pair[0].1.len() as u32).to_be_bytes()
+                && name == pair[0].1.as_bytes())
+            .then_some((index, blob))
+        })
+        .ok_or_else(|| "No SSH public key found".to_string())?;
+    let public_key = PublicKey::from_bytes(&blob).map_err(|e| e.to_string())?;
+
+    let (key_start, _) = tokens[index];
+    let (blob_start, blob_text) = tokens[index + 1];
+    let key_data = public_key.key_data();
+    let key_type = key_type(key_data);
+    let bits = key_bits(key_data);
+    let digest: [u8; 32] = sha256_hash(&blob)
+        .try_into()
+        .map_err(|_| "Invalid digest".to_string())?;
+    let header = match bits {
+        Some(bits) => format!("[{} {}]", key_type, bits),
+        None => format!("[{}]", key_type),
+    };
+
+    Ok(SshPublicKey {
+        line: line_number,
+        algorithm: tokens[index].1.to_string(),
+        key_type,
+        bits,
+        comment: line[blob_start + blob_text.len()..].trim().to_string(),
+        options: line[..key_start].trim().to_string(),
+        sha256_fingerprint: sha256_fingerprint(&blob),
+        md5_fingerprint: md5_fingerprint(&blob),
+        randomart: Fingerprint::Sha256(digest).to_randomart(&header),
+    })
+}
+
+/// Whitespace separated tokens with their offsets, keeping double quoted
+/// strings such as `command="echo hello"` together
+fn tokenize(line: &str) -> Vec<(usize, &str)> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    let mut quoted = false;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        if escaped {
+            escaped = false;
+            continue;
+        }
+        match c {
+            '\\' if quoted => escaped = true,
+            '"' => quoted = !quoted,
+            c if c.is_whitespace() && !quoted => {
+                if let Some(start) = start.take() {
+                    tokens.push((start, &line[start..index]));
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(index);
+    }
+    if let Some(start) = start {
+        tokens.push((start, &line[start..]));
+    }
+    tokens
+}
+
+fn key_type(key_data: &KeyData) -> &'static str {
+    match key_data {
+        KeyData::Dsa(_) => "DSA",
+        KeyData::Ecdsa(_) => "ECDSA",
+        KeyData::Ed25519(_) => "ED25519",
+        KeyData::Rsa(_) => "RSA",
+        KeyData::SkEcdsaSha2NistP256(_) => "ECDSA-SK",
+        KeyData::SkEd25519(_) => "ED25519-SK",
+        _ => "UNKNOWN",
+    }
+}
+
+fn key_bits(key_data: &KeyData) -> Option<usize> {
+    let bits = match key_data {
+        KeyData::Dsa(key) => mpint_bits(key.p.as_positive_bytes()?),
+        KeyData::Ecdsa(key) => match key.curve() {
+            EcdsaCurve::NistP256 => 256,
+            EcdsaCurve::NistP384 => 384,
+            EcdsaCurve::NistP521 => 521,
+        },
+        KeyData::Ed25519(_) | KeyData::SkEd25519(_) | KeyData::SkEcdsaSha2NistP256(_) => 256,
+        KeyData::Rsa(key) => mpint_bits(key.n.as_positive_bytes()?),
+        _ => return None,
+    };
+    Some(bits)
+}
+
+fn mpint_bits(bytes: &[u8]) -> usize {
+    match bytes.iter().position(|byte| *byte != 0) {
+        Some(start) => (bytes.len() - start) * 8 - bytes[start].leading_zeros() as usize,
+        None => 0,
+    }
+}
+
+/// `SHA256:` and unpadded Base64, as `ssh-keygen -l` prints it
+pub(super) fn sha256_fingerprint(blob: &[u8]) -> String {
+    format!(
+        "SHA256:{}",
+        base64::encode_config(sha256_hash(blob), base64::STANDARD_NO_PAD)
+    )
+}
+
+/// Colon separated hex, as `ssh-keygen -l -E md5` prints it
+pub(super) fn md5_fingerprint(blob: &[u8]) -> String {
+    let hex = md5_hash(blob)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<Vec<_>>()
+        .join(":");
+    format!("MD5:{}", hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ED25519: &str = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIAmo4Lq+Kb6JwPyD6FulnB9tule4Znro5PD8WwoKL31k alice@example.com";
+    const AUTHORIZED_KEY: &str = r#"command="echo \"hi there\"",no-pty ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBLh1nWcY+kW1skJd9layY1Iy+cnor32YYNBXvXo8GF9QVNQnWe5dUnvCPPdtgCly8qW7V7w5hAsQ+d3n9reGPtc= deploy key"#;
+
+    #[test]
+    fn ed25519_matches_ssh_keygen() {
+        let keys = parse_ssh_public_keys(ED25519).unwrap();
+        let key = &keys[0];
+        assert_eq!(key.algorithm, "ssh-ed25519");
+        assert_eq!((key.key_type, key.bits), ("ED25519", Some(256)));
+        assert_eq!(key.comment, "alice@example.com");
+        assert_eq!(key.options, "");
+        // ssh-keygen -l -v -f key.pub
+        assert_eq!(
+            key.sha256_fingerprint,
+            "SHA256:kTcoJKPs2meIV38JnKC4YhxelPem4SnsloKcuNlGb8o"
+        );
+        // ssh-keygen -l -E md5 -f key.pub
+        assert_eq!(
+            key.md5_fingerprint,
+            "MD5:52:73:0f:17:e3:17:07:0d:86:98:6f:51:6e:f9:13:4f"
+        );
+        assert_eq!(
+            key.randomart,
+            "\
++--[ED25519 256]--+
+|    o .          |
+| . ..+   o       |
+|  oo... + o      |
+| o...o.o o .     |
+|..o...+oS        |
+|o==o..=. .       |
+|O==+++. o        |
+|=Oo=+  .         |
+|ooE+             |
++----[SHA256]-----+"
+        );
+    }
+
+    #[test]
+    fn authorized_keys_options_are_kept_whole() {
+        let keys = parse_ssh_public_keys(AUTHORIZED_KEY).unwrap();
+        let key = &keys[0];
+        assert_eq!(key.options, r#"command="echo \"hi there\"",no-pty"#);
+        assert_eq!(key.algorithm, "ecdsa-sha2-nistp256");
+        assert_eq!((key.key_type, key.bits), ("ECDSA", Some(256)));
+        assert_eq!(key.comment, "deploy key");
+        assert_eq!(
+            key.sha256_fingerprint,
+            "SHA256:wVIrZg6XkJHnjzVLxm/ZlX7+vFxzSjh/tFa30IkN6KM"
+        );
+        assert_eq!(
+            key.md5_fingerprint,
+            "MD5:ef:52:e7:2f:76:4d:43:6d:f4:e2:9b:81:9d:00:22:04"
+        );
+        assert!(key
+            .randomart
+            .starts_with("+---[ECDSA 256]---+\n|    o+  .        |"));
+    }
+
+    #[test]
+    fn tokenize_keeps_quoted_strings_together() {
+        let tokens: Vec<_> = tokenize(r#"a="b c"  d="e \" f" g"#)
+            .into_iter()
+            .map(|(_, token)| token)
+            .collect();
+        assert_eq!(tokens, [r#"a="b c""#, r#"d="e \" f""#, "g"]);
+        assert_eq!(tokenize("  x\ty ")[1], (4, "y"));
+    }
+
+    #[test]
+    fn lines_are_numbered_skipping_comments() {
+        let text = format!("# keys\n\n{}\n{}\n", ED25519, AUTHORIZED_KEY);
+        let lines: Vec<_> = parse_ssh_public_keys(&text)
+            .unwrap()
+            .iter()
+            .map(|key| key.line)
+            .collect();
+        assert_eq!(lines, [3, 4]);
+        assert!(parse_ssh_public_keys("# only a comment").is_err());
+    }
+}
@@ -0,0 +1,888 @@
+//! X.509 certificates, certificate signing requests and revocation lists
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use super::{
+    decode_der_input, decode_pem, format_digest, oid_name, parse_asn1, sha1_hash, sha256_hash,
+    Asn1Element, DigestFormat, TagClass,
+};
+
+/// PEM labels of the structures decoded here. Other blocks in a bundle, such as
+/// private keys, are skipped.
+const X509_LABELS: [&str; 6] = [
+    "CERTIFICATE",
+    "TRUSTED CERTIFICATE",
+    "X509 CERTIFICATE",
+    "CERTIFICATE REQUEST",
+    "NEW CERTIFICATE REQUEST",
+    "X509 CRL",
+];
+
+/// Certificates expiring within this many days are flagged
+pub const EXPIRY_WARNING_DAYS: i64 = 30;
+
+const NOT_X509: &str = "Not a certificate, CSR or CRL";
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum X509Kind {
+    Certificate,
+    Request,
+    Crl,
+}
+
+impl X509Kind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            X509Kind::Certificate => "Certificate",
+            X509Kind::Request => "Certificate signing request",
+            X509Kind::Crl => "Certificate revocation list",
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct X509Time {
+    pub text: String,
+    pub unix: Option<i64>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct X509Extension {
+    pub name: String,
+    pub critical: bool,
+    pub value: String,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct RevokedCertificate {
+    pub serial: String,
+    pub date: X509Time,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum Validity {
+    NotYetValid { days: i64 },
+    Valid { days_left: i64 },
+    ExpiresSoon { days_left: i64 },
+    Expired { days_ago: i64 },
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct X509Object {
+    pub kind: X509Kind,
+    pub version: i64,
+    pub serial: Option<String>,
+    pub subject: Option<String>,
+    pub issuer: Option<String>,
+    /// Start of the validity period, or this update for CRLs
+    pub not_before: Option<X509Time>,
+    /// End of the validity period, or next update for CRLs
+    pub not_after: Option<X509Time>,
+    pub public_key: Option<String>,
+    /// Extensions, and any other attributes of a CSR
+    pub extensions: Vec<X509Extension>,
+    pub revoked: Vec<RevokedCertificate>,
+    pub signature_algorithm: String,
+    pub sha1_fingerprint: Vec<u8>,
+    pub sha256_fingerprint: Vec<u8>,
+    /// Position of the issuing certificate in the bundle
+    pub issuer_index: Option<usize>,
+    subject_der: Option<Vec<u8>>,
+    issuer_der: Option<Vec<u8>>,
+}
+
+/// Certificates, CSRs and CRLs in `text`, either PEM blocks or a single DER
+/// structure in hex or Base64. Certificates come first in chain order, each
+/// followed by its issuer where the bundle has it.
+pub fn parse_x509(text: &str) -> Result<Vec<X509Object>, String> {
+    let mut objects = Vec::new();
+    if text.contains("-----BEGIN ") {
+        let mut rest = text;
+        let mut block = 0;
+        while rest.contains("-----BEGIN ") {
+            let (label, der, next) = decode_pem(rest)?;
+            block += 1;
+            if X509_LABELS.contains(&label) {
+                let object = X509Object::parse(&der)
+                    .map_err(|e| format!("Block {} ({}): {}", block, label, e))?;
+                objects.push(object);
+            }
+            rest = next;
+        }
+        if objects.is_empty() {
+            return Err("No certificate, CSR or CRL PEM blocks found".to_string());
+        }
+    } else {
+        let (_, der) = decode_der_input(text)?;
+        objects.push(X509Object::parse(&der)?);
+    }
+    Ok(chain_order(objects))
+}
+
+impl X509Object {
+    pub fn is_self_issued(&self) -> bool {
+        self.subject_der.is_some() && self.subject_der == self.issuer_der
+    }
+
+    /// Where `now`, in Unix seconds, falls in a certificate's validity period
+    pub fn validity(&self, now: i64) -> Option<Validity> {
+        if self.kind != X509Kind::Certificate {
+            return None;
+        }
+        let not_before = self.not_before.as_ref()?.unix?;
+        let not_after = self.not_after.as_ref()?.unix?;
+        let validity = if now < not_before {
+            Validity::NotYetValid {
+                days: (not_before - now) / 86400,
+            }
+        } else if now > not_after {
+            Validity::Expired {
+                days_ago: (now - not_after) / 86400,
+            }
+        } else {
+            let days_left = (not_after - now) / 86400;
+            if days_left < EXPIRY_WARNING_DAYS {
+                Validity::ExpiresSoon { days_left }
+            } else {
+                Validity::Valid { days_left }
+            }
+        };
+        Some(validity)
+    }
+
+    fn parse(der: &[u8]) -> Result<Self, String> {
+        let elements = parse_asn1(der)?;
+        let outer = &elements[0];
+        let [tbs, algorithm, signature] = outer.children.as_slice() else {
+            return Err(NOT_X509.to_string());
+        };
+        if !outer.is_universal(16) || !tbs.is_universal(16) || !signature.is_universal(3) {
+            return Err(NOT_X509.to_string());
+        }
+        let fields = &tbs.children;
+        let kind = if fields.iter().any(is_time) {
+            X509Kind::Crl
+        } else if fields.len() == 4 && is_context(&fields[3], 0) {
+            X509Kind::Request
+        } else {
+            X509Kind::Certificate
+        };
+        let raw = span(der, outer);
+
+        let mut object = X509Object {
+            kind,
+            version: 1,
+            serial: None,
+            subject: None,
+            issuer: None,
+            not_before: None,
+            not_after: None,
+            public_key: None,
+            extensions: Vec::new(),
+            revoked: Vec::new(),
+            signature_algorithm: algorithm_name(algorithm)?,
+            sha1_fingerprint: sha1_hash(raw),
+            sha256_fingerprint: sha256_hash(raw),
+            issuer_index: None,
+            subject_der: None,
+            issuer_der: None,
+        };
+        match kind {
+            X509Kind::Certificate => object.parse_certificate(der, fields)?,
+            X509Kind::Request => object.parse_request(der, fields)?,
+            X509Kind::Crl => object.parse_crl(der, fields)?,
+        }
+        Ok(object)
+    }
+
+    fn parse_certificate(&mut self, der: &[u8], fields: &[Asn1Element]) -> Result<(), String> {
+        let mut fields = fields.iter().peekable();
+        if let Some(version) = fields.next_if(|field| is_context(field, 0)) {
+            let version = version.children.first().and_then(small_integer);
+            self.version = version.ok_or_else(|| "Invalid version".to_string())? + 1;
+        }
+        let serial = next_field(&mut fields, 2, "serial number")?;
+        next_field(&mut fields, 16, "signature algorithm")?;
+        let issuer = next_field(&mut fields, 16, "issuer")?;
+        let validity = next_field(&mut fields, 16, "validity")?;
+        let subject = next_field(&mut fields, 16, "subject")?;
+        let public_key = next_field(&mut fields, 16, "public key")?;
+
+        let [not_before, not_after] = validity.children.as_slice() else {
+            return Err("Invalid validity".to_string());
+        };
+        self.serial = Some(format_digest(&serial.contents, &DigestFormat::HexColon));
+        self.set_issuer(der, issuer);
+        self.set_subject(der, subject);
+        self.not_before = Some(x509_time(not_before)?);
+        self.not_after = Some(x509_time(not_after)?);
+        self.public_key = Some(public_key_info(public_key));
+        for field in fields {
+            if is_context(field, 3) {
+                let extensions = field.children.first();
+                let extensions = extensions.ok_or_else(|| "Invalid extensions".to_string())?;
+                self.extensions = parse_extensions(extensions)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_request(&mut self, der: &[u8], fields: &[Asn1Element]) -> Result<(), String> {
+        let [version, subject, public_key, attributes] = fields else {
+            return Err(NOT_X509.to_string());
+        };
+        let version = small_integer(version);
+        self.version = version.ok_or_else(|| "Invalid version".to_string())? + 1;
+        self.set_subject(der, subject);
+        self.public_key = Some(public_key_info(public_key));
+        for attribute in &attributes.children {
+            let [oid, values] = attribute.children.as_slice() else {
+                return Err("Invalid attribute".to_string());
+            };
+            let oid = oid.oid().ok_or_else(|| "Invalid attribute".to_string())?;
+            if oid == "1.2.840.113549.1.9.14" {
+                if let Some(extensions) = values.children.first() {
+                    self.extensions.extend(parse_extensions(extensions)?);
+                }
+            } else {
+                let value = values
+                    .children
+                    .iter()
+                    .map(|value| value.text().unwrap_or_else(|| hex::encode(&value.contents)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                self.extensions.push(X509Extension {
+                    name: oid_name(&oid).map(str::to_string).unwrap_or(oid),
+                    critical: false,
+                    value,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_crl(&mut self, der: &[u8], fields: &[Asn1Element]) -> Result<(), String> {
+        let mut fields = fields.iter().peekable();
+        if let Some(version) = fields.next_if(|field| field.is_universal(2)) {
+            let version = small_integer(version);
+            self.version = version.ok_or_else(|| "Invalid version".to_string())? + 1;
+        }
+        next_field(&mut fields, 16, "signature algorithm")?;
+        let issuer = next_field(&mut fields, 16, "issuer")?;
+        let this_update = fields
+            .next()
+            .ok_or_else(|| "Missing this update".to_string())?;
+        self.set_issuer(der, issuer);
+        self.not_before = Some(x509_time(this_update)?);
+        if let Some(next_update) = fields.next_if(|field| is_time(field)) {
+            self.not_after = Some(x509_time(next_update)?);
+        }
+        if let Some(revoked) = fields.next_if(|field| field.is_universal(16)) {
+            for entry in &revoked.children {
+                let [serial, date, rest @ ..] = entry.children.as_slice() else {
+                    return Err("Invalid revoked certificate".to_string());
+                };
+                let reason = match rest.first() {
+                    Some(extensions) => parse_extensions(extensions)?
+                        .into_iter()
+                        .find(|extension| extension.name == "cRLReason")
+                        .map(|extension| extension.value),
+                    None => None,
+                };
+                self.revoked.push(RevokedCertificate {
+                    serial: format_digest(&serial.contents, &DigestFormat::HexColon),
+                    date: x509_time(date)?,
+                    reason,
+                });
+            }
+        }
+        for field in fields {
+            if is_context(field, 0) {
+                let extensions = field.children.first();
+                let extensions = extensions.ok_or_else(|| "Invalid extensions".to_string())?;
+                self.extensions = parse_extensions(extensions)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn set_subject(&mut self, der: &[u8], name: &Asn1Element) {
+        self.subject = Some(format_name(name));
+        self.subject_der = Some(span(der, name).to_vec());
+    }
+
+    fn set_issuer(&mut self, der: &[u8], name: &Asn1Element) {
+        self.issuer = Some(format_name(name));
+        self.issuer_der = Some(span(der, name).to_vec());
+    }
+}
+
+/// Certificates from leaf to root, by matching issuer and subject names, then
+/// any CSRs and CRLs
+fn chain_order(objects: Vec<X509Object>) -> Vec<X509Object> {
+    let issued = |issuer: &X509Object, object: &X509Object| {
+        issuer.kind == X509Kind::Certificate
+            && issuer.subject_der.is_some()
+            && issuer.subject_der == object.issuer_der
+    };
+
+    let (mut remaining, others): (Vec<_>, Vec<_>) = objects
+        .into_iter()
+        .partition(|object| object.kind == X509Kind::Certificate);
+    let mut ordered = Vec::new();
+    while !remaining.is_empty() {
+        // Start each chain from a certificate that issued none of the others
+        let leaf = (0..remaining.len())
+            .find(|&i| {
+                !remaining
+                    .iter()
+                    .enumerate()
+                    .any(|(j, other)| i != j && issued(&remaining[i], other))
+            })
+            .unwrap_or(0);
+        let mut current = remaining.remove(leaf);
+        loop {
+            let issuer = remaining
+                .iter()
+                .position(|candidate| issued(candidate, &current));
+            ordered.push(current);
+            match issuer {
+                Some(issuer) => current = remaining.remove(issuer),
+                None => break,
+            }
+        }
+    }
+    ordered.extend(others);
+
+    let issuer_indexes = (0..ordered.len())
+        .map(|i| (0..ordered.len()).find(|&j| i != j && issued(&ordered[j], &ordered[i])))
+        .collect::<Vec<_>>();
+    for (object, issuer_index) in ordered.iter_mut().zip(issuer_indexes) {
+        object.issuer_index = issuer_index;
+    }
+    ordered
+}
+
+fn next_field<'a>(
+    fields: &mut impl Iterator<Item = &'a Asn1Element>,
+    tag: u32,
+    name: &str,
+) -> Result<&'a Asn1Element, String> {
+    fields
+        .next()
+        .filter(|field| field.is_universal(tag))
+        .ok_or_else(|| format!("Missing {}", name))
+}
+
+/// Encoding of an element, including its header
+fn span<'a>(der: &'a [u8], element: &Asn1Element) -> &'a [u8] {
+    let end_of_contents = if element.indefinite { 2 } else { 0 };
+    &der[element.offset..element.offset + element.header_length + element.length + end_of_contents]
+}
+
+fn is_time(element: &Asn1Element) -> bool {
+    element.is_universal(23) || element.is_universal(24)
+}
+
+fn is_context(element: &Asn1Element, tag: u32) -> bool {
+    element.class == TagClass::ContextSpecific && element.tag == tag
+}
+
+fn small_integer(element: &Asn1Element) -> Option<i64> {
+    if !(element.is_universal(2) || element.is_universal(10)) || element.contents.len() > 8 {
+        return None;
+    }
+    let initial = if element.contents.first()? & 0x80 != 0 {
+        -1
+    } else {
+        0
+    };
+    Some(
+        element
+            .contents
+            .iter()
+            .fold(initial, |value, byte| value << 8 | *byte as i64),
+    )
+}
+
+/// Significant bits of an unsigned big-endian integer
+fn bit_length(contents: &[u8]) -> usize {
+    let contents = match contents.iter().position(|byte| *byte != 0) {
+        Some(start) => &contents[start..],
+        None => return 0,
+    };
+    contents.len() * 8 - contents[0].leading_zeros() as usize
+}
+
+fn x509_time(element: &Asn1Element) -> Result<X509Time, String> {
+    if !is_time(element) {
+        return Err("Invalid time".to_string());
+    }
+    Ok(X509Time {
+        text: element
+            .time()
+            .unwrap_or_else(|| String::from_utf8_lossy(&element.contents).into_owned()),
+        unix: element.unix_time(),
+    })
+}
+
+fn oid_display(element: &Asn1Element) -> Option<String> {
+    let oid = element.oid()?;
+    Some(oid_name(&oid).map(str::to_string).unwrap_or(oid))
+}
+
+fn algorithm_name(algorithm: &Asn1Element) -> Result<String, String> {
+    algorithm
+        .children
+        .first()
+        .and_then(oid_display)
+        .ok_or_else(|| "Invalid signature algorithm".to_string())
+}
+
+/// Distinguished name in the order encoded, such as `C=GB, O=Example, CN=example.com`
+fn format_name(name: &Asn1Element) -> String {
+    let formatted = name
+        .children
+        .iter()
+        .map(|rdn| {
+            rdn.children
+                .iter()
+                .filter_map(|attribute| {
+                    let [oid, value] = attribute.children.as_slice() else {
+                        return None;
+                    };
+                    let oid = oid.oid()?;
+                    let value = value.text().unwrap_or_else(|| hex::encode(&value.contents));
+                    Some(format!("{}={}", attribute_name(&oid), value))
+                })
+                .collect::<Vec<_>>()
+                .join(" + ")
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    if formatted.is_empty() {
+        "(empty)".to_string()
+    } else {
+        formatted
+    }
+}
+
+/// Short names of distinguished name attributes, as in RFC 4514
+fn attribute_name(oid: &str) -> String {
+    let name = match oid {
+        "2.5.4.3" => "CN",
+        "2.5.4.4" => "SN",
+        "2.5.4.5" => "serialNumber",
+        "2.5.4.6" => "C",
+        "2.5.4.7" => "L",
+        "2.5.4.8" => "ST",
+        "2.5.4.9" => "STREET",
+        "2.5.4.10" => "O",
+        "2.5.4.11" => "OU",
+        "2.5.4.42" => "GN",
+        "0.9.2342.19200300.100.1.1" => "UID",
+        "0.9.2342.19200300.100.1.25" => "DC",
+        _ => return oid_name(oid).unwrap_or(oid).to_string(),
+    };
+    name.to_string()
+}
+
+/// Algorithm and size of a SubjectPublicKeyInfo
+fn public_key_info(public_key: &Asn1Element) -> String {
+    let (Some(algorithm), Some(key)) = (public_key.children.first(), public_key.children.get(1))
+    else {
+        return "Unknown".to_string();
+    };
+    let Some(oid) = algorithm.children.first().and_then(|oid| oid.oid()) else {
+        return "Unknown".to_string();
+    };
+    let parameters = algorithm.children.get(1);
+    let key_bits = key.contents.get(1..).unwrap_or_default();
+    match oid.as_str() {
+        "1.2.840.113549.1.1.1" => {
+            let rsa_key = parse_asn1(key_bits).ok().and_then(|elements| {
+                let [modulus, exponent] = elements.first()?.children.as_slice() else {
+                    return None;
+                };
+                Some(format!(
+                    "RSA {} bits, exponent {}",
+                    bit_length(&modulus.contents),
+                    exponent.value()?
+                ))
+            });
+            rsa_key.unwrap_or_else(|| "RSA".to_string())
+        }
+        "1.2.840.10045.2.1" => {
+            let curve = parameters.and_then(|parameters| parameters.oid());
+            let curve = match curve.as_deref() {
+                Some("1.2.840.10045.3.1.7") => "P-256".to_string(),
+                Some("1.3.132.0.34") => "P-384".to_string(),
+                Some("1.3.132.0.35") => "P-521".to_string(),
+                Some(curve) => oid_name(curve).unwrap_or(curve).to_string(),
+                None => "explicit parameters".to_string(),
+            };
+            format!("EC {}", curve)
+        }
+        "1.2.840.10040.4.1" => {
+            let prime = parameters.and_then(|parameters| parameters.children.first());
+            match prime {
+                Some(prime) => format!("DSA {} bits", bit_length(&prime.contents)),
+                None => "DSA".to_string(),
+            }
+        }
+        _ => oid_name(&oid).map(str::to_string).unwrap_or(oid),
+    }
+}
+
+fn parse_extensions(extensions: &Asn1Element) -> Result<Vec<X509Extension>, String> {
+    extensions
+        .children
+        .iter()
+        .map(|extension| {
+            let invalid = || "Invalid extension".to_string();
+            let mut parts = extension.children.iter();
+            let oid = parts.next().and_then(|oid| oid.oid()).ok_or_else(invalid)?;
+            let mut value = parts.next().ok_or_else(invalid)?;
+            let mut critical = false;
+            if value.is_universal(1) {
+                critical = value.contents.first().is_some_and(|byte| *byte != 0);
+                value = parts.next().ok_or_else(invalid)?;
+            }
+            Ok(X509Extension {
+                name: oid_name(&oid).map(str::to_string).unwrap_or(oid.clone()),
+                critical,
+                value: extension_value(&oid, &value.contents),
+            })
+        })
+        .collect()
+}
+
+/// Readable form of common extensions, falling back to hex
+fn extension_value(oid: &str, contents: &[u8]) -> String {
+    let Some(value) = parse_asn1(contents)
+        .ok()
+        .and_then(|elements| elements.into_iter().next())
+    else {
+        return hex::encode(contents);
+    };
+    let formatted = match oid {
+        // Subject and issuer alternative names
+        "2.5.29.17" | "2.5.29.18" => Some(join(value.children.iter().map(general_name))),
+        "2.5.29.15" => Some(key_usage(&value)),
+        "2.5.29.37" => Some(join(value.children.iter().filter_map(oid_display))),
+        "2.5.29.19" => {
+            let mut parts = value.children.iter().peekable();
+            let ca = parts
+                .next_if(|part| part.is_universal(1))
+                .is_some_and(|ca| ca.contents.first().is_some_and(|byte| *byte != 0));
+            match parts.next().and_then(small_integer) {
+                Some(length) => Some(format!("CA: {}, path length: {}", ca, length)),
+                None => Some(format!("CA: {}", ca)),
+            }
+        }
+        "2.5.29.14" => Some(format_digest(&value.contents, &DigestFormat::HexColon)),
+        "2.5.29.35" => value
+            .children
+            .iter()
+            .find(|part| is_context(part, 0))
+            .map(|key_id| format_digest(&key_id.contents, &DigestFormat::HexColon)),
+        "2.5.29.31" => {
+            let mut names = Vec::new();
+            collect_general_names(&value, &mut names);
+            Some(join(names.into_iter()))
+        }
+        // Authority and subject information access
+        "1.3.6.1.5.5.7.1.1" | "1.3.6.1.5.5.7.1.11" => {
+            Some(join(value.children.iter().filter_map(|access| {
+                let [method, location] = access.children.as_slice() else {
+                    return None;
+                };
+                Some(format!(
+                    "{} {}",
+                    oid_display(method)?,
+                    general_name(location)
+                ))
+            })))
+        }
+        "2.5.29.32" => {
+            Some(join(value.children.iter().filter_map(|policy| {
+                policy.children.first().and_then(oid_display)
+            })))
+        }
+        // CRL number and delta CRL indicator
+        "2.5.29.20" | "2.5.29.27" => value.value(),
+        "2.5.29.21" => small_integer(&value).map(|reason| crl_reason(reason).to_string()),
+        _ => None,
+    };
+    formatted.unwrap_or_else(|| hex::encode(contents))
+}
+
+fn join(values: impl Iterator<Item = String>) -> String {
+    values.collect::<Vec<_>>().join(", ")
+}
+
+/// URIs and other names nested anywhere in `element`, as in CRL distribution points
+fn collect_general_names(element: &Asn1Element, names: &mut Vec<String>) {
+    for child in &element.children {
+        if child.class == TagClass::ContextSpecific && !child.constructed {
+            names.push(general_name(child));
+        } else {
+            collect_general_names(child, names);
+        }
+    }
+}
+
+/// A GeneralName in the style of OpenSSL, such as `DNS:example.com`
+fn general_name(name: &Asn1Element) -> String {
+    let contents = &name.contents;
+    let text = || String::from_utf8_lossy(contents).into_owned();
+    if name.class != TagClass::ContextSpecific {
+        return hex::encode(contents);
+    }
+    match name.tag {
+        0 => "othername:<unsupported>".to_string(),
+        1 => format!("email:{}", text()),
+        2 => format!("DNS:{}", text()),
+        4 => match name.children.first() {
+            Some(directory_name) => format!("DirName:{}", format_name(directory_name)),
+            None => "DirName:".to_string(),
+        },
+        6 => format!("URI:{}", text()),
+        7 => match contents.len() {
+            4 => format!(
+                "IP:{}",
+                Ipv4Addr::from(<[u8; 4]>::try_from(&contents[..]).unwrap())
+            ),
+            16 => format!(
+                "IP:{}",
+                Ipv6Addr::from(<[u8; 16]>::try_from(&contents[..]).unwrap())
+            ),
+            _ => format!("IP:{}", hex::encode(contents)),
+        },
+        tag => format!("[{}]:{}", tag, hex::encode(contents)),
+    }
+}
+
+fn key_usage(value: &Asn1Element) -> String {
+    const USAGES: [&str; 9] = [
+        "digitalSignature",
+        "nonRepudiation",
+        "keyEncipherment",
+        "dataEncipherment",
+        "keyAgreement",
+        "keyCertSign",
+        "cRLSign",
+        "encipherOnly",
+        "decipherOnly",
+    ];
+    let bits = value.contents.get(1..).unwrap_or_default();
+    let usages = USAGES
+        .iter()
+        .enumerate()
+        .filter(|(bit, _)| {
+            bits.get(bit / 8)
+                .is_some_and(|byte| byte & (0x80 >> (bit % 8)) != 0)
+        })
+        .map(|(_, usage)| usage.to_string());
+    join(usages)
+}
+
+fn crl_reason(reason: i64) -> &'static str {
+    match reason {
+        0 => "unspecified",
+        1 => "keyCompromise",
+        2 => "cACompromise",
+        3 => "affiliationChanged",
+        4 => "superseded",
+        5 => "cessationOfOperation",
+        6 => "certificateHold",
+        8 => "removeFromCRL",
+        9 => "privilegeWithdrawn",
+        10 => "aACompromise",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A leaf and its root CA, given root first
+    const BUNDLE: &str = "\
+-----BEGIN CERTIFICATE-----
+MIIBpDCCAUqgAwIBAgIBATAKBggqhkjOPQQDAjApMRUwEwYDVQQDDAxUZXN0IFJv
+b3QgQ0ExEDAOBgNVBAoMB1JlY29kZXIwHhcNMjQwMTAxMDAwMDAwWhcNMzQwMTAx
+MDAwMDAwWjApMRUwEwYDVQQDDAxUZXN0IFJvb3QgQ0ExEDAOBgNVBAoMB1JlY29k
+ZXIwWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAAS4SeFxsmMzBNi9c0KBj/S/Y2Tg
+/wFew4yMKQ0Q6iXK+87JxrHkT2Qs5MizKKFIrxTpMWczyr1jRXqvEPlCaTuVo2Mw
+YTAdBgNVHQ4EFgQUIPpv+w++50W2wQM7x33q60hHKwMwHwYDVR0jBBgwFoAUIPpv
++w++50W2wQM7x33q60hHKwMwDwYDVR0TAQH/BAUwAwEB/zAOBgNVHQ8BAf8EBAMC
+AQYwCgYIKoZIzj0EAwIDSAAwRQIgFOcixtJCxa4goqyHIxf6m0rujulLY0G0slam
+qLg2C0kCIQCSOI1pbjHVse5vIvwTKiO/eyPRLU1BUSalepnHQMbBJw==
+-----END CERTIFICATE-----
+-----BEGIN CERTIFICATE-----
+MIIByzCCAXKgAwIBAgICEjQwCgYIKoZIzj0EAwIwKTEVMBMGA1UEAwwMVGVzdCBS
+b290IENBMRAwDgYDVQQKDAdSZWNvZGVyMB4XDTI0MDEwMTAwMDAwMFoXDTI1MDEw
+MTAwMDAwMFowFjEUMBIGA1UEAwwLZXhhbXBsZS5jb20wWTATBgcqhkjOPQIBBggq
+hkjOPQMBBwNCAAS3we9tw0jecPomQn+DRmuYh9ad8dPiH6usvlef8Q+OyV4ZC7r7
+iOvNFDu0gx5yYKJpZnd6BxlU/BVRzSzI2CIfo4GcMIGZMAkGA1UdEwQCMAAwHQYD
+VR0lBBYwFAYIKwYBBQUHAwEGCCsGAQUFBwMCMC0GA1UdEQQmMCSCC2V4YW1wbGUu
+Y29tgg93d3cuZXhhbXBsZS5jb22HBMAAAgEwHQYDVR0OBBYEFO0DpL3EumNb1iSU
+z0DGRYoTxjkFMB8GA1UdIwQYMBaAFCD6b/sPvudFtsEDO8d96utIRysDMAoGCCqG
+SM49BAMCA0cAMEQCIHfwqbZixGrlP/mentrNNiDxgApO9ZsdyR7efkWAv08bAiBr
+iEcGzPqSpvRZSyyzN461ptR0hhbova12xbR+mv3owA==
+-----END CERTIFICATE-----
+";
+
+    const CSR: &str = "\
+-----BEGIN CERTIFICATE REQUEST-----
+MIIBETCBuAIBADAWMRQwEgYDVQQDDAtleGFtcGxlLmNvbTBZMBMGByqGSM49AgEG
+CCqGSM49AwEHA0IABLfB723DSN5w+iZCf4NGa5iH1p3x0+Ifq6y+V5/xD47JXhkL
+uvuI680UO7SDHnJgomlmd3oHGVT8FVHNLMjYIh+gQDA+BgkqhkiG9w0BCQ4xMTAv
+MC0GA1UdEQQmMCSCC2V4YW1wbGUuY29tgg93d3cuZXhhbXBsZS5jb22HBMAAAgEw
+CgYIKoZIzj0EAwIDSAAwRQIhANVxl1HcqlzPhFvJ90Uy0rwVrKktomTsl9Fecfj1
+HqTLAiB23A1rr/hqmANm87xurSdem2yUtUb9NOQZBF0hg6abHA==
+-----END CERTIFICATE REQUEST-----
+";
+
+    const CRL: &str = "\
+-----BEGIN X509 CRL-----
+MIHlMIGNAgEBMAoGCCqGSM49BAMCMCkxFTATBgNVBAMMDFRlc3QgUm9vdCBDQTEQ
+MA4GA1UECgwHUmVjb2RlchcNMjYxMDE5MTAwOTQ5WhcNMjYxMTE4MTAwOTQ5WjAj
+MCECAhI0Fw0yNDA2MDEwMDAwMDBaMAwwCgYDVR0VBAMKAQGgDjAMMAoGA1UdFAQD
+AgEBMAoGCCqGSM49BAMCA0cAMEQCIGxz0KuEBUgz8bT2f3PZ6Bt8AYCZuT80nlhK
+98V+0VDDAiBAOmOLpyiIU7DnGpfjp5dtgZeL+SWAVkJbuuExsuLNbg==
+-----END X509 CRL-----
+";
+
+    fn fingerprint(bytes: &[u8]) -> String {
+        format_digest(bytes, &DigestFormat::HexColon).to_uppercase()
+    }
+
+    fn extension<'a>(object: &'a X509Object, name: &str) -> &'a X509Extension {
+        object
+            .extensions
+            .iter()
+            .find(|extension| extension.name == name)
+            .unwrap()
+    }
+
+    #[test]
+    fn chain_is_ordered_leaf_first() {
+        let objects = parse_x509(BUNDLE).unwrap();
+        let [leaf, root] = objects.as_slice() else {
+            panic!("expected two certificates");
+        };
+        assert_eq!(leaf.subject.as_deref(), Some("CN=example.com"));
+        assert_eq!(leaf.issuer_index, Some(1));
+        assert_eq!(root.subject.as_deref(), Some("CN=Test Root CA, O=Recoder"));
+        assert!(root.is_self_issued());
+        assert_eq!(root.issuer_index, None);
+    }
+
+    #[test]
+    fn certificate_fields() {
+        let objects = parse_x509(BUNDLE).unwrap();
+        let (leaf, root) = (&objects[0], &objects[1]);
+        assert_eq!(leaf.kind, X509Kind::Certificate);
+        assert_eq!(leaf.version, 3);
+        assert_eq!(leaf.serial.as_deref(), Some("12:34"));
+        assert_eq!(leaf.public_key.as_deref(), Some("EC P-256"));
+        assert_eq!(leaf.signature_algorithm, "ecdsa-with-SHA256");
+        assert_eq!(
+            extension(leaf, "subjectAltName").value,
+            "DNS:example.com, DNS:www.example.com, IP:192.0.2.1"
+        );
+        assert_eq!(
+            extension(leaf, "extKeyUsage").value,
+            "serverAuth, clientAuth"
+        );
+        assert_eq!(extension(leaf, "basicConstraints").value, "CA: false");
+        let constraints = extension(root, "basicConstraints");
+        assert_eq!(constraints.value, "CA: true");
+        assert!(constraints.critical);
+    }
+
+    #[test]
+    fn fingerprints_match_openssl() {
+        // openssl x509 -noout -fingerprint -sha256 / -sha1
+        let objects = parse_x509(BUNDLE).unwrap();
+        assert_eq!(
+            fingerprint(&objects[0].sha256_fingerprint),
+            "44:05:0A:4E:B5:6B:B9:C7:6F:04:58:13:54:00:BF:28:96:2E:D7:A5:1A:EB:81:20:6F:AA:F6:AD:A2:A3:4C:FB"
+        );
+        assert_eq!(
+            fingerprint(&objects[0].sha1_fingerprint),
+            "E6:7A:82:9F:C1:2F:C9:62:37:5B:6C:30:94:C0:6A:74:A7:FF:AA:D9"
+        );
+        assert_eq!(
+            fingerprint(&objects[1].sha256_fingerprint),
+            "7A:77:6E:55:BE:2C:38:17:BF:EB:1E:E8:D1:CC:5F:C9:76:29:C2:71:54:9D:6E:D8:24:9D:EC:02:6A:E6:08:DB"
+        );
+    }
+
+    #[test]
+    fn validity() {
+        // Valid from 2024-01-01 to 2025-01-01
+        let leaf = &parse_x509(BUNDLE).unwrap()[0];
+        assert_eq!(
+            leaf.validity(1703894400),
+            Some(Validity::NotYetValid { days: 2 })
+        );
+        assert_eq!(
+            leaf.validity(1717200000),
+            Some(Validity::Valid { days_left: 214 })
+        );
+        assert_eq!(
+            leaf.validity(1734220800),
+            Some(Validity::ExpiresSoon { days_left: 17 })
+        );
+        assert_eq!(
+            leaf.validity(1736553600),
+            Some(Validity::Expired { days_ago: 10 })
+        );
+    }
+
+    #[test]
+    fn request_and_crl_are_classified() {
+        let request = &parse_x509(CSR).unwrap()[0];
+        assert_eq!(request.kind, X509Kind::Request);
+        assert_eq!(request.subject.as_deref(), Some("CN=example.com"));
+        assert_eq!(
+            extension(request, "subjectAltName").value,
+            "DNS:example.com, DNS:www.example.com, IP:192.0.2.1"
+        );
+        assert_eq!(request.validity(1717200000), None);
+
+        let crl = &parse_x509(CRL).unwrap()[0];
+        assert_eq!(crl.kind, X509Kind::Crl);
+        assert_eq!(crl.version, 2);
+        assert_eq!(crl.issuer.as_deref(), Some("CN=Test Root CA, O=Recoder"));
+        assert_eq!(
+            crl.revoked,
+            vec![RevokedCertificate {
+                serial: "12:34".to_string(),
+                date: X509Time {
+                    text: "2024-06-01 00:00:00 UTC".to_string(),
+                    unix: Some(1717200000),
+                },
+                reason: Some("keyCompromise".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn crl_sorts_after_certificates() {
+        let bundle = format!("{}{}", CRL, BUNDLE);
+        let kinds: Vec<_> = parse_x509(&bundle)
+            .unwrap()
+            .iter()
+            .map(|object| object.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [X509Kind::Certificate, X509Kind::Certificate, X509Kind::Crl]
+        );
+    }
+}
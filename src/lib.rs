@@ -0,0 +1,6 @@
+mod app;
+mod components;
+mod engine;
+pub mod worker;
+
+pub use app::App;
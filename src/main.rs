@@ -1,8 +1,4 @@
-mod app;
-mod components;
-mod engine;
-
-use app::App;
+use recoder::App;
 
 fn main() {
     yew::Renderer::<App>::new().render();
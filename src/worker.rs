@@ -0,0 +1,120 @@
+//! Web worker for slow operations, so they don't freeze the UI.
+//!
+//! Built by Trunk from `src/bin/worker.rs`.
+
+use crate::engine::{
+    age_decrypt, age_encrypt, generate_key_pair, verify_password, AgeKeys, KdfAlgorithm, KeyPair,
+    KeyType, OpensslParams, PasswordAlgorithm,
+};
+use gloo_worker::{HandlerId, Worker, WorkerScope};
+use serde::{Deserialize, Serialize};
+
+/// Where Trunk puts the worker script
+pub const WORKER_PATH: &str = "/worker.js";
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum WorkerRequest {
+    Hash {
+        password: String,
+        algorithm: PasswordAlgorithm,
+    },
+    Verify {
+        password: String,
+        hash: String,
+    },
+    AgeEncrypt {
+        plaintext: Vec<u8>,
+        keys: AgeKeys,
+        armor: bool,
+    },
+    AgeDecrypt {
+        ciphertext: Vec<u8>,
+        keys: AgeKeys,
+    },
+    /// `openssl enc`, whose PBKDF2 can take seconds
+    OpensslEncrypt {
+        params: OpensslParams,
+        password: String,
+        salt: Vec<u8>,
+        plaintext: Vec<u8>,
+    },
+    OpensslDecrypt {
+        params: OpensslParams,
+        password: String,
+        ciphertext: Vec<u8>,
+    },
+    GenerateKey(KeyType),
+    DeriveKey {
+        algorithm: KdfAlgorithm,
+        secret: Vec<u8>,
+        salt: Vec<u8>,
+        length: usize,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub enum WorkerResponse {
+    Hashed(Result<String, String>),
+    Verified(Result<bool, String>),
+    /// Encrypted or decrypted age file
+    Age(Result<Vec<u8>, String>),
+    /// Encrypted or decrypted `openssl enc` data
+    Openssl(Result<Vec<u8>, String>),
+    KeyPair(Box<Result<KeyPair, String>>),
+    DerivedKey(Result<Vec<u8>, String>),
+}
+
+pub struct BackgroundWorker;
+
+impl Worker for BackgroundWorker {
+    type Message = ();
+    type Input = WorkerRequest;
+    type Output = WorkerResponse;
+
+    fn create(_scope: &WorkerScope<Self>) -> Self {
+        Self
+    }
+
+    fn update(&mut self, _scope: &WorkerScope<Self>, _msg: Self::Message) {}
+
+    fn received(&mut self, scope: &WorkerScope<Self>, msg: Self::Input, id: HandlerId) {
+        let response = match msg {
+            WorkerRequest::Hash {
+                password,
+                algorithm,
+            } => WorkerResponse::Hashed(algorithm.hash(&password)),
+            WorkerRequest::Verify { password, hash } => {
+                WorkerResponse::Verified(verify_password(&password, &hash))
+            }
+            WorkerRequest::AgeEncrypt {
+                plaintext,
+                keys,
+                armor,
+            } => WorkerResponse::Age(age_encrypt(&plaintext, &keys, armor)),
+            WorkerRequest::AgeDecrypt { ciphertext, keys } => {
+                WorkerResponse::Age(age_decrypt(&ciphertext, &keys))
+            }
+            WorkerRequest::OpensslEncrypt {
+                params,
+                password,
+                salt,
+                plaintext,
+            } => WorkerResponse::Openssl(params.encrypt(&password, &salt, &plaintext)),
+            WorkerRequest::OpensslDecrypt {
+                params,
+                password,
+                ciphertext,
+            } => WorkerResponse::Openssl(params.decrypt(&password, &ciphertext)),
+            WorkerRequest::GenerateKey(key_type) => {
+                WorkerResponse::KeyPair(Box::new(generate_key_pair(key_type)))
+            }
+            WorkerRequest::DeriveKey {
+                algorithm,
+                secret,
+                salt,
+                length,
+            } => WorkerResponse::DerivedKey(algorithm.derive(&secret, &salt, length)),
+        };
+        scope.respond(id, response);
+    }
+}